@@ -354,6 +354,29 @@ export function GetActions(instance: ModuleInstance): CompanionActionDefinitions
 			},
 		},
 
+		presentation_jump: {
+			name: 'Presentation: Skip Slides',
+			description: 'Move forward (or back, with a negative number) by several slides',
+			options: [
+				{
+					type: 'number',
+					id: 'offset',
+					label: 'Offset',
+					default: 2,
+					min: -99,
+					max: 99,
+				},
+			],
+			callback: async (action: CompanionActionEvent) => {
+				const offset = action.options['offset'] as number
+				instance.log('debug', `Presentation: Skip ${offset} slides`)
+				const result = await instance.api.presentationJump(offset)
+				if (!result.success) {
+					instance.log('error', `Presentation jump failed: ${result.error}`)
+				}
+			},
+		},
+
 		presentation_first: {
 			name: 'Presentation: First Slide',
 			description: 'Go to the first slide',
@@ -386,16 +409,10 @@ export function GetActions(instance: ModuleInstance): CompanionActionDefinitions
 			options: [],
 			callback: async () => {
 				instance.log('debug', 'Presentation: Toggle blank')
-				if (instance.presentationStatus?.blanked) {
-					const result = await instance.api.presentationUnblank()
-					if (!result.success) {
-						instance.log('error', `Presentation unblank failed: ${result.error}`)
-					}
-				} else {
-					const result = await instance.api.presentationBlank()
-					if (!result.success) {
-						instance.log('error', `Presentation blank failed: ${result.error}`)
-					}
+				// The server flips the state itself, so this works even when Keynote can't report it
+				const result = await instance.api.presentationToggleBlank()
+				if (!result.success) {
+					instance.log('error', `Presentation toggle blank failed: ${result.error}`)
 				}
 			},
 		},
@@ -103,7 +103,8 @@ export class SermonHelperApi {
 
 		this.commandSlugToId.clear()
 		return data.commands.map((c) => {
-			this.commandSlugToId.set(c.slug, c.id)
+			// Commands arrive oldest first; keep the first id so duplicate slugs resolve deterministically.
+			if (!this.commandSlugToId.has(c.slug)) this.commandSlugToId.set(c.slug, c.id)
 			return {
 				id: c.id,
 				name: c.name,
@@ -212,6 +213,11 @@ export class SermonHelperApi {
 		return sent ? { success: true } : { success: false, error: 'WebSocket not connected' }
 	}
 
+	async presentationJump(offset: number): Promise<{ success: boolean; error?: string }> {
+		const sent = this.sendWsCommand('presentation.jump', { offset })
+		return sent ? { success: true } : { success: false, error: 'WebSocket not connected' }
+	}
+
 	async presentationFirst(): Promise<{ success: boolean; error?: string }> {
 		const sent = this.sendWsCommand('presentation.first')
 		return sent ? { success: true } : { success: false, error: 'WebSocket not connected' }
@@ -232,6 +238,11 @@ export class SermonHelperApi {
 		return sent ? { success: true } : { success: false, error: 'WebSocket not connected' }
 	}
 
+	async presentationToggleBlank(): Promise<{ success: boolean; error?: string }> {
+		const sent = this.sendWsCommand('presentation.toggle_mute')
+		return sent ? { success: true } : { success: false, error: 'WebSocket not connected' }
+	}
+
 	// ── WebSocket management ──────────────────────────────────────────────────
 
 	/** Send a command over the active WebSocket. Returns true if sent. */
@@ -268,11 +279,12 @@ export class SermonHelperApi {
 				this.startPingInterval()
 			})
 
-			this.ws.on('close', () => {
+			this.ws.on('close', (code) => {
 				this.stopPingInterval()
 				this.clearPendingRequests()
 				this.onConnectionChange?.(false)
-				this.scheduleReconnect()
+				// 1001 = server shut down deliberately; back off instead of hammering the port
+				this.scheduleReconnect(code === 1001 ? 30000 : 5000)
 			})
 
 			this.ws.on('error', (error) => {
@@ -349,7 +361,9 @@ export class SermonHelperApi {
 			}
 			case 'error': {
 				const errMsg = message.message as string | undefined
-				if (errMsg === 'unauthorized') {
+				if (errMsg === 'server_shutting_down') {
+					console.info('[SermonHelper] Server is shutting down — will reconnect later')
+				} else if (errMsg === 'unauthorized') {
 					console.error('[SermonHelper] WebSocket auth failed — check the Auth Token in module settings matches the token shown in the Sermon Helper app.')
 					this.onConnectionChange?.(false)
 				} else {
@@ -362,11 +376,11 @@ export class SermonHelperApi {
 		}
 	}
 
-	private scheduleReconnect(): void {
+	private scheduleReconnect(delayMs = 5000): void {
 		if (this.wsReconnectTimer) clearTimeout(this.wsReconnectTimer)
 		this.wsReconnectTimer = setTimeout(() => {
 			this.connectWebSocket()
-		}, 5000)
+		}, delayMs)
 	}
 
 	private startPingInterval(): void {
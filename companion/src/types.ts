@@ -29,6 +29,7 @@ export interface PptFolder {
 	id: string
 	path: string
 	name: string
+	extensions?: string[]
 }
 
 export interface PptFile {
@@ -88,7 +88,7 @@ pub async fn download_shaderfilter() -> Result<PathBuf, String> {
     fs::create_dir_all(&plugin_dir)
         .map_err(|e| format!("Failed to create plugin directory: {}", e))?;
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
 
     let (url, file_name) = resolve_latest_download_url(&client).await?;
 
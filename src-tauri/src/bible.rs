@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
 // V2 API types (nyiregyhazimetodista.hu)
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -103,13 +108,209 @@ fn remove_breaks(html: &str) -> String {
     re.replace_all(html, "").to_string()
 }
 
+/// Which parts of the verse HTML survive cleaning. The default strips
+/// everything, leaving plain text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CleanOptions {
+    /// Keep `<sup>` footnote markers; legacy notes are inlined as `<sup>n</sup>`.
+    pub keep_footnotes: bool,
+    /// Turn `<br>` into newlines instead of dropping it.
+    pub keep_line_breaks: bool,
+    /// Keep section heading text instead of removing it.
+    pub keep_headings: bool,
+}
+
 // Clean HTML from verse text
-fn clean_verse_text(text: &str) -> String {
-    let cleaned = remove_headings(text);
-    let cleaned = remove_breaks(&cleaned);
+fn clean_verse_text(text: &str, opts: CleanOptions) -> String {
+    let cleaned = if opts.keep_headings { text.to_string() } else { remove_headings(text) };
+    let cleaned = if opts.keep_line_breaks {
+        let re = regex::Regex::new(r"<br\s*/?>").unwrap_or_else(|_| regex::Regex::new("").unwrap());
+        re.replace_all(&cleaned, "\n").to_string()
+    } else {
+        remove_breaks(&cleaned)
+    };
     // Remove any remaining HTML tags
     let re = regex::Regex::new(r"<[^>]*>").unwrap_or_else(|_| regex::Regex::new("").unwrap());
-    re.replace_all(&cleaned, "").to_string()
+    if opts.keep_footnotes {
+        let sup = regex::Regex::new(r"^</?sup\b").unwrap_or_else(|_| regex::Regex::new("").unwrap());
+        re.replace_all(&cleaned, |caps: &regex::Captures| {
+            let tag = &caps[0];
+            if sup.is_match(tag) { tag.to_string() } else { String::new() }
+        })
+        .to_string()
+    } else {
+        re.replace_all(&cleaned, "").to_string()
+    }
+}
+
+// Insert `<sup>n</sup>` markers for legacy notes at their character
+// positions (end of the verse when unknown), numbering from `next_number`.
+fn inline_legacy_notes(text: &str, notes: &[LegacyNote], next_number: &mut usize) -> String {
+    let len = text.chars().count();
+    let mut markers: Vec<(usize, String)> = notes
+        .iter()
+        .map(|note| {
+            let pos = note.position.and_then(|p| usize::try_from(p).ok()).unwrap_or(len).min(len);
+            let marker = format!("<sup>{}</sup>", *next_number);
+            *next_number += 1;
+            (pos, marker)
+        })
+        .collect();
+    // Insert from the back so earlier positions stay valid.
+    markers.sort_by_key(|(pos, _)| std::cmp::Reverse(*pos));
+    let mut chars: Vec<String> = text.chars().map(String::from).collect();
+    for (pos, marker) in markers {
+        chars.insert(pos, marker);
+    }
+    chars.concat()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BibleError {
+    /// The API answered with something other than JSON (e.g. an HTML
+    /// maintenance page served with 200).
+    #[error("Bible API unavailable (unexpected {content_type} response)")]
+    UpstreamUnavailable { content_type: String },
+    #[error("Parse error: {0}")]
+    Parse(#[from] reqwest::Error),
+    #[error("Translation '{translation}' is not available from the {backend} API (supported: {})", supported.join(", "))]
+    UnsupportedTranslation {
+        translation: String,
+        backend: &'static str,
+        supported: &'static [&'static str],
+    },
+}
+
+/// Translation codes the V2 API serves (without the frontend's `_v2` suffix).
+pub const V2_TRANSLATIONS: &[&str] = &["UF", "RUF"];
+/// Translation codes used with the legacy szentiras.eu API.
+pub const LEGACY_TRANSLATIONS: &[&str] = &["RUF", "KG", "KNB", "SZIT", "BD", "STL"];
+
+// Reject unknown translations up front; the APIs answer them with errors
+// that don't say what went wrong.
+fn check_translation(
+    translation: &str,
+    backend: &'static str,
+    supported: &'static [&'static str],
+) -> Result<(), BibleError> {
+    if supported.iter().any(|t| t.eq_ignore_ascii_case(translation)) {
+        Ok(())
+    } else {
+        Err(BibleError::UnsupportedTranslation { translation: translation.to_string(), backend, supported })
+    }
+}
+
+impl From<BibleError> for String {
+    fn from(e: BibleError) -> Self {
+        e.to_string()
+    }
+}
+
+const BODY_PREVIEW_CHARS: usize = 200;
+
+// Deserialize a successful response, rejecting non-JSON bodies up front so
+// upstream outages don't surface as opaque parse errors.
+async fn parse_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+    url: &str,
+) -> Result<T, BibleError> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+
+    if !content_type.contains("json") {
+        let body = response.text().await.unwrap_or_default();
+        let preview: String = body.chars().take(BODY_PREVIEW_CHARS).collect();
+        tracing::warn!("Bible API returned {content_type:?} from {url}: {preview}");
+        return Err(BibleError::UpstreamUnavailable {
+            content_type: if content_type.is_empty() { "untyped".to_string() } else { content_type },
+        });
+    }
+
+    Ok(response.json().await?)
+}
+
+// Latest in-flight request per command and caller. A newer request id for the same caller
+// wakes the older request's `cancel` so its reqwest future is dropped.
+struct InFlightFetch {
+    request_id: u64,
+    cancel: Arc<Notify>,
+}
+
+static IN_FLIGHT_FETCHES: LazyLock<Mutex<HashMap<String, InFlightFetch>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Run `fetch` as the latest request for `caller`. Without a request id the
+// fetch runs unconditionally, which keeps older frontends working.
+async fn run_latest<T>(
+    caller: &str,
+    request_id: Option<u64>,
+    fetch: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    let Some(request_id) = request_id else {
+        return fetch.await;
+    };
+
+    let cancel = {
+        let mut in_flight = IN_FLIGHT_FETCHES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(current) = in_flight.get(caller) {
+            if current.request_id > request_id {
+                return Err("superseded".to_string());
+            }
+            current.cancel.notify_one();
+        }
+        let cancel = Arc::new(Notify::new());
+        in_flight.insert(
+            caller.to_string(),
+            InFlightFetch { request_id, cancel: Arc::clone(&cancel) },
+        );
+        cancel
+    };
+
+    let result = tokio::select! {
+        res = fetch => res,
+        _ = cancel.notified() => Err("superseded".to_string()),
+    };
+
+    let mut in_flight = IN_FLIGHT_FETCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if in_flight.get(caller).is_some_and(|f| f.request_id == request_id) {
+        in_flight.remove(caller);
+    }
+    result
+}
+
+/// Default V2 API base URL, matching the frontend's `v2ApiUrl`. Used by
+/// server-side lookups that have no frontend to supply one.
+pub(crate) const V2_API_URL: &str = "https://api.nyiregyhazimetodista.hu";
+
+/// `app-settings.json` key holding the V2 API base URL the frontend uses.
+const V2_API_URL_SETTING: &str = "bible_v2_api_url";
+
+/// Remember the frontend's V2 API base URL, so server-side lookups (the
+/// scripture caption) query the same API.
+#[tauri::command]
+pub async fn set_bible_api_url(url: String, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+    let url = url.trim().trim_end_matches('/');
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid API URL: {url}"));
+    }
+    let store = app.store("app-settings.json").map_err(|e| e.to_string())?;
+    store.set(V2_API_URL_SETTING, serde_json::Value::String(url.to_string()));
+    store.save().map_err(|e| e.to_string())
+}
+
+/// The stored V2 API base URL, or [`V2_API_URL`] when none was set.
+pub(crate) fn configured_v2_api_url(app: Option<&tauri::AppHandle>) -> String {
+    use tauri_plugin_store::StoreExt;
+    app.and_then(|app| app.store("app-settings.json").ok())
+        .and_then(|store| store.get(V2_API_URL_SETTING))
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| V2_API_URL.to_string())
 }
 
 // V2 API: Fetch verses directly (immediate results)
@@ -118,42 +319,458 @@ pub async fn fetch_bible_v2(
     reference: String,
     translation: String,
     api_url: String,
+    request_id: Option<u64>,
+    caller: Option<String>,
+    clean: Option<CleanOptions>,
+) -> Result<V2SuggestResponse, String> {
+    let caller = format!("fetch_bible_v2:{}", caller.unwrap_or_default());
+    run_latest(
+        &caller,
+        request_id,
+        fetch_v2(reference, translation, api_url, clean.unwrap_or_default()),
+    )
+    .await
+}
+
+pub(crate) async fn fetch_v2(
+    reference: String,
+    translation: String,
+    api_url: String,
+    clean: CleanOptions,
 ) -> Result<V2SuggestResponse, String> {
+    let mut data = fetch_v2_raw(reference, translation, api_url).await?;
+
+    // Clean up verse text
+    for verse in &mut data.verses {
+        verse.text = clean_verse_text(&verse.text, clean);
+    }
+
+    Ok(data)
+}
+
+// Fetch without cleaning verse text, for callers that clean incrementally.
+async fn fetch_v2_raw(
+    reference: String,
+    translation: String,
+    api_url: String,
+) -> Result<V2SuggestResponse, String> {
+    check_translation(&translation, "V2", V2_TRANSLATIONS)?;
     let url = format!("{}/suggest/{}/{}", api_url, urlencoding::encode(&reference), translation);
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    let client = crate::http::http_client();
+    let response = match client.get(&url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            if let Some(data) = read_offline(&reference, &translation).await {
+                tracing::info!("V2 API unreachable ({e}); serving {reference} from the offline store");
+                return Ok(data);
+            }
+            return Err(format!("Request failed: {}", e));
+        }
+    };
 
     if !response.status().is_success() {
         return Err(format!("API error: {} - {}", response.status(), url));
     }
 
-    let mut data: V2SuggestResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+    let data: V2SuggestResponse = parse_json(response, &url).await?;
 
-    // Clean up verse text
-    for verse in &mut data.verses {
-        verse.text = clean_verse_text(&verse.text);
-    }
+    remember_book_names(&data.parsed_refs);
 
     Ok(data)
 }
 
+// Root of the downloaded translations (`<app data>/bible`), set at startup.
+static OFFLINE_DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+
+/// Serve V2 lookups from translations saved by `download_bible_translation`
+/// when the API can't be reached.
+pub fn set_offline_dir(dir: std::path::PathBuf) {
+    let _ = OFFLINE_DIR.set(dir);
+}
+
+// Answer a single-chapter reference ("Jn 3", "Jn 3,16", "Jn 3,16-18") from
+// the offline store. Anything more complex, or a chapter that wasn't
+// downloaded, yields `None` and the network error stands.
+async fn read_offline(reference: &str, translation: &str) -> Option<V2SuggestResponse> {
+    let dir = OFFLINE_DIR.get()?.join(translation);
+    let normalized = normalize_reference(reference.to_string()).ok()?.normalized;
+    let (book, locator) = normalized.rsplit_once(' ')?;
+    let book_index = CANON.iter().position(|(name, _)| *name == book)?;
+    let (chapter, verses) = match locator.split_once(',') {
+        Some((chapter, verses)) => (chapter, Some(verses)),
+        None => (locator, None),
+    };
+    let range = match verses {
+        Some(verses) => {
+            let (from, to) = verses.split_once('-').unwrap_or((verses, verses));
+            Some(from.parse::<i32>().ok()?..=to.parse::<i32>().ok()?)
+        }
+        None => None,
+    };
+    let bytes = tokio::fs::read(chapter_path(&dir, book_index, chapter.parse().ok()?)).await.ok()?;
+    let mut data: V2SuggestResponse = serde_json::from_slice(&bytes).ok()?;
+    if let Some(range) = range {
+        let keep: Vec<bool> = data.verses.iter().map(|v| range.contains(&v.verse)).collect();
+        if data.verses_as_text.len() == keep.len() {
+            let mut keep_text = keep.iter();
+            data.verses_as_text.retain(|_| *keep_text.next().unwrap_or(&false));
+        }
+        let mut keep_verse = keep.iter();
+        data.verses.retain(|_| *keep_verse.next().unwrap_or(&false));
+        if data.verses.is_empty() {
+            return None;
+        }
+        // The stored label names the whole chapter.
+        data.hungarian_label = normalized.clone();
+        data.label = normalized;
+    }
+    Some(data)
+}
+
+/// Tauri event carrying one cleaned verse of a streamed fetch;
+/// payload `{ streamId, index, verse }`.
+pub const BIBLE_VERSE_EVENT: &str = "bible-verse";
+/// Tauri event ending a streamed fetch; payload
+/// `{ streamId, label, count, error }`.
+pub const BIBLE_COMPLETE_EVENT: &str = "bible-complete";
+
+/// Verses cleaned between yields, so long chapters don't hog the runtime.
+const STREAM_CHUNK: usize = 10;
+
+// V2 API: Like `fetch_bible_v2`, but delivers verses as `bible-verse` events
+// while they are cleaned, then a `bible-complete` event. The V2 API returns
+// a single JSON document, so the post-processing is what gets chunked.
+// `stream_id` is echoed in every payload to tell concurrent streams apart.
+#[tauri::command]
+pub async fn fetch_bible_v2_stream(
+    app: tauri::AppHandle,
+    stream_id: u64,
+    reference: String,
+    translation: String,
+    api_url: String,
+    clean: Option<CleanOptions>,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let clean = clean.unwrap_or_default();
+    let data = match fetch_v2_raw(reference.clone(), translation, api_url).await {
+        Ok(data) => data,
+        Err(e) => {
+            let _ = app.emit(
+                BIBLE_COMPLETE_EVENT,
+                serde_json::json!({ "streamId": stream_id, "label": reference, "count": 0, "error": e }),
+            );
+            return Err(e);
+        }
+    };
+
+    let count = data.verses.len();
+    for (index, mut verse) in data.verses.into_iter().enumerate() {
+        verse.text = clean_verse_text(&verse.text, clean);
+        let _ = app.emit(
+            BIBLE_VERSE_EVENT,
+            serde_json::json!({ "streamId": stream_id, "index": index, "verse": verse }),
+        );
+        if (index + 1) % STREAM_CHUNK == 0 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    let label = if data.hungarian_label.is_empty() { reference } else { data.hungarian_label };
+    let _ = app.emit(
+        BIBLE_COMPLETE_EVENT,
+        serde_json::json!({ "streamId": stream_id, "label": label, "count": count, "error": null }),
+    );
+    Ok(())
+}
+
+// V2 book names seen in parsed references, keyed by `book_id`. The V2 API only
+// accepts human references, so ID-based lookups rebuild one from this table.
+static V2_BOOK_NAMES: LazyLock<Mutex<HashMap<i32, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn remember_book_names(refs: &[V2ParsedRef]) {
+    let mut names = V2_BOOK_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    for r in refs {
+        names.entry(r.book_id).or_insert_with(|| r.book.clone());
+    }
+}
+
+/// How long an ID-based lookup is reused, so prev/next navigation and
+/// prefetched verses don't hit the API again during a service.
+const VERSE_CACHE_TTL: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct VerseKey {
+    book_id: i32,
+    chapter: i32,
+    verse: i32,
+    verse_to: Option<i32>,
+    translation: String,
+    clean: CleanOptions,
+}
+
+// ID-based lookups with their fetch time.
+static VERSE_CACHE: LazyLock<Mutex<HashMap<VerseKey, (Instant, Vec<V2Verse>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// V2 API: Fetch a verse (or a short range up to `verse_to`) by numeric IDs,
+// for prev/next navigation without re-parsing a human reference. With
+// `prefetch_adjacent`, the verse after the result is fetched into the cache
+// in the background.
+#[tauri::command]
+pub async fn fetch_bible_by_ids(
+    book_id: i32,
+    chapter: i32,
+    verse: i32,
+    verse_to: Option<i32>,
+    translation: String,
+    api_url: String,
+    clean: Option<CleanOptions>,
+    prefetch_adjacent: Option<bool>,
+) -> Result<Vec<V2Verse>, String> {
+    if chapter < 1 || verse < 1 {
+        return Err("Chapter and verse must be positive".to_string());
+    }
+    let key = VerseKey {
+        book_id,
+        chapter,
+        verse,
+        verse_to: verse_to.filter(|&to| to > verse),
+        translation,
+        clean: clean.unwrap_or_default(),
+    };
+    let verses = fetch_by_ids_cached(key.clone(), api_url.clone()).await?;
+
+    if prefetch_adjacent.unwrap_or(false) {
+        let next = VerseKey { verse: key.verse_to.unwrap_or(key.verse) + 1, verse_to: None, ..key };
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = fetch_by_ids_cached(next, api_url).await {
+                tracing::debug!("Verse prefetch failed: {e}");
+            }
+        });
+    }
+    Ok(verses)
+}
+
+async fn fetch_by_ids_cached(key: VerseKey, api_url: String) -> Result<Vec<V2Verse>, String> {
+    {
+        let cache = VERSE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((fetched_at, verses)) = cache.get(&key) {
+            if fetched_at.elapsed() < VERSE_CACHE_TTL {
+                return Ok(verses.clone());
+            }
+        }
+    }
+
+    let book = V2_BOOK_NAMES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key.book_id)
+        .cloned()
+        .ok_or_else(|| {
+            format!("Unknown book id {}; look the passage up by reference first", key.book_id)
+        })?;
+
+    let reference = match key.verse_to {
+        Some(to) => format!("{book} {},{}-{to}", key.chapter, key.verse),
+        None => format!("{book} {},{}", key.chapter, key.verse),
+    };
+
+    let data = fetch_v2(reference, key.translation.clone(), api_url, key.clean).await?;
+    // Past the end of a chapter the API answers with no verses; don't pin that.
+    if !data.verses.is_empty() {
+        let mut cache = VERSE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.retain(|_, (fetched_at, _)| fetched_at.elapsed() < VERSE_CACHE_TTL);
+        cache.insert(key, (Instant::now(), data.verses.clone()));
+    }
+    Ok(data.verses)
+}
+
+/// Canonical V2 book abbreviations with the other spellings users type:
+/// full Hungarian names, Catholic (szentiras.eu) abbreviations and English
+/// names. Numbered books list aliases without their number; it is matched
+/// separately.
+const BOOK_ALIASES: &[(&str, &[&str])] = &[
+    ("1Móz", &["1móz", "1mózes", "ter", "teremtés", "gen", "genesis"]),
+    ("2Móz", &["2móz", "2mózes", "kiv", "kivonulás", "ex", "exod", "exodus"]),
+    ("3Móz", &["3móz", "3mózes", "lev", "leviták", "leviticus"]),
+    ("4Móz", &["4móz", "4mózes", "szám", "számok", "num", "numbers"]),
+    ("5Móz", &["5móz", "5mózes", "mtörv", "második törvénykönyv", "deut", "deuteronomy"]),
+    ("Józs", &["józs", "józsué", "josh", "joshua"]),
+    ("Bír", &["bír", "bírák", "judg", "judges"]),
+    ("Ruth", &["ruth", "rut", "ruth könyve"]),
+    ("1Sám", &["1sám", "1sámuel", "1sam", "1samuel"]),
+    ("2Sám", &["2sám", "2sámuel", "2sam", "2samuel"]),
+    ("1Kir", &["1kir", "1királyok", "1kgs", "1kings"]),
+    ("2Kir", &["2kir", "2királyok", "2kgs", "2kings"]),
+    ("1Krón", &["1krón", "1krónika", "1chr", "1chronicles"]),
+    ("2Krón", &["2krón", "2krónika", "2chr", "2chronicles"]),
+    ("Ezsd", &["ezsd", "ezsdrás", "ezd", "ezra"]),
+    ("Neh", &["neh", "nehémiás", "nehemiah"]),
+    ("Eszt", &["eszt", "eszter", "est", "esth", "esther"]),
+    ("Jób", &["jób", "job"]),
+    ("Zsolt", &["zsolt", "zsoltár", "zsoltárok", "ps", "psa", "psalm", "psalms"]),
+    ("Péld", &["péld", "példabeszédek", "prov", "proverbs"]),
+    ("Préd", &["préd", "prédikátor", "koh", "eccl", "ecclesiastes"]),
+    ("Énekek", &["énekek", "én", "énekek éneke", "song", "song of songs"]),
+    ("Ézs", &["ézs", "ézsaiás", "iz", "izajás", "isa", "isaiah"]),
+    ("Jer", &["jer", "jeremiás", "jeremiah"]),
+    ("JSir", &["jsir", "siral", "siralmak", "jeremiás siralmai", "lam", "lamentations"]),
+    ("Ez", &["ez", "ezékiel", "ezek", "ezekiel"]),
+    ("Dán", &["dán", "dániel", "dan", "daniel"]),
+    ("Hós", &["hós", "hóseás", "oz", "hos", "hosea"]),
+    ("Jóel", &["jóel", "joel"]),
+    ("Ám", &["ám", "ámósz", "amos"]),
+    ("Abd", &["abd", "abdiás", "obad", "obadiah"]),
+    ("Jón", &["jón", "jónás", "jonah"]),
+    ("Mik", &["mik", "mikeás", "mic", "micah"]),
+    ("Náh", &["náh", "náhum", "nah", "nahum"]),
+    ("Hab", &["hab", "habakuk", "habakkuk"]),
+    ("Zof", &["zof", "sof", "zofóniás", "zeph", "zephaniah"]),
+    ("Agg", &["agg", "aggeus", "hag", "haggai"]),
+    ("Zak", &["zak", "zakariás", "zech", "zechariah"]),
+    ("Mal", &["mal", "malakiás", "malachi"]),
+    ("Mt", &["mt", "máté", "mát", "matt", "matthew"]),
+    ("Mk", &["mk", "márk", "mark"]),
+    ("Lk", &["lk", "lukács", "luk", "luke"]),
+    ("Jn", &["jn", "ján", "jános", "john"]),
+    ("ApCsel", &["apcsel", "csel", "apostolok cselekedetei", "acts"]),
+    ("Róm", &["róm", "rómaiakhoz", "rom", "romans"]),
+    ("1Kor", &["1kor", "1korinthus", "1korintus", "1cor", "1corinthians"]),
+    ("2Kor", &["2kor", "2korinthus", "2korintus", "2cor", "2corinthians"]),
+    ("Gal", &["gal", "galatákhoz", "galatians"]),
+    ("Ef", &["ef", "efézus", "efezusiakhoz", "eph", "ephesians"]),
+    ("Fil", &["fil", "filippi", "filippiekhez", "phil", "philippians"]),
+    ("Kol", &["kol", "kolossé", "kolosséiakhoz", "col", "colossians"]),
+    ("1Thessz", &["1thessz", "1tesz", "1thesszalonika", "1thess", "1thessalonians"]),
+    ("2Thessz", &["2thessz", "2tesz", "2thesszalonika", "2thess", "2thessalonians"]),
+    ("1Tim", &["1tim", "1timóteus", "1timothy"]),
+    ("2Tim", &["2tim", "2timóteus", "2timothy"]),
+    ("Tit", &["tit", "titusz", "titus"]),
+    ("Filem", &["filem", "filemon", "phlm", "philemon"]),
+    ("Zsid", &["zsid", "zsidókhoz", "heb", "hebrews"]),
+    ("Jak", &["jak", "jakab", "jas", "james"]),
+    ("1Pt", &["1pt", "1pét", "1péter", "1pet", "1peter"]),
+    ("2Pt", &["2pt", "2pét", "2péter", "2pet", "2peter"]),
+    ("1Jn", &["1jn", "1ján", "1jános", "1john"]),
+    ("2Jn", &["2jn", "2ján", "2jános", "2john"]),
+    ("3Jn", &["3jn", "3ján", "3jános", "3john"]),
+    ("Júd", &["júd", "júdás", "jude"]),
+    ("Jel", &["jel", "jelenések", "rev", "revelation"]),
+];
+
+// Lowercase without accents, spaces or dots, so "1. Mózes" and "1moz" meet.
+fn fold_book_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && *c != '.')
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' | 'ö' | 'ő' => 'o',
+            'ú' | 'ü' | 'ű' => 'u',
+            other => other,
+        })
+        .collect()
+}
+
+fn canonical_book(book: &str) -> Option<&'static str> {
+    let folded = fold_book_name(book);
+    // "I. Móz", "II Kor": roman ordinals become digits.
+    let folded = [("iii", "3"), ("ii", "2"), ("i", "1")]
+        .iter()
+        .find_map(|(roman, digit)| {
+            let rest = folded.strip_prefix(roman)?;
+            let is_ordinal = book.trim_start().get(roman.len()..).is_some_and(|r| r.starts_with(['.', ' ']));
+            is_ordinal.then(|| format!("{digit}{rest}"))
+        })
+        .unwrap_or(folded);
+    BOOK_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.iter().any(|a| fold_book_name(a) == folded))
+        .map(|(canonical, _)| *canonical)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedReference {
+    pub normalized: String,
+    /// V2 book id, as the API reported it for this book. `None` for unknown
+    /// book names and for books no V2 response has included yet.
+    pub book_id: Option<i32>,
+}
+
+/// Normalize a typed reference into the form the APIs accept: canonical
+/// book abbreviation, `chapter,verse` with a comma, and single spaces
+/// ("jn3.16" → "Jn 3,16", "I. Kor 13:4 - 7" → "1Kor 13,4-7"). Unknown book
+/// names are passed through as typed.
+#[tauri::command]
+pub fn normalize_reference(input: String) -> Result<NormalizedReference, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Empty reference".to_string());
+    }
+
+    // The book is everything up to the first digit that follows a letter,
+    // so leading ordinals ("1Kor", "2. Sám") stay with the name.
+    let mut seen_letter = false;
+    let split = input
+        .char_indices()
+        .find(|&(_, c)| {
+            if c.is_alphabetic() {
+                seen_letter = true;
+            }
+            seen_letter && c.is_ascii_digit()
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(input.len());
+    let (book_part, locator) = input.split_at(split);
+    let book_part = book_part.trim().trim_end_matches('.').trim();
+
+    let book = canonical_book(book_part)
+        .map(str::to_string)
+        .unwrap_or_else(|| book_part.split_whitespace().collect::<Vec<_>>().join(" "));
+
+    // A dot is the chapter separator only in "jn3.16" style; once the
+    // chapter is marked with a comma or colon it separates verses ("3,16.18").
+    let dot_is_chapter = !locator.contains([',', ':']);
+    let locator: String = locator
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == ':' || (c == '.' && dot_is_chapter) { ',' } else { c })
+        .map(|c| if c == '–' || c == '—' { '-' } else { c })
+        .collect();
+    let normalized = if locator.is_empty() { book.clone() } else { format!("{book} {locator}") };
+
+    let book_id = V2_BOOK_NAMES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(_, name)| fold_book_name(name) == fold_book_name(&book))
+        .map(|(id, _)| *id);
+
+    Ok(NormalizedReference { normalized, book_id })
+}
+
 // Legacy API: Get suggestions for autocomplete
 #[tauri::command]
 pub async fn fetch_bible_suggestions(
     term: String,
     api_url: String,
+    request_id: Option<u64>,
+    caller: Option<String>,
 ) -> Result<Vec<LegacySuggestion>, String> {
+    let caller = format!("fetch_bible_suggestions:{}", caller.unwrap_or_default());
+    run_latest(&caller, request_id, fetch_suggestions(term, api_url)).await
+}
+
+async fn fetch_suggestions(term: String, api_url: String) -> Result<Vec<LegacySuggestion>, String> {
     let url = format!("{}/kereses/suggest?term={}", api_url, urlencoding::encode(&term));
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let response = client
         .get(&url)
         .send()
@@ -164,10 +781,7 @@ pub async fn fetch_bible_suggestions(
         return Err(format!("API error: {}", response.status()));
     }
 
-    let suggestions: Vec<LegacySuggestion> = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+    let suggestions: Vec<LegacySuggestion> = parse_json(response, &url).await?;
 
     // Filter by cat === 'ref' and map book names
     let filtered: Vec<LegacySuggestion> = suggestions
@@ -194,12 +808,31 @@ pub async fn fetch_bible_legacy(
     reference: String,
     translation: String,
     api_url: String,
+    request_id: Option<u64>,
+    caller: Option<String>,
+    clean: Option<CleanOptions>,
 ) -> Result<LegacySearchResponse, String> {
+    let caller = format!("fetch_bible_legacy:{}", caller.unwrap_or_default());
+    run_latest(
+        &caller,
+        request_id,
+        fetch_legacy(reference, translation, api_url, clean.unwrap_or_default()),
+    )
+    .await
+}
+
+async fn fetch_legacy(
+    reference: String,
+    translation: String,
+    api_url: String,
+    clean: CleanOptions,
+) -> Result<LegacySearchResponse, String> {
+    check_translation(&translation, "legacy", LEGACY_TRANSLATIONS)?;
     // Strip leading slash if present and encode only spaces
     let clean_ref = reference.trim_start_matches('/');
     let url = format!("{}/api/idezet/{}/{}", api_url, encode_path_segment(clean_ref), translation);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let response = client
         .get(&url)
         .send()
@@ -210,15 +843,517 @@ pub async fn fetch_bible_legacy(
         return Err(format!("API error: {}", response.status()));
     }
 
-    let mut data: LegacySearchResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+    let mut data: LegacySearchResponse = parse_json(response, &url).await?;
 
     // Clean up verse text
+    let mut note_number = 1;
     for verse in &mut data.valasz.versek {
-        verse.szoveg = clean_verse_text(&verse.szoveg);
+        verse.szoveg = clean_verse_text(&verse.szoveg, clean);
+        if clean.keep_footnotes && !verse.jegyzetek.is_empty() {
+            verse.szoveg = inline_legacy_notes(&verse.szoveg, &verse.jegyzetek, &mut note_number);
+        }
     }
 
     Ok(data)
 }
+
+/// One full-text search hit from the legacy API.
+#[derive(Debug, Serialize, Clone)]
+pub struct LegacyTextHit {
+    /// Human reference ("Jn 3,16") when the API provides one, else `gepi`.
+    pub reference: String,
+    pub gepi: String,
+    pub text: String,
+}
+
+const MAX_SEARCH_QUERY_LEN: usize = 200;
+
+// Walk `fullTextResult.results[].verses[]`. The search payload is less
+// stable than `idezet`, so read fields leniently instead of with a schema.
+fn collect_text_hits(data: &serde_json::Value, clean: CleanOptions) -> Vec<LegacyTextHit> {
+    let str_field = |v: &serde_json::Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| v.get(*k).and_then(|f| f.as_str()))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let results = data
+        .pointer("/fullTextResult/results")
+        .and_then(|r| r.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    results
+        .iter()
+        .flat_map(|result| {
+            let result_ref = str_field(result, &["ref", "szep"]);
+            result
+                .get("verses")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(move |verse| {
+                    let gepi = str_field(verse, &["gepi"]);
+                    let reference = [str_field(verse, &["ref", "szep"]), result_ref.clone(), gepi.clone()]
+                        .into_iter()
+                        .find(|r| !r.is_empty())
+                        .unwrap_or_default();
+                    LegacyTextHit {
+                        reference,
+                        gepi,
+                        text: clean_verse_text(&str_field(verse, &["text", "szoveg"]), clean),
+                    }
+                })
+        })
+        .filter(|hit| !hit.text.trim().is_empty())
+        .collect()
+}
+
+// Legacy API: full-text search for a phrase. Returns an empty list when
+// nothing matches.
+#[tauri::command]
+pub async fn search_bible_text(
+    query: String,
+    translation: String,
+    api_url: String,
+    clean: Option<CleanOptions>,
+) -> Result<Vec<LegacyTextHit>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    if query.chars().count() > MAX_SEARCH_QUERY_LEN {
+        return Err(format!("Search text is too long (max {MAX_SEARCH_QUERY_LEN} characters)"));
+    }
+    check_translation(&translation, "legacy", LEGACY_TRANSLATIONS)?;
+
+    let url = format!("{}/api/search/{}/{}", api_url, urlencoding::encode(query), translation);
+
+    let client = crate::http::http_client();
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    // The API answers 404 rather than an empty result set for no matches.
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    let data: serde_json::Value = parse_json(response, &url).await?;
+    Ok(collect_text_hits(&data, clean.unwrap_or_default()))
+}
+
+/// Output shape for [`fetch_bible_plaintext`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaintextFormat {
+    /// One verse per line.
+    Verses,
+    /// Verses joined by spaces.
+    Paragraph,
+    /// Joined by spaces, each verse prefixed with its number ("16 For God…").
+    WithNumbers,
+}
+
+// Verse number from a legacy `gepi` location (book, chapter, verse as
+// 3-digit groups; the verse is the last group).
+fn legacy_verse_number(gepi: &str) -> Option<i32> {
+    gepi.get(gepi.len().checked_sub(3)?..)?.parse().ok()
+}
+
+fn join_plaintext(verses: &[(i32, String)], format: PlaintextFormat) -> String {
+    let texts = verses.iter().map(|(_, text)| text.trim()).filter(|t| !t.is_empty());
+    match format {
+        PlaintextFormat::Verses => texts.collect::<Vec<_>>().join("\n"),
+        PlaintextFormat::Paragraph => texts.collect::<Vec<_>>().join(" "),
+        PlaintextFormat::WithNumbers => verses
+            .iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(n, text)| format!("{n} {}", text.trim()))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Fetch a passage as plain text for copying or lower thirds. Translations
+/// with a `_v2` suffix (as used by the frontend) go to the V2 API, anything
+/// else to the legacy API; `api_url` must point at the matching backend.
+#[tauri::command]
+pub async fn fetch_bible_plaintext(
+    reference: String,
+    translation: String,
+    api_url: String,
+    format: PlaintextFormat,
+    clean: Option<CleanOptions>,
+) -> Result<String, String> {
+    let clean = clean.unwrap_or_default();
+    let verses: Vec<(i32, String)> = match translation.strip_suffix("_v2") {
+        Some(code) => fetch_v2(reference, code.to_string(), api_url, clean)
+            .await?
+            .verses
+            .into_iter()
+            .map(|v| (v.verse, v.text))
+            .collect(),
+        None => fetch_legacy(reference, translation, api_url, clean)
+            .await?
+            .valasz
+            .versek
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let n = legacy_verse_number(&v.hely.gepi).unwrap_or(i as i32 + 1);
+                (n, v.szoveg)
+            })
+            .collect(),
+    };
+    Ok(join_plaintext(&verses, format))
+}
+
+/// Passages the welcome screen rotates through when no list is supplied.
+/// Hungarian abbreviations, as accepted by the V2 API.
+const CURATED_REFERENCES: &[&str] = &[
+    "Jn 3,16",
+    "Zsolt 23,1-3",
+    "Zsolt 46,2",
+    "Zsolt 119,105",
+    "Péld 3,5-6",
+    "Ézs 40,31",
+    "Ézs 41,10",
+    "Jer 29,11",
+    "JSir 3,22-23",
+    "Mt 5,14-16",
+    "Mt 6,33",
+    "Mt 11,28",
+    "Jn 14,27",
+    "Róm 8,28",
+    "Róm 12,2",
+    "1Kor 13,4-7",
+    "2Kor 5,17",
+    "Gal 5,22-23",
+    "Fil 4,6-7",
+    "Fil 4,13",
+    "Kol 3,23",
+    "Zsid 11,1",
+    "Jak 1,5",
+    "1Pt 5,7",
+    "1Jn 4,18",
+];
+
+#[derive(Debug, Serialize)]
+pub struct RandomVerse {
+    /// The reference that was picked, as sent to the API.
+    pub reference: String,
+    /// The API's display label, falling back to `reference`.
+    pub label: String,
+    pub verses: Vec<V2Verse>,
+}
+
+/// SplitMix64 finalizer: spreads nearby seeds (e.g. consecutive day numbers)
+/// across the whole list instead of walking it in order.
+fn mix_seed(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Fetch one passage from `references` (or the bundled curated list) via the
+/// V2 API. The same `seed` always picks the same passage, so passing e.g. the
+/// day number yields a verse of the day; without one the pick is random.
+#[tauri::command]
+pub async fn fetch_random_verse(
+    translation: String,
+    api_url: String,
+    seed: Option<u64>,
+    references: Option<Vec<String>>,
+    clean: Option<CleanOptions>,
+) -> Result<RandomVerse, String> {
+    let references: Vec<String> = match references {
+        Some(list) => list.into_iter().filter(|r| !r.trim().is_empty()).collect(),
+        None => CURATED_REFERENCES.iter().map(|r| r.to_string()).collect(),
+    };
+    if references.is_empty() {
+        return Err("No references to pick from".to_string());
+    }
+
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let reference = references[(mix_seed(seed) % references.len() as u64) as usize].clone();
+
+    let code = translation.strip_suffix("_v2").unwrap_or(&translation).to_string();
+    let data = fetch_v2(reference.clone(), code, api_url, clean.unwrap_or_default()).await?;
+    let label = if data.hungarian_label.is_empty() { reference.clone() } else { data.hungarian_label };
+    Ok(RandomVerse { reference, label, verses: data.verses })
+}
+
+/// Books of the 66-book canon with their chapter counts, in the order the
+/// Hungarian Protestant translations use (Jóel has 4 chapters, Malakiás 3).
+const CANON: &[(&str, u32)] = &[
+    ("1Móz", 50), ("2Móz", 40), ("3Móz", 27), ("4Móz", 36), ("5Móz", 34),
+    ("Józs", 24), ("Bír", 21), ("Ruth", 4), ("1Sám", 31), ("2Sám", 24),
+    ("1Kir", 22), ("2Kir", 25), ("1Krón", 29), ("2Krón", 36), ("Ezsd", 10),
+    ("Neh", 13), ("Eszt", 10), ("Jób", 42), ("Zsolt", 150), ("Péld", 31),
+    ("Préd", 12), ("Énekek", 8), ("Ézs", 66), ("Jer", 52), ("JSir", 5),
+    ("Ez", 48), ("Dán", 12), ("Hós", 14), ("Jóel", 4), ("Ám", 9),
+    ("Abd", 1), ("Jón", 4), ("Mik", 7), ("Náh", 3), ("Hab", 3),
+    ("Zof", 3), ("Agg", 2), ("Zak", 14), ("Mal", 3),
+    ("Mt", 28), ("Mk", 16), ("Lk", 24), ("Jn", 21), ("ApCsel", 28),
+    ("Róm", 16), ("1Kor", 16), ("2Kor", 13), ("Gal", 6), ("Ef", 6),
+    ("Fil", 4), ("Kol", 4), ("1Thessz", 5), ("2Thessz", 3), ("1Tim", 6),
+    ("2Tim", 4), ("Tit", 3), ("Filem", 1), ("Zsid", 13), ("Jak", 5),
+    ("1Pt", 5), ("2Pt", 3), ("1Jn", 5), ("2Jn", 1), ("3Jn", 1),
+    ("Júd", 1), ("Jel", 22),
+];
+
+/// Tauri event reporting download progress; payload
+/// `{ translation, done, total, failed }`.
+pub const BIBLE_DOWNLOAD_PROGRESS_EVENT: &str = "bible-download-progress";
+
+/// Chapters fetched at once; keeps the V2 API from being hammered.
+const DOWNLOAD_CONCURRENCY: usize = 4;
+const DOWNLOAD_ATTEMPTS: u32 = 4;
+const DOWNLOAD_BACKOFF: Duration = Duration::from_millis(500);
+
+// Translations with a download in progress, so a second call can't race it.
+static ACTIVE_DOWNLOADS: LazyLock<Mutex<std::collections::HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(std::collections::HashSet::new()));
+
+/// Holds a translation's `ACTIVE_DOWNLOADS` entry and releases it on drop,
+/// so a cancelled or panicking download doesn't block later ones.
+struct ActiveDownload(String);
+
+impl ActiveDownload {
+    fn claim(translation: &str) -> Option<Self> {
+        ACTIVE_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(translation.to_string())
+            .then(|| Self(translation.to_string()))
+    }
+}
+
+impl Drop for ActiveDownload {
+    fn drop(&mut self) {
+        ACTIVE_DOWNLOADS.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.0);
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibleDownloadSummary {
+    pub translation: String,
+    pub total_chapters: usize,
+    /// Chapters already on disk from an earlier, interrupted download.
+    pub skipped: usize,
+    pub downloaded: usize,
+    /// References that still failed after retries; run again to resume.
+    pub failed: Vec<String>,
+}
+
+// One file per chapter: `bible/<translation>/<book index>-<chapter>.json`,
+// holding the raw (uncleaned) V2 response so any clean options apply later.
+fn chapter_path(dir: &std::path::Path, book_index: usize, chapter: u32) -> std::path::PathBuf {
+    dir.join(format!("{:02}-{:03}.json", book_index + 1, chapter))
+}
+
+async fn download_chapter(reference: &str, translation: &str, api_url: &str) -> Result<V2SuggestResponse, String> {
+    let mut delay = DOWNLOAD_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match fetch_v2_raw(reference.to_string(), translation.to_string(), api_url.to_string()).await {
+            Ok(data) if !data.verses.is_empty() => return Ok(data),
+            Ok(_) => return Err(format!("No verses returned for '{reference}'")),
+            Err(e) if attempt >= DOWNLOAD_ATTEMPTS => return Err(e),
+            Err(e) => {
+                tracing::debug!("Retrying {reference} in {delay:?} (attempt {attempt}): {e}");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Download every chapter of `translation` from the V2 API into the app
+/// data directory, which V2 lookups fall back to when the API is
+/// unreachable. Emits `bible-download-progress` as chapters complete.
+/// Chapters already on disk are skipped, so calling this again resumes an
+/// interrupted download.
+#[tauri::command]
+pub async fn download_bible_translation(
+    app: tauri::AppHandle,
+    translation: String,
+    api_url: String,
+) -> Result<BibleDownloadSummary, String> {
+    use futures_util::StreamExt;
+    use tauri::{Emitter, Manager};
+
+    let translation = translation.strip_suffix("_v2").unwrap_or(&translation).to_string();
+    check_translation(&translation, "V2", V2_TRANSLATIONS)?;
+    let _active = ActiveDownload::claim(&translation)
+        .ok_or_else(|| format!("A download of {translation} is already running"))?;
+
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("bible")
+        .join(&translation);
+    tokio::fs::create_dir_all(&dir).await.map_err(|e| e.to_string())?;
+
+    let chapters: Vec<(usize, &str, u32)> = CANON
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &(book, count))| (1..=count).map(move |ch| (i, book, ch)))
+        .collect();
+    let total = chapters.len();
+    let mut pending = Vec::new();
+    for (i, book, ch) in chapters {
+        if !tokio::fs::try_exists(chapter_path(&dir, i, ch)).await.unwrap_or(false) {
+            pending.push((i, book, ch));
+        }
+    }
+    let skipped = total - pending.len();
+
+    let mut done = skipped;
+    let mut downloaded = 0;
+    let mut failed = Vec::new();
+    let mut results = futures_util::stream::iter(pending)
+        .map(|(i, book, ch)| {
+            let (dir, translation, api_url) = (&dir, &translation, &api_url);
+            async move {
+                let reference = format!("{book} {ch}");
+                let outcome = async {
+                    let data = download_chapter(&reference, translation, api_url).await?;
+                    let json = serde_json::to_vec(&data).map_err(|e| e.to_string())?;
+                    // Write aside and rename, so an interrupted write never
+                    // leaves a truncated chapter that resume would skip.
+                    let path = chapter_path(dir, i, ch);
+                    let tmp = path.with_extension("json.tmp");
+                    tokio::fs::write(&tmp, json).await.map_err(|e| e.to_string())?;
+                    tokio::fs::rename(&tmp, &path).await.map_err(|e| e.to_string())
+                }
+                .await;
+                (reference, outcome)
+            }
+        })
+        .buffer_unordered(DOWNLOAD_CONCURRENCY);
+
+    while let Some((reference, outcome)) = results.next().await {
+        done += 1;
+        match outcome {
+            Ok(()) => downloaded += 1,
+            Err(e) => {
+                tracing::warn!("Could not download {reference} ({translation}): {e}");
+                failed.push(reference);
+            }
+        }
+        let _ = app.emit(
+            BIBLE_DOWNLOAD_PROGRESS_EVENT,
+            serde_json::json!({
+                "translation": translation,
+                "done": done,
+                "total": total,
+                "failed": failed.len(),
+            }),
+        );
+    }
+
+    Ok(BibleDownloadSummary { translation: translation.clone(), total_chapters: total, skipped, downloaded, failed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(input: &str) -> (String, Option<i32>) {
+        let r = normalize_reference(input.to_string()).unwrap();
+        (r.normalized, r.book_id)
+    }
+
+    #[test]
+    fn normalizes_compact_input() {
+        assert_eq!(normalized("jn3.16").0, "Jn 3,16");
+        assert_eq!(normalized("zsolt23").0, "Zsolt 23");
+    }
+
+    #[test]
+    fn book_ids_come_from_v2_responses_only() {
+        remember_book_names(&[V2ParsedRef {
+            book: "Jn".to_string(),
+            book_id: 43,
+            chapter_from: 3,
+            chapter_to: None,
+            verse_from: Some(16),
+            verse_to: None,
+        }]);
+        assert_eq!(normalized("jn3.16").1, Some(43));
+        // Never seen in a response: no guess from the canon position.
+        assert_eq!(normalized("Abd 1,1").1, None);
+    }
+
+    #[tokio::test]
+    async fn unknown_book_ids_are_rejected() {
+        let key = VerseKey {
+            book_id: 31,
+            chapter: 1,
+            verse: 1,
+            verse_to: None,
+            translation: "RUF".to_string(),
+            clean: CleanOptions::default(),
+        };
+        let err = fetch_by_ids_cached(key, "http://127.0.0.1:9".to_string()).await.unwrap_err();
+        assert!(err.starts_with("Unknown book id 31"), "{err}");
+    }
+
+    #[test]
+    fn normalizes_ordinals_and_separators() {
+        assert_eq!(normalized("I. Kor 13:4 - 7").0, "1Kor 13,4-7");
+        assert_eq!(normalized("2. Sám 7,12").0, "2Sám 7,12");
+        assert_eq!(normalized("1Mózes 1:1–3").0, "1Móz 1,1-3");
+    }
+
+    #[test]
+    fn normalizes_full_and_english_names() {
+        assert_eq!(normalized("Jeremiás siralmai 3,22-23").0, "JSir 3,22-23");
+        assert_eq!(normalized("Romans 8:28").0, "Róm 8,28");
+        assert_eq!(normalized("  Máté   5,3 ").0, "Mt 5,3");
+    }
+
+    #[test]
+    fn keeps_dot_as_verse_separator_after_comma() {
+        assert_eq!(normalized("Jn 3,16.18").0, "Jn 3,16.18");
+        assert_eq!(normalized("Jn 3:16.18").0, "Jn 3,16.18");
+    }
+
+    #[test]
+    fn passes_unknown_books_through() {
+        assert_eq!(normalized("Foo  1,2"), ("Foo 1,2".to_string(), None));
+        assert!(normalize_reference("  ".to_string()).is_err());
+    }
+
+    #[test]
+    fn active_download_is_released_on_drop() {
+        let first = ActiveDownload::claim("TEST_GUARD").unwrap();
+        assert!(ActiveDownload::claim("TEST_GUARD").is_none());
+        drop(first);
+        assert!(ActiveDownload::claim("TEST_GUARD").is_some());
+    }
+
+    #[test]
+    fn curated_references_use_canonical_books() {
+        for reference in CURATED_REFERENCES {
+            assert_eq!(normalized(reference).0, *reference);
+            let (book, _) = reference.split_once(' ').unwrap();
+            assert!(CANON.iter().any(|(abbr, _)| *abbr == book), "{reference}");
+        }
+    }
+}
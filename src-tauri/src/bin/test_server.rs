@@ -9,6 +9,7 @@
 //! Optional env var:
 //!   TEST_SERVER_PORT  — port to listen on (default: 3738)
 
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -70,7 +71,9 @@ async fn main() -> anyhow::Result<()> {
     server::build_and_serve(
         pool,
         auth_token,
+        Arc::new(RwLock::new(Vec::new())), // no named API keys in test mode
         connection_url,
+        std::net::IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED),
         port,
         None, // no static file serving in test mode
         obs_connector,
@@ -81,6 +84,8 @@ async fn main() -> anyhow::Result<()> {
         youtube_config,
         facebook_config,
         oauth_states,
+        Arc::new(AtomicBool::new(false)),
+        None, // no idle auto-stop
         None, // no AppHandle — OAuth flows are unavailable in test mode
         cron_scheduler,
         #[cfg(target_os = "macos")]
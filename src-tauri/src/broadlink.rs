@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 /// Default Broadlink encryption key (before auth)
@@ -23,6 +24,10 @@ const DEFAULT_IV: [u8; 16] = [
     0xdd, 0xb3, 0xba, 0x69, 0x5a, 0x2e, 0x6f, 0x58,
 ];
 
+/// UDP port the Broadlink protocol uses.
+const DEFAULT_PORT: u16 = 80;
+
+
 /// Raw Broadlink device handler for direct protocol communication
 struct BroadlinkDevice {
     socket: UdpSocket,
@@ -35,20 +40,20 @@ struct BroadlinkDevice {
 }
 
 impl BroadlinkDevice {
-    /// Connect to a Broadlink device
-    fn connect(host: &str, mac: &str, devtype: &str, local_ip: Ipv4Addr) -> Result<Self, String> {
+    /// Connect to a Broadlink device on `port` (the protocol default when `None`)
+    fn connect(
+        host: &str,
+        mac: &str,
+        devtype: &str,
+        local_ip: Ipv4Addr,
+        port: Option<u16>,
+    ) -> Result<Self, String> {
         let device_ip: Ipv4Addr = host.parse()
             .map_err(|e| format!("Invalid IP: {}", e))?;
 
         // Parse MAC address - use as-is from discovery response (no reversal needed)
         // The MAC bytes from discovery are already in the correct format for packets
-        let mac_bytes: Vec<u8> = mac.split(':')
-            .map(|s| u8::from_str_radix(s, 16).unwrap_or(0))
-            .collect();
-        let mut device_mac = [0u8; 6];
-        if mac_bytes.len() == 6 {
-            device_mac.copy_from_slice(&mac_bytes);
-        }
+        let device_mac = parse_mac(mac)?;
 
         // Parse device type (e.g., "0x520b")
         let device_type = if devtype.starts_with("0x") {
@@ -70,7 +75,7 @@ impl BroadlinkDevice {
             .map_err(|e| format!("Failed to set timeout: {}", e))?;
 
         // Connect to device (helps Windows route correctly)
-        let device_addr = SocketAddr::new(device_ip.into(), 80);
+        let device_addr = SocketAddr::new(device_ip.into(), port.unwrap_or(DEFAULT_PORT));
         socket.connect(device_addr)
             .map_err(|e| format!("Failed to connect socket: {}", e))?;
 
@@ -456,6 +461,48 @@ pub struct DiscoveredDevice {
     pub name: String,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum BroadlinkError {
+    #[error("Invalid MAC address '{0}': expected six hex bytes (aa:bb:cc:dd:ee:ff)")]
+    InvalidMac(String),
+}
+
+impl From<BroadlinkError> for String {
+    fn from(e: BroadlinkError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Parse a MAC written as `aa:bb:cc:dd:ee:ff`, `aa-bb-cc-dd-ee-ff`,
+/// `aabb.ccdd.eeff` or `aabbccddeeff`.
+pub fn parse_mac(mac: &str) -> Result<[u8; 6], BroadlinkError> {
+    let invalid = || BroadlinkError::InvalidMac(mac.to_string());
+    let trimmed = mac.trim();
+    let mut out = [0u8; 6];
+
+    if let Some(sep) = [':', '-'].into_iter().find(|&c| trimmed.contains(c)) {
+        let parts: Vec<&str> = trimmed.split(sep).collect();
+        if parts.len() != 6 {
+            return Err(invalid());
+        }
+        for (byte, part) in out.iter_mut().zip(parts) {
+            if part.is_empty() || part.len() > 2 || !part.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_| invalid())?;
+        }
+    } else {
+        let digits: String = trimmed.chars().filter(|&c| c != '.').collect();
+        if digits.len() != 12 {
+            return Err(invalid());
+        }
+        let bytes = hex::decode(&digits).map_err(|_| invalid())?;
+        out.copy_from_slice(&bytes);
+    }
+
+    Ok(out)
+}
+
 /// Format MAC address bytes as colon-separated hex string
 fn format_mac(mac: &[u8; 6]) -> String {
     format!(
@@ -464,7 +511,9 @@ fn format_mac(mac: &[u8; 6]) -> String {
     )
 }
 
-/// Get device model name from device type code
+/// Get device model name from device type code. The ids after the SP2
+/// block follow python-broadlink's `SUPPORTED_TYPES`, so rarer RM revisions
+/// and the non-remote devices that answer discovery are named too.
 fn get_device_model(devtype: u16) -> (&'static str, &'static str) {
     match devtype {
         // RM Mini 3
@@ -476,20 +525,38 @@ fn get_device_model(devtype: u16) -> (&'static str, &'static str) {
         0x27a9 => ("RM Pro", "Remote"),
         // RM4 Mini
         0x5f36 => ("RM4 Mini", "Remote"),
+        0x51da => ("RM4 Mini", "Remote"),
         0x520b => ("RM4 Mini", "Remote"),
         0x520c => ("RM4 Mini", "Remote"),
         0x520d => ("RM4 Mini", "Remote"),
+        0x5216 => ("RM4 Mini", "Remote"),
+        0x521c => ("RM4 Mini", "Remote"),
+        0x610e => ("RM4 Mini", "Remote"),
+        0x62bc => ("RM4 Mini", "Remote"),
+        0x648d => ("RM4 Mini", "Remote"),
         // RM4 Pro
         0x6026 => ("RM4 Pro", "Remote"),
         0x61a2 => ("RM4 Pro", "Remote"),
         0x6184 => ("RM4 Pro", "Remote"),
         0x649b => ("RM4 Pro", "Remote"),
         0x653a => ("RM4 Pro", "Remote"),
+        0x5213 => ("RM4 Pro", "Remote"),
+        0x653c => ("RM4 Pro", "Remote"),
+        // RM4C Pro
+        0x5218 => ("RM4C Pro", "Remote"),
         // RM4C Mini
         0x6508 => ("RM4C Mini", "Remote"),
         0x6070 => ("RM4C Mini", "Remote"),
+        0x610f => ("RM4C Mini", "Remote"),
+        0x62be => ("RM4C Mini", "Remote"),
+        // RM4C Mate
+        0x5211 => ("RM4C Mate", "Remote"),
+        // RM4S
+        0x6364 => ("RM4S", "Remote"),
         // RM4 TV Mate
         0x6539 => ("RM4 TV Mate", "Remote"),
+        0x5209 => ("RM4 TV Mate", "Remote"),
+        0x5212 => ("RM4 TV Mate", "Remote"),
         // SP Mini
         0x7547 => ("SP Mini", "Plug"),
         0x7918 => ("SP Mini", "Plug"),
@@ -498,7 +565,52 @@ fn get_device_model(devtype: u16) -> (&'static str, &'static str) {
         0x2719 => ("SP2", "Plug"),
         0x7919 => ("SP2", "Plug"),
         0x791a => ("SP2", "Plug"),
-        // Default
+        // rmmini
+        0x278f => ("RM Mini", "Remote"),
+        0x27c7 | 0x27cc | 0x27cd | 0x27d0 | 0x27d1 | 0x27d3 | 0x27dc | 0x27de => {
+            ("RM Mini 3", "Remote")
+        }
+        // rmpro
+        0x2712 => ("RM Pro/Pro+", "Remote"),
+        0x272a | 0x273d => ("RM Pro", "Remote"),
+        0x277c | 0x2783 => ("RM Home", "Remote"),
+        0x278b | 0x27a1 | 0x27a6 => ("RM Plus", "Remote"),
+        0x2797 | 0x27c3 => ("RM Pro+", "Remote"),
+        // rmminib
+        0x6507 => ("RM Mini 3", "Remote"),
+        // sp1 / sp2 / sp2s / sp3 / sp3s
+        0x0000 => ("SP1", "Plug"),
+        0x2717 => ("NEO", "Plug"),
+        0x2716 => ("NEO PRO", "Plug"),
+        0x271a | 0x2728 => ("SP2 (compatible)", "Plug"),
+        0x2720 | 0x273e => ("SP Mini", "Plug"),
+        0x271d => ("Ego", "Plug"),
+        0x2736 => ("SP Mini+", "Plug"),
+        0x2733 | 0x7d00 => ("SP3", "Plug"),
+        0x7530 | 0x7539 | 0x7544 | 0x7546 => ("SP2", "Plug"),
+        0x753e | 0x7583 | 0x7d0d | 0x7d11 => ("SP Mini 3", "Plug"),
+        0x7540 => ("MP2", "Plug"),
+        0x9479 | 0x947a => ("SP3S", "Plug"),
+        // sp4 / sp4b
+        0x7568 | 0x7579 | 0x757b | 0x7587 | 0xa569 | 0xa56c | 0xa589 | 0xa5d3 | 0x618b
+        | 0x6489 | 0x648c | 0x6494 => ("SP4L", "Plug"),
+        0x756b | 0x756c | 0x648b => ("SP4M", "Plug"),
+        0x756f | 0xa56a | 0x6111 => ("MCB1", "Plug"),
+        0xa56b | 0x5115 | 0x6113 => ("SCB1E", "Plug"),
+        // mp1
+        0x4eb5 | 0x4f1b | 0x4f65 => ("MP1", "Power strip"),
+        // a1
+        0x2714 => ("e-Sensor", "Sensor"),
+        // s1c
+        0x2722 => ("S2KIT", "Alarm"),
+        // lb1
+        0x5043 | 0x606e => ("Smart bulb", "Light"),
+        0x504e | 0x60c7 | 0x60c8 | 0x6112 | 0x644b => ("LB1", "Light"),
+        0x644c | 0x644e => ("LB2x", "Light"),
+        // hysen
+        0x4ead => ("HY02/HY03", "Thermostat"),
+        // dooya
+        0x4e4d => ("DT360E", "Curtain motor"),
         _ => ("Unknown", "Unknown"),
     }
 }
@@ -657,6 +769,9 @@ fn raw_discover_on_interface(local_ip: Ipv4Addr, timeout_secs: u64) -> Vec<Disco
 pub struct LearnResult {
     pub code: Option<String>,
     pub error: Option<String>,
+    /// Assessment of a successful capture; prompt a re-learn when it's low.
+    #[serde(default)]
+    pub quality: Option<CodeQuality>,
 }
 
 /// Result of a send operation
@@ -669,9 +784,80 @@ pub struct SendResult {
 /// Global state for managing learn cancellation
 static LEARN_CANCEL: AtomicBool = AtomicBool::new(false);
 
-/// Get all IPv4 addresses from network interfaces (excluding loopback and virtual)
-fn get_local_ipv4_addresses() -> Vec<Ipv4Addr> {
+/// Hex code from the most recent successful learn, kept for quick re-testing.
+static LAST_LEARNED: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+
+fn last_learned() -> &'static Mutex<Option<String>> {
+    LAST_LEARNED.get_or_init(|| Mutex::new(None))
+}
+
+/// User overrides for which interfaces discovery and sending may use.
+/// Patterns are case-insensitive substrings of the interface name.
+#[derive(Debug, Clone, Default)]
+struct InterfaceFilter {
+    /// Always used, even when a built-in rule would skip them (e.g. a `br-`
+    /// bridge that really is the LAN).
+    include: Vec<String>,
+    /// Always skipped, in addition to the built-in rules.
+    exclude: Vec<String>,
+}
+
+static INTERFACE_FILTER: OnceLock<Mutex<InterfaceFilter>> = OnceLock::new();
+
+fn interface_filter() -> &'static Mutex<InterfaceFilter> {
+    INTERFACE_FILTER.get_or_init(|| Mutex::new(InterfaceFilter::default()))
+}
+
+/// Replace the include/exclude interface patterns (from `BroadlinkConfig`).
+pub fn configure_interface_filter(include: &[String], exclude: &[String]) {
+    let normalize = |patterns: &[String]| {
+        patterns
+            .iter()
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect()
+    };
+    *interface_filter().lock().unwrap_or_else(|e| e.into_inner()) = InterfaceFilter {
+        include: normalize(include),
+        exclude: normalize(exclude),
+    };
+}
+
+/// Built-in reason to skip an interface by name, if any.
+fn default_skip_reason(name_lower: &str) -> Option<&'static str> {
+    // On Windows, "vEthernet (External Switch)" is the actual physical NIC
+    // bridged to Hyper-V - we need to keep it for LAN access
+    if name_lower.contains("external switch") {
+        return None;
+    }
+    let is_virtual = name_lower.starts_with("veth")
+        || name_lower.starts_with("docker")
+        || name_lower.starts_with("br-")
+        || name_lower.starts_with("virbr")
+        || name_lower.contains("wsl")
+        || name_lower.contains("hyper-v")
+        || name_lower.contains("virtualbox")
+        || name_lower.contains("default switch");
+    // macOS VPN / tunnel interfaces — point-to-point, no broadcast subnet
+    let is_tunnel = name_lower.starts_with("utun")
+        || name_lower.starts_with("awdl")
+        || name_lower.starts_with("llw")
+        || name_lower.starts_with("anpi")
+        || name_lower.starts_with("bridge");
+    if is_virtual {
+        Some("virtual interface")
+    } else if is_tunnel {
+        Some("tunnel interface")
+    } else {
+        None
+    }
+}
+
+/// Get all IPv4 addresses from network interfaces (excluding loopback,
+/// virtual and tunnel interfaces, subject to the configured filter)
+pub(crate) fn get_local_ipv4_addresses() -> Vec<Ipv4Addr> {
     let mut addresses = Vec::new();
+    let filter = interface_filter().lock().unwrap_or_else(|e| e.into_inner()).clone();
 
     if let Ok(interfaces) = local_ip_address::list_afinet_netifas() {
         for (name, ip) in interfaces {
@@ -681,36 +867,20 @@ fn get_local_ipv4_addresses() -> Vec<Ipv4Addr> {
                     continue;
                 }
 
-                // Skip common virtual interface prefixes
-                let name_lower = name.to_lowercase();
-
-                // On Windows, "vEthernet (External Switch)" is the actual physical NIC
-                // bridged to Hyper-V - we need to keep it for LAN access
-                let is_external_switch = name_lower.contains("external switch");
-
                 // Skip link-local addresses (169.254.x.x) — unusable for LAN broadcast
                 if ipv4.is_link_local() {
                     tracing::debug!("Skipping link-local address: {} ({})", name, ipv4);
                     continue;
                 }
 
-                if !is_external_switch && (
-                    name_lower.starts_with("veth")
-                    || name_lower.starts_with("docker")
-                    || name_lower.starts_with("br-")
-                    || name_lower.starts_with("virbr")
-                    || name_lower.contains("wsl")
-                    || name_lower.contains("hyper-v")
-                    || name_lower.contains("virtualbox")
-                    || name_lower.contains("default switch")
-                    // macOS VPN / tunnel interfaces — point-to-point, no broadcast subnet
-                    || name_lower.starts_with("utun")
-                    || name_lower.starts_with("awdl")
-                    || name_lower.starts_with("llw")
-                    || name_lower.starts_with("anpi")
-                    || name_lower.starts_with("bridge")
-                ) {
-                    tracing::debug!("Skipping virtual/tunnel interface: {} ({})", name, ipv4);
+                let name_lower = name.to_lowercase();
+                if let Some(pattern) = filter.exclude.iter().find(|p| name_lower.contains(p.as_str())) {
+                    tracing::info!("Skipping interface {} ({}): matches exclude pattern '{}'", name, ipv4, pattern);
+                    continue;
+                }
+                let included = filter.include.iter().any(|p| name_lower.contains(p.as_str()));
+                if let Some(reason) = default_skip_reason(&name_lower).filter(|_| !included) {
+                    tracing::info!("Skipping interface {} ({}): {}", name, ipv4, reason);
                     continue;
                 }
 
@@ -723,6 +893,37 @@ fn get_local_ipv4_addresses() -> Vec<Ipv4Addr> {
     addresses
 }
 
+/// Resolve a hostname (DNS, or mDNS `.local` names via the OS resolver) to
+/// all of its addresses. IP literals are returned as-is without a lookup.
+pub async fn resolve_host(host: &str) -> Result<Vec<IpAddr>, String> {
+    let host = host.trim();
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(vec![ip]);
+    }
+    let mut addrs: Vec<IpAddr> = tokio::net::lookup_host((host, 0))
+        .await
+        .map_err(|e| format!("Could not resolve '{}': {}", host, e))?
+        .map(|addr| addr.ip())
+        .collect();
+    addrs.dedup();
+    if addrs.is_empty() {
+        return Err(format!("Could not resolve '{}': no addresses", host));
+    }
+    Ok(addrs)
+}
+
+/// First IPv4 address for `host`; Broadlink devices only speak IPv4.
+pub(crate) async fn resolve_ipv4(host: &str) -> Result<Ipv4Addr, String> {
+    resolve_host(host)
+        .await?
+        .into_iter()
+        .find_map(|ip| match ip {
+            IpAddr::V4(v4) => Some(v4),
+            IpAddr::V6(_) => None,
+        })
+        .ok_or_else(|| format!("'{}' has no IPv4 address", host))
+}
+
 /// Discover Broadlink devices on the network by trying all interfaces
 pub async fn discover_devices(timeout: u32) -> Result<Vec<DiscoveredDevice>, String> {
     let timeout_secs = timeout.max(1) as u64;
@@ -767,7 +968,7 @@ pub async fn discover_devices(timeout: u32) -> Result<Vec<DiscoveredDevice>, Str
 }
 
 /// Get the best local IP for communicating with a specific device IP
-fn get_local_ip_for_device(device_ip: Ipv4Addr) -> Option<Ipv4Addr> {
+pub(crate) fn get_local_ip_for_device(device_ip: Ipv4Addr) -> Option<Ipv4Addr> {
     let local_ips = get_local_ipv4_addresses();
 
     // Try to find an IP in the same subnet (simple heuristic: same first 3 octets)
@@ -792,11 +993,15 @@ pub async fn learn_code(
     mac: &str,
     devtype: &str,
     signal_type: &str,
+    port: Option<u16>,
 ) -> Result<LearnResult, String> {
-    // Reset cancellation flag
+    parse_mac(mac)?;
+
+    // Reset cancellation flag and forget the previous learn
     LEARN_CANCEL.store(false, Ordering::SeqCst);
+    *last_learned().lock().unwrap_or_else(|e| e.into_inner()) = None;
 
-    let host = host.to_string();
+    let host = resolve_ipv4(host).await?.to_string();
     let mac = mac.to_string();
     let devtype = devtype.to_string();
     let signal_type = signal_type.to_string();
@@ -814,7 +1019,7 @@ pub async fn learn_code(
 
         // Connect using our custom BroadlinkDevice with RM4 protocol support
         tracing::info!("Connecting to device...");
-        let mut device = BroadlinkDevice::connect(&host, &mac, &devtype, local_ip)?;
+        let mut device = BroadlinkDevice::connect(&host, &mac, &devtype, local_ip, port)?;
 
         tracing::info!("Connected! Starting {} learning (RM4 protocol)...", signal_type);
 
@@ -830,9 +1035,15 @@ pub async fn learn_code(
                 tracing::info!("Learned code: {} bytes", code.len());
                 // Convert bytes to hex string
                 let hex_code = hex::encode(&code);
+                *last_learned().lock().unwrap_or_else(|e| e.into_inner()) = Some(hex_code.clone());
+                let quality = validate_ir_code(&hex_code, &signal_type);
+                if quality.score < MIN_GOOD_QUALITY {
+                    tracing::warn!("Learned code looks implausible: {:?}", quality.warnings);
+                }
                 Ok(LearnResult {
                     code: Some(hex_code),
                     error: None,
+                    quality: Some(quality),
                 })
             }
             Err(e) => {
@@ -840,6 +1051,7 @@ pub async fn learn_code(
                 Ok(LearnResult {
                     code: None,
                     error: Some(e),
+                    quality: None,
                 })
             }
         }
@@ -848,6 +1060,21 @@ pub async fn learn_code(
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// Send the code captured by the most recent learn without saving it first
+pub async fn send_last_learned(
+    host: &str,
+    mac: &str,
+    devtype: &str,
+    port: Option<u16>,
+) -> Result<SendResult, String> {
+    let code = last_learned()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .ok_or_else(|| "No code has been learned yet".to_string())?;
+    send_code(host, mac, devtype, &code, port).await
+}
+
 /// Cancel ongoing learning operation
 pub async fn cancel_learn() {
     LEARN_CANCEL.store(true, Ordering::SeqCst);
@@ -859,8 +1086,11 @@ pub async fn send_code(
     mac: &str,
     devtype: &str,
     code: &str,
+    port: Option<u16>,
 ) -> Result<SendResult, String> {
-    let host = host.to_string();
+    parse_mac(mac)?;
+
+    let host = resolve_ipv4(host).await?.to_string();
     let mac = mac.to_string();
     let devtype = devtype.to_string();
     let code = code.to_string();
@@ -881,7 +1111,7 @@ pub async fn send_code(
         tracing::info!("Sending to device {} using local IP {}", ip, local_ip);
 
         // Connect using our custom BroadlinkDevice with RM4 protocol support
-        let mut device = BroadlinkDevice::connect(&host, &mac, &devtype, local_ip)
+        let mut device = BroadlinkDevice::connect(&host, &mac, &devtype, local_ip, port)
             .map_err(|e| format!("Failed to connect to device: {}", e))?;
 
         // Send the code
@@ -900,71 +1130,397 @@ pub async fn send_code(
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
-/// Test if a device is reachable using raw UDP ping
+/// First byte of a Broadlink code: the signal type.
+const BROADLINK_IR: u8 = 0x26;
+/// Closes the pulse data of every code the device learns.
+const CODE_TERMINATOR: [u8; 2] = [0x0d, 0x05];
+/// Broadlink pulse unit: one tick is 269/8192 ms (~32.84 µs).
+const TICK_NUMERATOR_US: u64 = 269_000;
+const TICK_DENOMINATOR: u64 = 8192;
+/// Broadlink codes don't record the carrier; this is what nearly every
+/// consumer IR remote uses.
+const DEFAULT_CARRIER_HZ: u32 = 38_000;
+/// Pronto frequency words count units of 0.241246 µs.
+const PRONTO_CLOCK_US: f64 = 0.241246;
+
+/// Target format for [`convert_ir_code`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrCodeFormat {
+    /// Pronto hex ("0000 006D ..."), learned-code form with a carrier header.
+    Pronto,
+    /// Broadlink hex as stored in `broadlink_commands`.
+    Broadlink,
+    /// Alternating on/off durations in microseconds, starting with "on".
+    RawTimings,
+}
+
+/// Decode a Broadlink code into alternating on/off durations in µs.
+/// Works for IR and RF codes alike; only the type byte differs.
+pub fn decode_broadlink_timings(code: &[u8]) -> Result<Vec<u32>, String> {
+    if code.len() < 4 {
+        return Err("Code is too short to be a Broadlink code".to_string());
+    }
+    let len = u16::from_le_bytes([code[2], code[3]]) as usize;
+    let data = &code[4..code.len().min(4 + len)];
+
+    let mut timings = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        if data[i..] == CODE_TERMINATOR {
+            break;
+        }
+        // Long pulses are a 0x00 marker followed by a big-endian u16.
+        let ticks = if data[i] == 0 {
+            let (Some(&hi), Some(&lo)) = (data.get(i + 1), data.get(i + 2)) else { break };
+            i += 3;
+            u16::from_be_bytes([hi, lo]) as u64
+        } else {
+            i += 1;
+            data[i - 1] as u64
+        };
+        timings.push((ticks * TICK_NUMERATOR_US / TICK_DENOMINATOR) as u32);
+    }
+    if timings.is_empty() {
+        return Err("Code contains no pulses".to_string());
+    }
+    Ok(timings)
+}
+
+/// Encode on/off durations in µs as a Broadlink IR code that plays
+/// `repeat` extra times, laid out like a learned code: terminator included
+/// in the length, zero-padded to a whole AES block.
+pub fn encode_broadlink_timings(timings: &[u32], repeat: u8) -> Vec<u8> {
+    let mut data = Vec::with_capacity(timings.len());
+    for &us in timings {
+        let ticks = ((us as u64 * TICK_DENOMINATOR + TICK_NUMERATOR_US / 2) / TICK_NUMERATOR_US)
+            .clamp(1, u16::MAX as u64) as u16;
+        if ticks < 0x100 {
+            data.push(ticks as u8);
+        } else {
+            data.push(0);
+            data.extend_from_slice(&ticks.to_be_bytes());
+        }
+    }
+    data.extend_from_slice(&CODE_TERMINATOR);
+    let mut code = vec![BROADLINK_IR, repeat];
+    code.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    code.extend_from_slice(&data);
+    code.resize(code.len().next_multiple_of(16), 0);
+    code
+}
+
+/// Pronto learned-code hex for `timings` at `carrier_hz`.
+pub fn encode_pronto(timings: &[u32], carrier_hz: u32) -> String {
+    let freq_word = (1_000_000.0 / (carrier_hz as f64 * PRONTO_CLOCK_US)).round() as u16;
+    let period_us = freq_word as f64 * PRONTO_CLOCK_US;
+    let mut cycles: Vec<u16> = timings
+        .iter()
+        .map(|&us| ((us as f64 / period_us).round() as u16).max(1))
+        .collect();
+    // Pronto works in on/off pairs; close an odd sequence with a short gap.
+    if cycles.len() % 2 == 1 {
+        cycles.push(cycles.last().copied().unwrap_or(1));
+    }
+    let header = [0x0000, freq_word, (cycles.len() / 2) as u16, 0x0000];
+    header
+        .iter()
+        .chain(cycles.iter())
+        .map(|w| format!("{w:04X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a learned Pronto code into (durations in µs, carrier in Hz). The
+/// once and repeat sequences are concatenated.
+pub fn decode_pronto(pronto: &str) -> Result<(Vec<u32>, u32), String> {
+    let words = pronto
+        .split_whitespace()
+        .map(|w| u16::from_str_radix(w, 16).map_err(|_| format!("Invalid Pronto word '{w}'")))
+        .collect::<Result<Vec<_>, _>>()?;
+    let &[kind, freq_word, once, repeat, ref rest @ ..] = words.as_slice() else {
+        return Err("Pronto code needs at least a four-word header".to_string());
+    };
+    if kind != 0x0000 {
+        return Err(format!("Only learned Pronto codes (0000) are supported, got {kind:04X}"));
+    }
+    if freq_word == 0 {
+        return Err("Pronto code has a zero carrier frequency".to_string());
+    }
+    let pairs = (once as usize + repeat as usize) * 2;
+    if rest.len() < pairs || pairs == 0 {
+        return Err(format!("Pronto code declares {pairs} durations but has {}", rest.len()));
+    }
+    let period_us = freq_word as f64 * PRONTO_CLOCK_US;
+    let timings = rest[..pairs].iter().map(|&c| (c as f64 * period_us).round() as u32).collect();
+    Ok((timings, (1_000_000.0 / period_us).round() as u32))
+}
+
+/// Convert an IR code between Broadlink hex, Pronto hex and raw timings.
+/// The input format is detected: Pronto is space-separated 4-digit words
+/// starting with `0000`, anything else is read as Broadlink hex.
+pub fn convert_ir_code(code: &str, to: IrCodeFormat) -> Result<String, String> {
+    let code = code.trim();
+    let (timings, carrier_hz, repeat) = if code.contains(char::is_whitespace) {
+        let (timings, carrier_hz) = decode_pronto(code)?;
+        (timings, carrier_hz, 0)
+    } else {
+        let bytes = hex::decode(code).map_err(|e| format!("Invalid hex code: {}", e))?;
+        if matches!(to, IrCodeFormat::Pronto) && bytes.first() != Some(&BROADLINK_IR) {
+            return Err("Only IR codes can be exported as Pronto".to_string());
+        }
+        (decode_broadlink_timings(&bytes)?, DEFAULT_CARRIER_HZ, bytes.get(1).copied().unwrap_or(0))
+    };
+    Ok(match to {
+        IrCodeFormat::Pronto => encode_pronto(&timings, carrier_hz),
+        IrCodeFormat::Broadlink => hex::encode(encode_broadlink_timings(&timings, repeat)),
+        IrCodeFormat::RawTimings => timings
+            .iter()
+            .enumerate()
+            .map(|(i, us)| format!("{}{us}", if i % 2 == 0 { '+' } else { '-' }))
+            .collect::<Vec<_>>()
+            .join(" "),
+    })
+}
+
+/// Pulse count and length of a code, for reports that describe codes
+/// without the raw hex.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingSummary {
+    pub pulses: usize,
+    pub duration_ms: f64,
+}
+
+/// Decode a Broadlink hex (IR or RF) or Pronto code and summarize its timings.
+pub fn timing_summary(code: &str) -> Result<TimingSummary, String> {
+    let code = code.trim();
+    let timings = if code.contains(char::is_whitespace) {
+        decode_pronto(code)?.0
+    } else {
+        let bytes = hex::decode(code).map_err(|e| format!("Invalid hex code: {}", e))?;
+        decode_broadlink_timings(&bytes)?
+    };
+    let total_us: u64 = timings.iter().map(|&us| us as u64).sum();
+    Ok(TimingSummary { pulses: timings.len(), duration_ms: total_us as f64 / 1000.0 })
+}
+
+/// Type bytes Broadlink uses for RF captures: 433 MHz and 315 MHz.
+const BROADLINK_RF_TYPES: &[u8] = &[0xb2, 0xd7];
+/// Captures with fewer on/off transitions than this are almost always noise;
+/// even the simplest remote protocols send a few dozen.
+const MIN_TRANSITIONS: usize = 16;
+/// Score below which the UI should suggest learning again.
+pub const MIN_GOOD_QUALITY: u8 = 60;
+
+/// Plausibility assessment of a learned code.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeQuality {
+    /// 0–100; below [`MIN_GOOD_QUALITY`] the capture likely won't work.
+    pub score: u8,
+    pub transitions: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Check that a learned code decodes, has a plausible number of timing
+/// transitions and, for RF, carries a captured frequency band.
+pub fn validate_ir_code(code_hex: &str, signal_type: &str) -> CodeQuality {
+    let mut warnings = Vec::new();
+    let mut score: i32 = 100;
+
+    let bytes = match hex::decode(code_hex.trim()) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return CodeQuality { score: 0, transitions: 0, warnings: vec![format!("Invalid hex code: {e}")] };
+        }
+    };
+    let transitions = match decode_broadlink_timings(&bytes) {
+        Ok(timings) => timings.len(),
+        Err(e) => {
+            return CodeQuality { score: 0, transitions: 0, warnings: vec![e] };
+        }
+    };
+
+    let declared = bytes.get(2..4).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize).unwrap_or(0);
+    if bytes.len() < 4 + declared {
+        warnings.push(format!("Capture is truncated ({} of {} pulse bytes)", bytes.len() - 4, declared));
+        score -= 40;
+    }
+    if transitions < MIN_TRANSITIONS {
+        warnings.push(format!("Only {transitions} transitions captured; this looks like noise"));
+        score -= 50;
+    }
+
+    let type_byte = bytes[0];
+    if signal_type == "rf" {
+        if !BROADLINK_RF_TYPES.contains(&type_byte) {
+            warnings.push("No RF frequency was captured".to_string());
+            score -= 50;
+        }
+    } else if type_byte != BROADLINK_IR {
+        warnings.push(format!("Unexpected signal type byte 0x{type_byte:02x} for an IR code"));
+        score -= 30;
+    }
+
+    CodeQuality { score: score.clamp(0, 100) as u8, transitions, warnings }
+}
+
+/// Test if a device is reachable using raw UDP ping on `port` (default 80)
 pub async fn test_device(
     host: &str,
     _mac: &str,
     _devtype: &str,
+    port: Option<u16>,
 ) -> Result<bool, String> {
-    let host = host.to_string();
+    let Ok(device_ip) = resolve_ipv4(host).await else {
+        return Ok(false);
+    };
 
     tokio::task::spawn_blocking(move || {
-        // Parse the IP address
-        let device_ip: Ipv4Addr = match host.parse() {
-            Ok(ip) => ip,
-            Err(_) => return Ok(false),
-        };
-
         // Get the best local IP for this device
         let local_ip = match get_local_ip_for_device(device_ip) {
             Some(ip) => ip,
             None => return Ok(false),
         };
 
-        // Send a discovery packet directly to the device (not broadcast)
-        let mut packet = vec![0u8; 0x30];
+        let port = port.unwrap_or(DEFAULT_PORT);
+        Ok(ping_port(device_ip, local_ip, port, Duration::from_secs(3)).is_some())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
 
-        // Local IP
-        let octets = local_ip.octets();
-        packet[0x18] = octets[0];
-        packet[0x19] = octets[1];
-        packet[0x1a] = octets[2];
-        packet[0x1b] = octets[3];
+/// Send a unicast discovery packet to `device_ip:port` and return the round
+/// trip time if a valid response came back within `timeout`.
+fn ping_port(
+    device_ip: Ipv4Addr,
+    local_ip: Ipv4Addr,
+    port: u16,
+    timeout: Duration,
+) -> Option<Duration> {
+    // Send a discovery packet directly to the device (not broadcast)
+    let mut packet = vec![0u8; 0x30];
 
-        // Command: discover (0x0006)
-        packet[0x26] = 0x06;
+    // Local IP
+    let octets = local_ip.octets();
+    packet[0x18] = octets[0];
+    packet[0x19] = octets[1];
+    packet[0x1a] = octets[2];
+    packet[0x1b] = octets[3];
 
-        // Calculate checksum
-        let mut checksum: u16 = 0xbeaf;
-        for byte in &packet {
-            checksum = checksum.wrapping_add(*byte as u16);
-        }
-        packet[0x20] = (checksum & 0xff) as u8;
-        packet[0x21] = ((checksum >> 8) & 0xff) as u8;
+    // Command: discover (0x0006)
+    packet[0x26] = 0x06;
 
-        // Bind socket
-        let bind_addr = SocketAddr::new(local_ip.into(), 0);
-        let socket = match UdpSocket::bind(bind_addr) {
-            Ok(s) => s,
-            Err(_) => return Ok(false),
-        };
+    // Calculate checksum
+    let mut checksum: u16 = 0xbeaf;
+    for byte in &packet {
+        checksum = checksum.wrapping_add(*byte as u16);
+    }
+    packet[0x20] = (checksum & 0xff) as u8;
+    packet[0x21] = ((checksum >> 8) & 0xff) as u8;
+
+    // Bind socket
+    let bind_addr = SocketAddr::new(local_ip.into(), 0);
+    let socket = match UdpSocket::bind(bind_addr) {
+        Ok(s) => s,
+        Err(_) => return None,
+    };
+
+    if socket.set_read_timeout(Some(timeout)).is_err() {
+        return None;
+    }
+
+    // Send directly to the device on the given port
+    let device_addr = SocketAddr::new(device_ip.into(), port);
+    let sent_at = Instant::now();
+    if socket.send_to(&packet, device_addr).is_err() {
+        return None;
+    }
+
+    // Wait for response
+    let mut buf = [0u8; 256];
+    match socket.recv_from(&mut buf) {
+        Ok((len, _)) if len >= 0x40 => Some(sent_at.elapsed()), // Valid response is at least 64 bytes
+        _ => None,
+    }
+}
 
-        if socket.set_read_timeout(Some(Duration::from_secs(3))).is_err() {
-            return Ok(false);
+/// Find the UDP port a device answers discovery on: the protocol's port 80
+/// first, then any `alternates` the user has seen the device use. Returns
+/// `None` when none of them respond.
+pub async fn probe_device_port(host: &str, alternates: &[u16]) -> Result<Option<u16>, String> {
+    let device_ip = resolve_ipv4(host).await?;
+    let mut candidates = vec![DEFAULT_PORT];
+    for &port in alternates {
+        if port != 0 && !candidates.contains(&port) {
+            candidates.push(port);
         }
+    }
 
-        // Send directly to device on port 80
-        let device_addr = SocketAddr::new(device_ip.into(), 80);
-        if socket.send_to(&packet, device_addr).is_err() {
-            return Ok(false);
+    tokio::task::spawn_blocking(move || {
+        let local_ip = get_local_ip_for_device(device_ip)
+            .ok_or_else(|| "No suitable local IP found".to_string())?;
+        let port = candidates
+            .iter()
+            .copied()
+            .find(|&port| ping_port(device_ip, local_ip, port, Duration::from_secs(2)).is_some());
+        match port {
+            Some(port) => tracing::info!("Device {} responds on port {}", device_ip, port),
+            None => tracing::warn!("Device {} did not respond on ports {:?}", device_ip, candidates),
         }
+        Ok(port)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
 
-        // Wait for response
-        let mut buf = [0u8; 256];
-        match socket.recv_from(&mut buf) {
-            Ok((len, _)) => Ok(len >= 0x40), // Valid response is at least 64 bytes
-            Err(_) => Ok(false),
+/// Round-trip statistics from [`ping_device`]. Times are `None` when no
+/// packet was answered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingStats {
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f64,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+}
+
+/// Most probes a single ping run sends.
+const MAX_PING_COUNT: u32 = 20;
+
+/// Send `count` directed discovery packets to a device and time each reply.
+pub async fn ping_device(host: &str, count: u32) -> Result<PingStats, String> {
+    let device_ip = resolve_ipv4(host).await?;
+    let count = count.clamp(1, MAX_PING_COUNT);
+
+    tokio::task::spawn_blocking(move || {
+        let local_ip = get_local_ip_for_device(device_ip)
+            .ok_or_else(|| "No suitable local IP found".to_string())?;
+
+        let mut rtts_ms = Vec::new();
+        for i in 0..count {
+            if i > 0 {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            if let Some(rtt) = ping_port(device_ip, local_ip, DEFAULT_PORT, Duration::from_secs(2)) {
+                rtts_ms.push(rtt.as_secs_f64() * 1000.0);
+            }
         }
+
+        let received = rtts_ms.len() as u32;
+        let min_ms = rtts_ms.iter().copied().reduce(f64::min);
+        let max_ms = rtts_ms.iter().copied().reduce(f64::max);
+        let avg_ms = (received > 0).then(|| rtts_ms.iter().sum::<f64>() / received as f64);
+        Ok(PingStats {
+            sent: count,
+            received,
+            loss_percent: f64::from(count - received) * 100.0 / f64::from(count),
+            min_ms,
+            avg_ms,
+            max_ms,
+        })
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
@@ -990,3 +1546,112 @@ pub async fn list_network_interfaces() -> Result<Vec<(String, String)>, String>
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
+
+/// One IPv4 address of a network interface, with its subnet and gateway.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterfaceInfo {
+    pub name: String,
+    pub ip: String,
+    pub netmask: String,
+    pub broadcast: String,
+    /// Default gateway reached through this interface, if any.
+    pub gateway: Option<String>,
+    pub is_up: bool,
+    pub is_loopback: bool,
+}
+
+/// Every IPv4 address on every interface, including loopback and virtual
+/// ones, for directed-broadcast discovery and support diagnostics.
+pub async fn list_network_interfaces_detailed() -> Result<Vec<NetworkInterfaceInfo>, String> {
+    tokio::task::spawn_blocking(|| {
+        let mut interfaces = Vec::new();
+        for iface in netdev::get_interfaces() {
+            let name = iface.friendly_name.clone().unwrap_or_else(|| iface.name.clone());
+            let gateway = iface
+                .gateway
+                .as_ref()
+                .and_then(|gw| gw.ipv4.first())
+                .map(|ip| ip.to_string());
+            for net in &iface.ipv4 {
+                interfaces.push(NetworkInterfaceInfo {
+                    name: name.clone(),
+                    ip: net.addr().to_string(),
+                    netmask: net.netmask().to_string(),
+                    broadcast: net.broadcast().to_string(),
+                    gateway: gateway.clone(),
+                    is_up: iface.is_up(),
+                    is_loopback: iface.is_loopback(),
+                });
+            }
+        }
+        Ok(interfaces)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A short NEC-style capture: leader, four bits and the trailing gap,
+    /// laid out as the device returns it.
+    const SAMPLE: &str = "2600120000010f88111111331133113311000d050d0500000000000000000000";
+    const SAMPLE_PRONTO: &str =
+        "0000 006D 0006 0000 0152 00AA 0015 0015 0015 0040 0015 0040 0015 0040 0015 1042";
+
+    #[test]
+    fn broadlink_to_pronto_and_back() {
+        let pronto = convert_ir_code(SAMPLE, IrCodeFormat::Pronto).unwrap();
+        assert_eq!(pronto, SAMPLE_PRONTO);
+        let broadlink = convert_ir_code(&pronto, IrCodeFormat::Broadlink).unwrap();
+        assert_eq!(broadlink, SAMPLE);
+    }
+
+    #[test]
+    fn broadlink_round_trip_keeps_repeat_byte() {
+        let repeated = format!("2603{}", &SAMPLE[4..]);
+        let broadlink = convert_ir_code(&repeated, IrCodeFormat::Broadlink).unwrap();
+        assert_eq!(broadlink, repeated);
+    }
+
+    #[test]
+    fn raw_timings_stop_at_terminator() {
+        let raw = convert_ir_code(SAMPLE, IrCodeFormat::RawTimings).unwrap();
+        assert_eq!(
+            raw,
+            "+8898 -4465 +558 -558 +558 -1674 +558 -1674 +558 -1674 +558 -109445"
+        );
+    }
+
+    #[test]
+    fn parse_mac_accepts_common_separators() {
+        let expected = [0xaa, 0xbb, 0xcc, 0x0d, 0xee, 0xff];
+        for mac in [
+            "aa:bb:cc:0d:ee:ff",
+            "AA-BB-CC-0D-EE-FF",
+            "aabb.cc0d.eeff",
+            "aabbcc0deeff",
+            " aa:bb:cc:d:ee:ff ",
+        ] {
+            assert_eq!(parse_mac(mac).unwrap(), expected, "{mac}");
+        }
+    }
+
+    #[test]
+    fn parse_mac_rejects_malformed_input() {
+        for mac in [
+            "",
+            "aa:bb:cc:dd:ee",
+            "aa:bb:cc:dd:ee:ff:00",
+            "aa:bb:cc::ee:ff",
+            "aa:bb:cc:+d:ee:ff",
+            "aa-bb:cc-dd:ee-ff",
+            "aabbccddeef",
+            "aabbccddeegg",
+        ] {
+            assert!(parse_mac(mac).is_err(), "{mac}");
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use crate::server::caption;
+
+/// Show `text` on the `/caption` overlay, clearing it after `duration_ms`
+/// when given (0 or omitted keeps it until replaced). Empty text clears it.
+/// `caption_type` limits it to one layout (`caption`, `full`, `preview`).
+#[tauri::command]
+pub fn set_live_caption(
+    text: String,
+    caption_type: Option<String>,
+    duration_ms: Option<u64>,
+) -> Option<caption::LiveCaption> {
+    let duration = duration_ms.filter(|&ms| ms > 0).map(Duration::from_millis);
+    caption::set_live_caption(text, caption_type, duration)
+}
+
+#[tauri::command]
+pub fn get_live_caption() -> Option<caption::LiveCaption> {
+    caption::live_caption()
+}
@@ -0,0 +1,24 @@
+/// Patch an exported `.companionconfig` to point at `target_url` and carry
+/// `token`, returning the JSON for the user to import into Companion.
+#[tauri::command]
+pub fn patch_companion_config(
+    config_json: String,
+    target_url: String,
+    token: String,
+) -> Result<String, String> {
+    crate::companion::patch_config(&config_json, &target_url, &token)
+}
+
+/// Press a Companion button remotely to check its actions end to end.
+/// `companion_url` defaults to Companion on this machine.
+#[tauri::command]
+pub async fn press_companion_button(
+    page: u32,
+    row: u32,
+    column: u32,
+    companion_url: Option<String>,
+) -> Result<(), String> {
+    let url = companion_url.unwrap_or_else(|| crate::companion::DEFAULT_COMPANION_URL.to_string());
+    let api = crate::companion::CompanionApi::new(&url)?;
+    Ok(api.press_button(page, row, column).await?)
+}
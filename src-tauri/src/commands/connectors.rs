@@ -112,6 +112,19 @@ pub async fn disconnect_obs(
     Ok(())
 }
 
+/// Attempts a one-off obs-websocket handshake with the given settings and
+/// reports whether OBS is unreachable, rejected the password, runs an
+/// unsupported version, or connected (with its versions).
+#[tauri::command]
+pub async fn check_obs_connection(
+    host: String,
+    port: u16,
+    password: Option<String>,
+) -> Result<crate::connectors::obs::ObsConnectionCheck, String> {
+    let password = password.filter(|p| !p.is_empty());
+    Ok(crate::connectors::obs::check_connection(host.trim(), port, password.as_deref()).await)
+}
+
 /// Returns the current OBS stream service settings (server URL and stream key).
 /// Fails if OBS is not connected.
 #[tauri::command]
@@ -172,6 +185,75 @@ pub async fn set_obs_stream_settings(
         .map_err(|e| e.to_string())
 }
 
+/// Returns the directory OBS currently writes recordings to, so the upload
+/// screen can use it as the scan directory. Fails if OBS is not connected.
+#[tauri::command]
+pub async fn get_obs_recording_directory(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<String, String> {
+    let obs_connector = {
+        let rt = runtime.read().await;
+        Arc::clone(&rt.obs_connector)
+    };
+    Ok(obs_connector.record_directory().await?)
+}
+
+/// Lists OBS scenes and the current program scene. Fails if OBS is not connected.
+#[tauri::command]
+pub async fn list_obs_scenes(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<crate::connectors::obs::ObsSceneList, String> {
+    let obs_connector = {
+        let rt = runtime.read().await;
+        Arc::clone(&rt.obs_connector)
+    };
+    Ok(obs_connector.list_scenes().await?)
+}
+
+/// Switches the OBS program output to `scene_name`. Fails if OBS is not connected.
+#[tauri::command]
+pub async fn set_obs_scene(
+    scene_name: String,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<(), String> {
+    let obs_connector = {
+        let rt = runtime.read().await;
+        Arc::clone(&rt.obs_connector)
+    };
+    Ok(obs_connector.set_scene(&scene_name).await?)
+}
+
+/// Shows or hides `source` within `scene`. Fails if OBS is not connected or
+/// the scene or source doesn't exist.
+#[tauri::command]
+pub async fn set_obs_source_enabled(
+    scene: String,
+    source: String,
+    enabled: bool,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<(), String> {
+    let obs_connector = {
+        let rt = runtime.read().await;
+        Arc::clone(&rt.obs_connector)
+    };
+    Ok(obs_connector.set_source_enabled(&scene, &source, enabled).await?)
+}
+
+/// Replaces the text of an OBS text source. Fails if OBS is not connected,
+/// the source doesn't exist, or it isn't a text source.
+#[tauri::command]
+pub async fn set_obs_text(
+    source_name: String,
+    text: String,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<(), String> {
+    let obs_connector = {
+        let rt = runtime.read().await;
+        Arc::clone(&rt.obs_connector)
+    };
+    Ok(obs_connector.set_text(&source_name, &text).await?)
+}
+
 // ── VMix (stubs) ─────────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -479,6 +561,7 @@ pub fn get_broadlink_config(app: AppHandle) -> Result<BroadlinkConfig, String> {
 
 #[tauri::command]
 pub fn save_broadlink_config(config: BroadlinkConfig, app: AppHandle) -> Result<(), String> {
+    crate::broadlink::configure_interface_filter(&config.interface_include, &config.interface_exclude);
     let store = app.store("app-settings.json").map_err(|e| e.to_string())?;
     store.set(
         "broadlink_config",
@@ -517,8 +600,9 @@ pub async fn broadlink_learn(
     mac: String,
     devtype: String,
     signal_type: String,
+    port: Option<u16>,
 ) -> Result<crate::broadlink::LearnResult, String> {
-    crate::broadlink::learn_code(&host, &mac, &devtype, &signal_type).await
+    crate::broadlink::learn_code(&host, &mac, &devtype, &signal_type, port).await
 }
 
 #[tauri::command]
@@ -532,8 +616,35 @@ pub async fn broadlink_send(
     mac: String,
     devtype: String,
     code: String,
+    port: Option<u16>,
 ) -> Result<crate::broadlink::SendResult, String> {
-    crate::broadlink::send_code(&host, &mac, &devtype, &code).await
+    crate::broadlink::send_code(&host, &mac, &devtype, &code, port).await
+}
+
+#[tauri::command]
+pub async fn broadlink_send_last_learned(
+    host: String,
+    mac: String,
+    devtype: String,
+    port: Option<u16>,
+) -> Result<crate::broadlink::SendResult, String> {
+    crate::broadlink::send_last_learned(&host, &mac, &devtype, port).await
+}
+
+/// Convert a learned code for export: `to` is "pronto", "broadlink" or
+/// "raw_timings". Accepts Broadlink hex or a learned Pronto code.
+#[tauri::command]
+pub fn broadlink_convert_code(
+    code: String,
+    to: crate::broadlink::IrCodeFormat,
+) -> Result<String, String> {
+    crate::broadlink::convert_ir_code(&code, to)
+}
+
+/// Score a learned code's plausibility before saving it.
+#[tauri::command]
+pub fn broadlink_validate_code(code: String, signal_type: String) -> crate::broadlink::CodeQuality {
+    crate::broadlink::validate_ir_code(&code, &signal_type)
 }
 
 #[tauri::command]
@@ -541,8 +652,23 @@ pub async fn broadlink_test_device(
     host: String,
     mac: String,
     devtype: String,
+    port: Option<u16>,
 ) -> Result<bool, String> {
-    crate::broadlink::test_device(&host, &mac, &devtype).await
+    crate::broadlink::test_device(&host, &mac, &devtype, port).await
+}
+
+/// Round-trip latency and packet loss to a device, for troubleshooting slow
+/// responses. `count` is capped at 20 probes.
+#[tauri::command]
+pub async fn broadlink_ping(host: String, count: u32) -> Result<crate::broadlink::PingStats, String> {
+    crate::broadlink::ping_device(&host, count).await
+}
+
+/// Check which UDP port a device answers on: 80, then any `ports` the user
+/// has seen it use. Pass the result as `port` to the learn/send commands.
+#[tauri::command]
+pub async fn broadlink_probe_port(host: String, ports: Option<Vec<u16>>) -> Result<Option<u16>, String> {
+    crate::broadlink::probe_device_port(&host, &ports.unwrap_or_default()).await
 }
 
 #[tauri::command]
@@ -550,3 +676,22 @@ pub async fn broadlink_list_interfaces() -> Result<Vec<(String, String)>, String
     crate::broadlink::list_network_interfaces().await
 }
 
+/// Resolve a hostname (including `.local` mDNS names) for manual device or
+/// OBS entry. Returns every address found, IPv4 and IPv6.
+#[tauri::command]
+pub async fn resolve_host(host: String) -> Result<Vec<String>, String> {
+    Ok(crate::broadlink::resolve_host(&host)
+        .await?
+        .into_iter()
+        .map(|ip| ip.to_string())
+        .collect())
+}
+
+/// Interfaces with netmask, broadcast address and gateway, for
+/// troubleshooting discovery.
+#[tauri::command]
+pub async fn list_network_interfaces_detailed(
+) -> Result<Vec<crate::broadlink::NetworkInterfaceInfo>, String> {
+    crate::broadlink::list_network_interfaces_detailed().await
+}
+
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use tauri_plugin_store::StoreExt;
+
+use crate::http::{self, HttpProxyConfig};
+
+/// Proxy settings as shown to the UI. The password never leaves the backend.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpProxySettings {
+    pub url: String,
+    pub username: Option<String>,
+    pub has_password: bool,
+}
+
+fn stored_proxy(store: &tauri_plugin_store::Store<tauri::Wry>) -> Option<HttpProxyConfig> {
+    store
+        .get("http_proxy")
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+#[tauri::command]
+pub async fn get_http_proxy(app: tauri::AppHandle) -> Result<Option<HttpProxySettings>, String> {
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+    Ok(stored_proxy(&store).map(|cfg| HttpProxySettings {
+        url: cfg.url,
+        username: cfg.username,
+        has_password: cfg.password.is_some_and(|p| !p.is_empty()),
+    }))
+}
+
+/// Route outbound HTTP through `url`; an empty or missing URL disables the proxy.
+/// A missing `password` keeps the stored one; an empty string clears it.
+#[tauri::command]
+pub async fn set_http_proxy(
+    url: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+
+    match url.filter(|u| !u.trim().is_empty()) {
+        Some(url) => {
+            let password = match password {
+                Some(p) if p.is_empty() => None,
+                Some(p) => Some(p),
+                None => stored_proxy(&store).and_then(|cfg| cfg.password),
+            };
+            let cfg = HttpProxyConfig { url, username, password };
+            http::configure_proxy(Some(&cfg))?;
+            store.set(
+                "http_proxy",
+                serde_json::to_value(&cfg).map_err(|e| e.to_string())?,
+            );
+        }
+        None => {
+            http::configure_proxy(None)?;
+            store.delete("http_proxy");
+        }
+    }
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use tracing::Level;
+
+use crate::logs::{self, LogEntry};
+
+/// Return the most recent in-memory log lines for the support screen.
+///
+/// `level_filter` is the minimum severity (`"error"`, `"warn"`, `"info"`,
+/// `"debug"`, `"trace"`); defaults to `"trace"`. `limit` defaults to 200.
+#[tauri::command]
+pub async fn get_recent_logs(
+    level_filter: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<LogEntry>, String> {
+    let level = match level_filter.as_deref() {
+        None | Some("") => Level::TRACE,
+        Some(s) => s
+            .parse::<Level>()
+            .map_err(|_| format!("Invalid level filter: {s}"))?,
+    };
+    Ok(logs::recent(level, limit.unwrap_or(200)))
+}
@@ -1,10 +1,23 @@
 #[cfg(desktop)]
 pub mod badge;
 #[cfg(desktop)]
+pub mod caption;
+#[cfg(desktop)]
 pub mod collections;
 #[cfg(desktop)]
+pub mod companion;
+#[cfg(desktop)]
 pub mod connectors;
 #[cfg(desktop)]
+pub mod pairing;
+#[cfg(desktop)]
+pub mod presentation;
+#[cfg(desktop)]
+pub mod recordings;
+#[cfg(desktop)]
 pub mod updater;
+pub mod http;
+pub mod logs;
 pub mod server;
+pub mod settings;
 pub mod token;
@@ -0,0 +1,60 @@
+use base64::Engine;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+
+use crate::AppRuntime;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingQr {
+    /// The URL encoded in the QR code.
+    pub url: String,
+    /// PNG image of the QR code, base64-encoded (no `data:` prefix).
+    pub png_base64: String,
+}
+
+/// Build the pairing URL a phone scans to connect to this server.
+pub(crate) fn pairing_url(host: &str, port: u16, token: &str) -> String {
+    format!(
+        "metocast://pair?host={}&port={}&token={}",
+        urlencoding::encode(host),
+        port,
+        urlencoding::encode(token),
+    )
+}
+
+/// Render a QR code for the pairing URL using the primary LAN address and the
+/// current auth token.
+#[tauri::command]
+pub async fn generate_pairing_qr(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<PairingQr, String> {
+    let (bind_address, port, auth_token) = {
+        let rt = runtime.read().await;
+        (rt.server_bind_address, rt.server_port, rt.auth_token.clone())
+    };
+    let token = auth_token.read().await.clone();
+    let host = if bind_address.is_unspecified() {
+        super::server::get_local_ip().ok_or("Could not determine the LAN address")?
+    } else {
+        bind_address.to_string()
+    };
+    let url = pairing_url(&host, port, &token);
+
+    let code = qrcode::QrCode::new(url.as_bytes()).map_err(|e| format!("QR encode failed: {e}"))?;
+    let img = code
+        .render::<image::Luma<u8>>()
+        .min_dimensions(320, 320)
+        .build();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageLuma8(img)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("PNG encode failed: {e}"))?;
+
+    Ok(PairingQr {
+        url,
+        png_base64: base64::engine::general_purpose::STANDARD.encode(png),
+    })
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+
+use crate::server::presenter::{self, FileValidation};
+use crate::AppRuntime;
+
+/// Check that a presentation file opens and has slides, without launching it.
+#[tauri::command]
+pub async fn presentation_validate_file(path: String) -> Result<FileValidation, String> {
+    tokio::task::spawn_blocking(move || presenter::validate_presentation_file(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Send a named slideshow shortcut (e.g. `arrow`, `white`, `escape`) to the
+/// presentation app. Only a fixed allowlist of keys is accepted.
+#[tauri::command]
+pub async fn send_presentation_key(
+    key: String,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let keynote = Arc::clone(&runtime.read().await.keynote_connector);
+        keynote.send_key(key.trim()).await
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (key, runtime);
+        Err("Presentation keystrokes are only available on macOS".to_string())
+    }
+}
+
+/// Initialise the presentation controller in the background so the first
+/// command is fast. Safe to call repeatedly; never fails, even when the
+/// presentation app isn't installed.
+#[tauri::command]
+pub async fn presentation_warmup(runtime: State<'_, Arc<RwLock<AppRuntime>>>) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let keynote = Arc::clone(&runtime.read().await.keynote_connector);
+        tokio::spawn(async move { keynote.warm_up().await });
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = runtime;
+    }
+    Ok(())
+}
+
+/// Capture the display the slideshow is projected on as a base64 PNG,
+/// optionally downscaled to `max_width` pixels.
+#[tauri::command]
+pub async fn capture_slideshow_screenshot(max_width: Option<u32>) -> Result<String, String> {
+    use base64::Engine;
+
+    let capture = tokio::task::spawn_blocking(move || {
+        crate::displays::capture_presentation_display(max_width)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    Ok(base64::engine::general_purpose::STANDARD.encode(capture.png))
+}
+
+/// List connected monitors with resolution, position and which is primary,
+/// so the UI can confirm the projector is attached as a secondary display.
+#[tauri::command]
+pub async fn list_displays() -> Result<Vec<crate::displays::DisplayInfo>, String> {
+    tokio::task::spawn_blocking(crate::displays::list_displays)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn presentation_get_watchdog_interval(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<u64, String> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(runtime.read().await.keynote_connector.watchdog_interval_secs())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = runtime;
+        Ok(0)
+    }
+}
+
+/// Set how often (in seconds) the presentation app is checked for an
+/// unexpected exit while a file is open; `0` disables the check. Persisted
+/// and applied from the next opened file.
+#[tauri::command]
+pub async fn presentation_set_watchdog_interval(
+    secs: u64,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+    store.set("presentation_watchdog_interval_secs", serde_json::Value::from(secs));
+    store.save().map_err(|e| e.to_string())?;
+
+    #[cfg(target_os = "macos")]
+    runtime.read().await.keynote_connector.set_watchdog_interval_secs(secs);
+    #[cfg(not(target_os = "macos"))]
+    let _ = runtime;
+    Ok(())
+}
@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde_json::json;
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+/// Tauri event emitted when a video file appears in the watched directory;
+/// payload `{ path }`.
+pub const RECORDING_CREATED_EVENT: &str = "recording-created";
+/// Tauri event emitted once that file has stopped growing; payload
+/// `{ path, sizeBytes }`.
+pub const RECORDING_FINALIZED_EVENT: &str = "recording-finalized";
+
+/// Container formats OBS can record to.
+const VIDEO_EXTENSIONS: &[&str] = &["mkv", "mp4", "mov", "flv", "ts", "m4v"];
+const SIZE_POLL: Duration = Duration::from_secs(2);
+/// Consecutive unchanged size readings before a file counts as finalized.
+const STABLE_POLLS: u32 = 3;
+
+struct RecordingWatch {
+    directory: PathBuf,
+    // Dropping the watcher stops the OS-level watch.
+    _watcher: notify::RecommendedWatcher,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static WATCH: LazyLock<Mutex<Option<RecordingWatch>>> = LazyLock::new(|| Mutex::new(None));
+
+fn is_video(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Track files reported by the watcher until their size holds still for
+/// `STABLE_POLLS` readings, then announce them as finalized.
+async fn track_recordings(app: tauri::AppHandle, mut rx: tokio::sync::mpsc::UnboundedReceiver<PathBuf>) {
+    // path → (last seen size, unchanged readings)
+    let mut pending: HashMap<PathBuf, (u64, u32)> = HashMap::new();
+    let mut interval = tokio::time::interval(SIZE_POLL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            path = rx.recv() => {
+                let Some(path) = path else { return };
+                // Rename events also report the old name, which no longer exists.
+                if pending.contains_key(&path) || !path.exists() {
+                    continue;
+                }
+                tracing::info!("Recording started: {}", path.display());
+                let _ = app.emit(RECORDING_CREATED_EVENT, json!({ "path": path.to_string_lossy() }));
+                pending.insert(path, (0, 0));
+            }
+            _ = interval.tick() => {
+                let mut done = Vec::new();
+                for (path, (size, stable)) in pending.iter_mut() {
+                    match tokio::fs::metadata(path).await {
+                        Ok(meta) if meta.len() > 0 && meta.len() == *size => {
+                            *stable += 1;
+                            if *stable >= STABLE_POLLS {
+                                done.push((path.clone(), Some(meta.len())));
+                            }
+                        }
+                        Ok(meta) => {
+                            *size = meta.len();
+                            *stable = 0;
+                        }
+                        // Deleted before it finished; drop it silently.
+                        Err(_) => done.push((path.clone(), None)),
+                    }
+                }
+                for (path, size) in done {
+                    pending.remove(&path);
+                    if let Some(size) = size {
+                        tracing::info!("Recording finalized: {} ({size} bytes)", path.display());
+                        let _ = app.emit(
+                            RECORDING_FINALIZED_EVENT,
+                            json!({ "path": path.to_string_lossy(), "sizeBytes": size }),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Watch `directory` (not recursively) for new video files, emitting
+/// `recording-created` when one appears and `recording-finalized` once its
+/// size has been stable for a few seconds. Replaces any previous watch.
+#[tauri::command]
+pub async fn watch_recording_directory(directory: String, app: tauri::AppHandle) -> Result<(), String> {
+    let directory = PathBuf::from(directory.trim());
+    if !directory.is_dir() {
+        return Err(format!("'{}' is not a directory", directory.display()));
+    }
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        if matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_))
+        ) {
+            for path in event.paths.into_iter().filter(|p| is_video(p)) {
+                let _ = tx.send(path);
+            }
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Could not watch '{}': {e}", directory.display()))?;
+
+    tracing::info!("Watching recording directory {}", directory.display());
+    let task = tauri::async_runtime::spawn(track_recordings(app, rx));
+    let previous = WATCH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(RecordingWatch { directory, _watcher: watcher, task });
+    if let Some(previous) = previous {
+        previous.task.abort();
+    }
+    Ok(())
+}
+
+/// Stop the watch started by `watch_recording_directory`. Returns whether
+/// one was running.
+#[tauri::command]
+pub fn stop_watching_recording_directory() -> bool {
+    let Some(watch) = WATCH.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+        return false;
+    };
+    watch.task.abort();
+    tracing::info!("Stopped watching {}", watch.directory.display());
+    true
+}
+
+// ── ffmpeg post-processing ───────────────────────────────────────────────────
+
+/// Tauri event emitted while an ffmpeg job runs; payload
+/// `{ path, operation, percent }` with `operation` `"split"` or `"extract_audio"`.
+pub const RECORDING_PROCESS_PROGRESS_EVENT: &str = "recording-process-progress";
+
+async fn ensure_ffmpeg() -> Result<(), String> {
+    match tokio::process::Command::new("ffmpeg").arg("-version").output().await {
+        Ok(o) if o.status.success() => Ok(()),
+        _ => Err("ffmpeg was not found; install it and make sure it is on PATH".to_string()),
+    }
+}
+
+/// Run ffmpeg with `args`, calling `on_progress` with the seconds of output
+/// written so far. Refuses to overwrite existing outputs.
+async fn run_ffmpeg(args: &[&dyn AsRef<OsStr>], mut on_progress: impl FnMut(f64)) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostats", "-loglevel", "error", "-progress", "pipe:1", "-n"])
+        .args(args.iter().map(|a| a.as_ref()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not start ffmpeg: {e}"))?;
+
+    // Drain stderr alongside stdout: a damaged input can log enough to fill
+    // the pipe, which would block ffmpeg before stdout ever closes.
+    let stderr = child.stderr.take();
+    let stdout = child.stdout.take();
+    let read_stderr = async move {
+        let mut buf = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buf).await;
+        }
+        buf
+    };
+    let read_progress = async {
+        if let Some(stdout) = stdout {
+            let mut lines = tokio::io::BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(us) = line.strip_prefix("out_time_us=").and_then(|v| v.parse::<i64>().ok()) {
+                    on_progress(us.max(0) as f64 / 1_000_000.0);
+                }
+            }
+        }
+    };
+    let (stderr, ()) = tokio::join!(read_stderr, read_progress);
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&stderr).trim()))
+    }
+}
+
+fn percent(done_secs: f64, total_secs: f64) -> f64 {
+    if total_secs > 0.0 {
+        (done_secs / total_secs * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    }
+}
+
+fn emit_progress(app: &tauri::AppHandle, path: &Path, operation: &str, percent: f64) {
+    let _ = app.emit(
+        RECORDING_PROCESS_PROGRESS_EVENT,
+        json!({ "path": path.to_string_lossy(), "operation": operation, "percent": percent }),
+    );
+}
+
+/// `<dir>/<stem><suffix>.<ext>`; `dir` defaults to the source's directory.
+fn output_path(source: &Path, dir: Option<&str>, suffix: &str, ext: &str) -> PathBuf {
+    let dir = dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| source.parent().map(Path::to_path_buf).unwrap_or_default());
+    let stem = source.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    dir.join(format!("{stem}{suffix}.{ext}"))
+}
+
+/// Split a recording at `split_at_secs` into `<name>-part1` and
+/// `<name>-part2` (same container) using stream copy, so nothing is
+/// re-encoded. Cuts land on the nearest keyframe. Returns both paths.
+#[tauri::command]
+pub async fn split_recording(
+    path: String,
+    split_at_secs: f64,
+    output_dir: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    ensure_ffmpeg().await?;
+    let source = PathBuf::from(&path);
+    if !source.is_file() {
+        return Err(format!("Recording not found: {path}"));
+    }
+    let duration = crate::server::probe_duration(&source).await;
+    if duration <= 0.0 {
+        return Err("Could not read the recording's duration (is ffprobe installed?)".to_string());
+    }
+    if !(split_at_secs > 0.0 && split_at_secs < duration) {
+        return Err(format!("Split point must be between 0 and {duration:.1} seconds"));
+    }
+
+    let ext = source
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mkv".to_string());
+    let first = output_path(&source, output_dir.as_deref(), "-part1", &ext);
+    let second = output_path(&source, output_dir.as_deref(), "-part2", &ext);
+    let split = format!("{split_at_secs:.3}");
+
+    run_ffmpeg(
+        &[&"-i", &source, &"-t", &split, &"-map", &"0", &"-c", &"copy", &first],
+        |secs| emit_progress(&app, &source, "split", percent(secs, duration)),
+    )
+    .await?;
+    // Seeking before `-i` jumps straight to the keyframe instead of decoding up to it.
+    run_ffmpeg(
+        &[
+            &"-ss", &split, &"-i", &source, &"-map", &"0", &"-c", &"copy",
+            &"-avoid_negative_ts", &"make_zero", &second,
+        ],
+        |secs| emit_progress(&app, &source, "split", percent(split_at_secs + secs, duration)),
+    )
+    .await?;
+    emit_progress(&app, &source, "split", 100.0);
+
+    Ok(vec![first.to_string_lossy().into_owned(), second.to_string_lossy().into_owned()])
+}
+
+/// Audio formats `extract_audio` can produce, with the encoder settings used.
+const AUDIO_FORMATS: &[(&str, &[&str])] = &[
+    ("mp3", &["-c:a", "libmp3lame", "-q:a", "2"]),
+    ("m4a", &["-c:a", "aac", "-b:a", "192k"]),
+];
+
+/// Write the audio track of a recording to `<name>.<format>` next to it
+/// (`mp3` or `m4a`), for the audio-only podcast. Returns the output path.
+#[tauri::command]
+pub async fn extract_audio(
+    path: String,
+    output_format: String,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let format = output_format.trim().to_lowercase();
+    let Some(&(_, codec_args)) = AUDIO_FORMATS.iter().find(|(f, _)| *f == format) else {
+        let supported: Vec<&str> = AUDIO_FORMATS.iter().map(|(f, _)| *f).collect();
+        return Err(format!(
+            "Unsupported audio format '{output_format}' (expected one of {})",
+            supported.join(", ")
+        ));
+    };
+    ensure_ffmpeg().await?;
+    let source = PathBuf::from(&path);
+    if !source.is_file() {
+        return Err(format!("Recording not found: {path}"));
+    }
+    // Without a duration the job still runs; progress just stays at 0.
+    let duration = crate::server::probe_duration(&source).await;
+    let output = output_path(&source, None, "", &format);
+
+    let mut args: Vec<&dyn AsRef<OsStr>> = vec![&"-i", &source, &"-vn", &"-map", &"0:a:0"];
+    args.extend(codec_args.iter().map(|a| a as &dyn AsRef<OsStr>));
+    args.push(&output);
+    run_ffmpeg(&args, |secs| {
+        emit_progress(&app, &source, "extract_audio", percent(secs, duration))
+    })
+    .await?;
+    emit_progress(&app, &source, "extract_audio", 100.0);
+
+    Ok(output.to_string_lossy().into_owned())
+}
+
+/// Save the frame at `at_secs` as a JPEG or PNG (picked from `output_path`'s
+/// extension), scaled to `width` pixels wide with the aspect ratio kept —
+/// e.g. for a YouTube thumbnail. An existing image at `output_path` is
+/// replaced, so trying several frames can reuse one path.
+#[tauri::command]
+pub async fn generate_recording_thumbnail(
+    path: String,
+    at_secs: f64,
+    output_path: String,
+    width: u32,
+) -> Result<String, String> {
+    let output = PathBuf::from(&output_path);
+    let ext = output
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !matches!(ext.as_str(), "jpg" | "jpeg" | "png") {
+        return Err(format!("Thumbnail must be a .jpg or .png file: {output_path}"));
+    }
+    if width == 0 {
+        return Err("Width must be at least 1 pixel".to_string());
+    }
+    ensure_ffmpeg().await?;
+    let source = PathBuf::from(&path);
+    if !source.is_file() {
+        return Err(format!("Recording not found: {path}"));
+    }
+    let duration = crate::server::probe_duration(&source).await;
+    if at_secs < 0.0 || (duration > 0.0 && at_secs >= duration) {
+        return Err(format!("Timestamp must be between 0 and {duration:.1} seconds"));
+    }
+
+    match tokio::fs::remove_file(&output).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("Could not replace {output_path}: {e}")),
+    }
+    let at = format!("{at_secs:.3}");
+    // `-2` keeps the aspect ratio with an even height, which encoders expect.
+    let scale = format!("scale={width}:-2");
+    let mut args: Vec<&dyn AsRef<OsStr>> =
+        vec![&"-ss", &at, &"-i", &source, &"-frames:v", &"1", &"-vf", &scale];
+    if ext != "png" {
+        args.extend([&"-q:v" as &dyn AsRef<OsStr>, &"2"]);
+    }
+    args.push(&output);
+    run_ffmpeg(&args, |_| {}).await?;
+
+    Ok(output.to_string_lossy().into_owned())
+}
@@ -10,6 +10,31 @@ pub async fn get_server_port(runtime: State<'_, Arc<RwLock<AppRuntime>>>) -> Res
     Ok(rt.server_port)
 }
 
+#[tauri::command]
+pub async fn get_server_bind_address(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<String, String> {
+    let rt = runtime.read().await;
+    Ok(rt.server_bind_address.to_string())
+}
+
+/// Persist the interface the server binds to (`0.0.0.0` for all, `127.0.0.1`
+/// for local-only). Takes effect the next time the server starts.
+#[tauri::command]
+pub async fn set_server_bind_address(
+    address: String,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let ip: std::net::IpAddr = address
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid bind address: {address}"))?;
+    save_setting(&app, "server_bind_address", ip.to_string()).await?;
+    runtime.write().await.server_bind_address = ip;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_app_mode(
     runtime: State<'_, Arc<RwLock<AppRuntime>>>,
@@ -29,9 +54,9 @@ pub async fn set_app_mode(
     rt.mode = Some(mode.clone());
     if let Some(u) = url {
         rt.client_url = Some(u.clone());
-        save_setting(&app, "server_url", &u).await?;
+        save_setting(&app, "server_url", u.as_str()).await?;
     }
-    save_setting(&app, "mode", &mode).await?;
+    save_setting(&app, "mode", mode.as_str()).await?;
     Ok(())
 }
 
@@ -53,7 +78,7 @@ pub async fn complete_setup(
         return Err("Server mode is not supported on mobile devices".to_string());
     }
 
-    save_setting(&app, "mode", &mode).await?;
+    save_setting(&app, "mode", mode.as_str()).await?;
 
     if mode == "client" {
         let url = server_url
@@ -78,50 +103,60 @@ pub async fn complete_setup(
         {
             let mut rt = runtime.write().await;
             rt.mode = Some(mode);
-            let auth_token_arc = rt.auth_token.clone();
-            let port = rt.server_port;
-            let obs = Arc::clone(&rt.obs_connector);
-            let vmix = Arc::clone(&rt.vmix_connector);
-            let yt = Arc::clone(&rt.youtube_connector);
-            let fb = Arc::clone(&rt.facebook_connector);
-            let bl = Arc::clone(&rt.broadlink_connector);
-            // Use the shared config Arcs from AppRuntime so that any config
-            // saved via Tauri commands is immediately visible to Axum routes.
-            let yt_cfg = Arc::clone(&rt.youtube_config);
-            let fb_cfg = Arc::clone(&rt.facebook_config);
-            let oauth = Arc::clone(&rt.oauth_states);
-            #[cfg(target_os = "macos")]
-            let kn = Arc::clone(&rt.keynote_connector);
-            drop(rt);
-
-            let handle = app.clone();
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) = crate::start_server(
-                    handle,
-                    auth_token_arc,
-                    port,
-                    obs,
-                    vmix,
-                    yt,
-                    fb,
-                    bl,
-                    yt_cfg,
-                    fb_cfg,
-                    oauth,
-                    #[cfg(target_os = "macos")]
-                    kn,
-                )
-                .await
-                {
-                    tracing::error!("Backend startup failed: {e}");
-                }
-            });
+            spawn_server(&rt, app.clone());
         }
     }
 
     Ok(())
 }
 
+/// Start the embedded database and Axum server in the background with the
+/// runtime's current settings.
+#[cfg(desktop)]
+fn spawn_server(rt: &AppRuntime, app: tauri::AppHandle) {
+    let auth_token_arc = rt.auth_token.clone();
+    let bind_address = rt.server_bind_address;
+    let port = rt.server_port;
+    let obs = Arc::clone(&rt.obs_connector);
+    let vmix = Arc::clone(&rt.vmix_connector);
+    let yt = Arc::clone(&rt.youtube_connector);
+    let fb = Arc::clone(&rt.facebook_connector);
+    let bl = Arc::clone(&rt.broadlink_connector);
+    // Use the shared config Arcs from AppRuntime so that any config
+    // saved via Tauri commands is immediately visible to Axum routes.
+    let yt_cfg = Arc::clone(&rt.youtube_config);
+    let fb_cfg = Arc::clone(&rt.facebook_config);
+    let oauth = Arc::clone(&rt.oauth_states);
+    let lan_only = Arc::clone(&rt.lan_only);
+    let api_keys = Arc::clone(&rt.api_keys);
+    #[cfg(target_os = "macos")]
+    let kn = Arc::clone(&rt.keynote_connector);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = crate::start_server(
+            app,
+            auth_token_arc,
+            api_keys,
+            bind_address,
+            port,
+            obs,
+            vmix,
+            yt,
+            fb,
+            bl,
+            yt_cfg,
+            fb_cfg,
+            oauth,
+            lan_only,
+            #[cfg(target_os = "macos")]
+            kn,
+        )
+        .await
+        {
+            tracing::error!("Backend startup failed: {e}");
+        }
+    });
+}
+
 #[tauri::command]
 pub async fn get_client_url(
     runtime: State<'_, Arc<RwLock<AppRuntime>>>,
@@ -144,12 +179,7 @@ pub async fn reset_setup(
     runtime: State<'_, Arc<RwLock<AppRuntime>>>,
     app: tauri::AppHandle,
 ) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app
-        .store("app-settings.json")
-        .map_err(|e| e.to_string())?;
-    store.delete("mode");
-    store.save().map_err(|e| e.to_string())?;
+    remove_setting(&app, "mode").await?;
 
     let mut rt = runtime.write().await;
     rt.mode = None;
@@ -164,12 +194,181 @@ pub fn get_local_ip() -> Option<String> {
     Some(socket.local_addr().ok()?.ip().to_string())
 }
 
-async fn save_setting(app: &tauri::AppHandle, key: &str, value: &str) -> Result<(), String> {
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn get_lan_only(runtime: State<'_, Arc<RwLock<AppRuntime>>>) -> Result<bool, String> {
+    let rt = runtime.read().await;
+    Ok(rt.lan_only.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Restrict the server to LAN peers. Applies immediately and is persisted
+/// so the next start uses the same setting.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn set_lan_only(
+    enabled: bool,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    save_setting(&app, "lan_only", enabled).await?;
+
+    let rt = runtime.read().await;
+    rt.lan_only.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+/// Start the server again after it stopped on its own, e.g. after the idle
+/// timeout. Fails while it is still running.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn start_stopped_server(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    if crate::server::is_running() {
+        return Err("Server is already running".to_string());
+    }
+    let rt = runtime.read().await;
+    if rt.mode.as_deref() != Some("server") {
+        return Err("The app is not in server mode".to_string());
+    }
+    spawn_server(&rt, app);
+    Ok(())
+}
+
+/// Move the running server to a new port and/or bind address without
+/// restarting the database. The new listener is bound before the old one
+/// stops, so a failed bind leaves the server untouched; settings are only
+/// persisted once the move succeeded. Connected WebSocket clients get a 1012
+/// close pointing at the new address, and `server://started` is re-emitted.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn restart_server(
+    port: Option<u16>,
+    bind_address: Option<String>,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let (current_ip, current_port) = {
+        let rt = runtime.read().await;
+        (rt.server_bind_address, rt.server_port)
+    };
+    let ip = match bind_address.as_deref().map(str::trim) {
+        Some(a) => a
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("Invalid bind address: {a}"))?,
+        None => current_ip,
+    };
+    let port = port.unwrap_or(current_port);
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+
+    crate::server::rebind(std::net::SocketAddr::new(ip, port)).await?;
+
+    save_setting(&app, "server_port", port).await?;
+    save_setting(&app, "server_bind_address", ip.to_string()).await?;
+
+    let mut rt = runtime.write().await;
+    rt.server_port = port;
+    rt.server_bind_address = ip;
+    Ok(())
+}
+
+/// Server URL on the interface sharing `client_ip`'s subnet, for showing a
+/// directly reachable address to a specific device.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn best_url_for_client(
+    client_ip: String,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<Option<String>, String> {
+    let ip = client_ip
+        .trim()
+        .parse::<std::net::IpAddr>()
+        .map_err(|_| format!("Invalid client IP: {client_ip}"))?;
+    let listen_addr = {
+        let rt = runtime.read().await;
+        std::net::SocketAddr::new(rt.server_bind_address, rt.server_port)
+    };
+    tokio::task::spawn_blocking(move || crate::server::lan::best_url_for_client(ip, listen_addr))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Try a throwaway bind of `port` on `bind_address` (default: the configured
+/// one) so the settings UI can warn before starting or moving the server.
+/// The port the server currently listens on reports as unavailable.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn check_port_available(
+    port: u16,
+    bind_address: Option<String>,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<bool, String> {
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    let ip = match bind_address.as_deref().map(str::trim) {
+        Some(a) => a
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("Invalid bind address: {a}"))?,
+        None => runtime.read().await.server_bind_address,
+    };
+    match tokio::net::TcpListener::bind(std::net::SocketAddr::new(ip, port)).await {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Ok(false),
+        Err(e) => Err(format!("Could not test {ip}:{port}: {e}")),
+    }
+}
+
+/// How the running server is configured — address, auth, LAN-only, CORS,
+/// idle timeout — in one call, for troubleshooting client connections.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn get_server_config() -> Result<crate::server::ServerConfig, String> {
+    crate::server::effective_config().await
+}
+
+#[tauri::command]
+pub async fn get_server_idle_timeout(app: tauri::AppHandle) -> Result<Option<u64>, String> {
+    use tauri_plugin_store::StoreExt;
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+    Ok(store.get("server_idle_timeout_secs").and_then(|v| v.as_u64()))
+}
+
+/// Stop the server after `secs` without requests or WebSocket clients;
+/// `None` or `0` disables the auto-stop. Takes effect on the next server start.
+#[tauri::command]
+pub async fn set_server_idle_timeout(secs: Option<u64>, app: tauri::AppHandle) -> Result<(), String> {
+    match secs.filter(|&s| s > 0) {
+        Some(s) => save_setting(&app, "server_idle_timeout_secs", s).await,
+        None => remove_setting(&app, "server_idle_timeout_secs").await,
+    }
+}
+
+async fn save_setting(
+    app: &tauri::AppHandle,
+    key: &str,
+    value: impl Into<serde_json::Value>,
+) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+    store.set(key, value.into());
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn remove_setting(app: &tauri::AppHandle, key: &str) -> Result<(), String> {
     use tauri_plugin_store::StoreExt;
     let store = app
         .store("app-settings.json")
         .map_err(|e| e.to_string())?;
-    store.set(key, serde_json::Value::String(value.to_string()));
+    store.delete(key);
     store.save().map_err(|e| e.to_string())?;
     Ok(())
 }
@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::Manager;
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_FILE: &str = "app-settings.json";
+const BACKUP_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "app-settings-";
+/// Backups kept after each `backup_settings`; older ones are deleted.
+const MAX_BACKUPS: usize = 10;
+/// Store key of the HTTP proxy settings (see `commands::http`).
+const PROXY_KEY: &str = "http_proxy";
+/// Store keys holding bearer secrets: the server token, the token this app
+/// uses as a client, and the named API keys (a key's `id` is its secret).
+const SECRET_KEYS: &[&str] = &[
+    "auth_token",
+    "client_auth_token",
+    crate::server::auth::API_KEYS_STORE_KEY,
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBackup {
+    pub name: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir().map_err(|e| e.to_string())?.join(SETTINGS_FILE))
+}
+
+fn backup_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir().map_err(|e| e.to_string())?.join(BACKUP_DIR))
+}
+
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX)
+        && name.ends_with(".json")
+        && !name.contains(['/', '\\'])
+}
+
+/// Backups newest first. Names embed the timestamp, so they sort by age.
+async fn read_backups(app: &tauri::AppHandle) -> Result<Vec<SettingsBackup>, String> {
+    let dir = backup_dir(app)?;
+    let mut entries = match tokio::fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut backups = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !is_backup_name(&name) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata().await else { continue };
+        backups.push(SettingsBackup {
+            name,
+            size_bytes: meta.len(),
+            created_at: meta.modified().map(DateTime::<Utc>::from).unwrap_or_else(|_| Utc::now()),
+        });
+    }
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+/// Copy the current settings, minus secrets, into `backups/` under a timestamped name
+/// and prune to the newest `MAX_BACKUPS`. Returns the new backup.
+#[tauri::command]
+pub async fn backup_settings(app: tauri::AppHandle) -> Result<SettingsBackup, String> {
+    // Flush pending in-memory changes so the copy matches what the app sees.
+    app.store(SETTINGS_FILE)
+        .map_err(|e| e.to_string())?
+        .save()
+        .map_err(|e| e.to_string())?;
+
+    let dir = backup_dir(&app)?;
+    tokio::fs::create_dir_all(&dir).await.map_err(|e| e.to_string())?;
+    let name = format!("{BACKUP_PREFIX}{}.json", Utc::now().format("%Y%m%d-%H%M%S%.3f"));
+    let contents = tokio::fs::read(settings_path(&app)?)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut settings: serde_json::Value =
+        serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+    // Backups are plain files the user may copy around; keep tokens, API
+    // keys and the proxy password out of them.
+    if let Some(obj) = settings.as_object_mut() {
+        for key in SECRET_KEYS {
+            obj.remove(*key);
+        }
+    }
+    if let Some(proxy) = settings.get_mut(PROXY_KEY).and_then(|v| v.as_object_mut()) {
+        proxy.remove("password");
+    }
+    let contents = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+    let size_bytes = contents.len() as u64;
+    tokio::fs::write(dir.join(&name), contents)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for old in read_backups(&app).await?.into_iter().skip(MAX_BACKUPS) {
+        if let Err(e) = tokio::fs::remove_file(dir.join(&old.name)).await {
+            tracing::warn!("Could not prune settings backup {}: {e}", old.name);
+        }
+    }
+
+    Ok(SettingsBackup { name, size_bytes, created_at: Utc::now() })
+}
+
+#[tauri::command]
+pub async fn list_settings_backups(app: tauri::AppHandle) -> Result<Vec<SettingsBackup>, String> {
+    read_backups(&app).await
+}
+
+/// Replace the live settings with backup `name` and reload the store.
+/// The backup must parse as a JSON object; otherwise nothing is touched.
+/// Settings already applied at startup (server port, mode, …) take effect
+/// on the next launch.
+#[tauri::command]
+pub async fn restore_settings_backup(name: String, app: tauri::AppHandle) -> Result<(), String> {
+    if !is_backup_name(&name) {
+        return Err(format!("invalid backup name '{name}'"));
+    }
+    let contents = tokio::fs::read(backup_dir(&app)?.join(&name))
+        .await
+        .map_err(|e| format!("Could not read backup '{name}': {e}"))?;
+    let mut settings = match serde_json::from_slice::<serde_json::Value>(&contents) {
+        Ok(v @ serde_json::Value::Object(_)) => v,
+        Ok(_) => return Err(format!("Backup '{name}' is not a settings object")),
+        Err(e) => return Err(format!("Backup '{name}' is not valid JSON: {e}")),
+    };
+
+    // Backups carry no secrets; keep the current ones.
+    let store = app.store(SETTINGS_FILE).map_err(|e| e.to_string())?;
+    if let Some(obj) = settings.as_object_mut() {
+        for key in SECRET_KEYS {
+            if let Some(current) = store.get(*key) {
+                obj.insert(key.to_string(), current);
+            }
+        }
+    }
+    let password = store
+        .get(PROXY_KEY)
+        .and_then(|v| v.get("password").cloned())
+        .filter(|p| p.is_string());
+    if let (Some(proxy), Some(password)) = (
+        settings.get_mut(PROXY_KEY).and_then(|v| v.as_object_mut()),
+        password,
+    ) {
+        proxy.entry("password").or_insert(password);
+    }
+
+    let contents = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+    tokio::fs::write(settings_path(&app)?, contents)
+        .await
+        .map_err(|e| e.to_string())?;
+    store.reload().map_err(|e| e.to_string())
+}
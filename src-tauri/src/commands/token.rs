@@ -40,3 +40,76 @@ pub async fn refresh_token(
 
     Ok(new_token)
 }
+
+#[cfg(desktop)]
+fn save_api_keys(
+    app: &tauri::AppHandle,
+    keys: &[crate::server::auth::ApiKey],
+) -> Result<(), String> {
+    let store = app
+        .store("app-settings.json")
+        .map_err(|e| e.to_string())?;
+    store.set(
+        crate::server::auth::API_KEYS_STORE_KEY,
+        serde_json::to_value(keys).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn list_api_keys(
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+) -> Result<Vec<crate::server::auth::ApiKey>, String> {
+    let api_keys = Arc::clone(&runtime.read().await.api_keys);
+    let keys = api_keys.read().await.clone();
+    Ok(keys)
+}
+
+/// Create a named API key. The returned `id` is the bearer token to hand to
+/// the integration; it keeps working when the primary token is refreshed.
+/// `scopes` defaults to every scope in `server::auth::ALL_SCOPES`.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn create_api_key(
+    name: String,
+    scopes: Option<Vec<String>>,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<crate::server::auth::ApiKey, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("API key name must not be empty".to_string());
+    }
+    let scopes = crate::server::auth::validate_scopes(scopes)?;
+    let key = crate::server::auth::ApiKey {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        created_at: chrono::Utc::now(),
+        scopes,
+    };
+
+    let api_keys = Arc::clone(&runtime.read().await.api_keys);
+    let mut keys = api_keys.write().await;
+    keys.push(key.clone());
+    save_api_keys(&app, &keys)?;
+    Ok(key)
+}
+
+/// Revoke one API key; other keys and the primary token are unaffected.
+#[cfg(desktop)]
+#[tauri::command]
+pub async fn revoke_api_key(
+    id: Uuid,
+    runtime: State<'_, Arc<RwLock<AppRuntime>>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let api_keys = Arc::clone(&runtime.read().await.api_keys);
+    let mut keys = api_keys.write().await;
+    let before = keys.len();
+    keys.retain(|key| key.id != id);
+    if keys.len() == before {
+        return Err("API key not found".to_string());
+    }
+    save_api_keys(&app, &keys)
+}
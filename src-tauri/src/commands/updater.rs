@@ -28,13 +28,9 @@ pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, Str
         return Ok(None);
     }
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| e.to_string())?;
-
-    let response = client
+    let response = crate::http::http_client()
         .get("https://api.github.com/repos/UMC-Hungary/sermon-helper-applications/releases/latest")
+        .timeout(std::time::Duration::from_secs(10))
         .header("User-Agent", "sermon-helper-tauri")
         .header("Accept", "application/vnd.github.v3+json")
         .send()
@@ -0,0 +1,159 @@
+//! Bitfocus Companion integration: adapting exported configs to this machine
+//! and driving a running Companion through its HTTP API.
+
+use reqwest::Url;
+use serde_json::{Map, Value};
+
+/// Module id of our Companion module (see `companion/companion/manifest.json`).
+const MODULE_ID: &str = "metocast";
+
+/// Hosts the shipped config points at; only these URLs are rewritten so
+/// actions aimed at other services are left alone.
+fn is_local_host(host: &str) -> bool {
+    matches!(host, "localhost" | "127.0.0.1" | "::1" | "[::1]" | "0.0.0.0")
+}
+
+/// Rewrite an exported `.companionconfig` so it reaches Sermon Helper at
+/// `target_url` with `token`:
+///
+/// - HTTP-request actions whose `options.url` points at localhost get the
+///   target's scheme, host and port (path and query are kept), and an
+///   `Authorization: Bearer` entry in their `options.headers`.
+/// - Connections of our own module get `host`, `port` and `authToken`.
+///
+/// Returns the patched config, pretty-printed for import.
+pub fn patch_config(config_json: &str, target_url: &str, token: &str) -> Result<String, String> {
+    let target = Url::parse(target_url).map_err(|e| format!("Invalid target URL '{target_url}': {e}"))?;
+    if !matches!(target.scheme(), "http" | "https") || target.host_str().is_none() {
+        return Err(format!("Target URL must be http(s) with a host, got '{target_url}'"));
+    }
+    let mut config: Value =
+        serde_json::from_str(config_json).map_err(|e| format!("Not a valid Companion config: {e}"))?;
+    if !config.is_object() {
+        return Err("Not a valid Companion config: expected a JSON object".to_string());
+    }
+
+    patch_value(&mut config, &target, token);
+    serde_json::to_string_pretty(&config).map_err(|e| e.to_string())
+}
+
+fn patch_value(value: &mut Value, target: &Url, token: &str) {
+    match value {
+        Value::Object(obj) => {
+            patch_module_connection(obj, target, token);
+            if let Some(Value::Object(options)) = obj.get_mut("options") {
+                patch_http_action(options, target, token);
+            }
+            for child in obj.values_mut() {
+                patch_value(child, target, token);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                patch_value(item, target, token);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Companion 3 exports connections with `instance_type`, 4.x with `moduleId`.
+fn patch_module_connection(obj: &mut Map<String, Value>, target: &Url, token: &str) {
+    let is_ours = ["instance_type", "moduleId"]
+        .iter()
+        .any(|key| obj.get(*key).and_then(Value::as_str) == Some(MODULE_ID));
+    if !is_ours {
+        return;
+    }
+    if let Some(Value::Object(config)) = obj.get_mut("config") {
+        if let Some(host) = target.host_str() {
+            config.insert("host".to_string(), Value::from(host));
+        }
+        if let Some(port) = target.port_or_known_default() {
+            config.insert("port".to_string(), Value::from(port));
+        }
+        config.insert("authToken".to_string(), Value::from(token));
+    }
+}
+
+fn patch_http_action(options: &mut Map<String, Value>, target: &Url, token: &str) {
+    let Some(Ok(mut url)) = options.get("url").and_then(Value::as_str).map(Url::parse) else {
+        return;
+    };
+    if !url.host_str().is_some_and(is_local_host) {
+        return;
+    }
+    // Both setters only fail for cannot-be-a-base URLs, which http(s) never are.
+    let _ = url.set_scheme(target.scheme());
+    let _ = url.set_host(target.host_str());
+    let _ = url.set_port(target.port());
+    options.insert("url".to_string(), Value::from(url.to_string()));
+
+    // The generic HTTP module stores headers as a JSON object in a string.
+    let mut headers = options
+        .get("headers")
+        .and_then(Value::as_str)
+        .and_then(|h| serde_json::from_str::<Map<String, Value>>(h).ok())
+        .unwrap_or_default();
+    headers.insert("Authorization".to_string(), Value::from(format!("Bearer {token}")));
+    options.insert("headers".to_string(), Value::from(Value::Object(headers).to_string()));
+}
+
+/// Default Companion web/API address.
+pub const DEFAULT_COMPANION_URL: &str = "http://127.0.0.1:8000";
+
+const BODY_PREVIEW_CHARS: usize = 200;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CompanionError {
+    #[error("Invalid Companion URL '{0}'")]
+    InvalidUrl(String),
+    #[error("Companion unreachable: {0}")]
+    Request(#[from] reqwest::Error),
+    /// Companion answered but refused, e.g. 404 for an empty or unknown button.
+    #[error("Companion returned {status}: {body}")]
+    Status { status: reqwest::StatusCode, body: String },
+}
+
+impl From<CompanionError> for String {
+    fn from(e: CompanionError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Client for Companion's HTTP remote-control API.
+pub struct CompanionApi {
+    base_url: Url,
+}
+
+impl CompanionApi {
+    pub fn new(base_url: &str) -> Result<Self, CompanionError> {
+        let base_url = Url::parse(base_url)
+            .ok()
+            .filter(|u| matches!(u.scheme(), "http" | "https"))
+            .ok_or_else(|| CompanionError::InvalidUrl(base_url.to_string()))?;
+        Ok(Self { base_url })
+    }
+
+    fn url(&self, path: &str) -> Result<Url, CompanionError> {
+        self.base_url
+            .join(path)
+            .map_err(|_| CompanionError::InvalidUrl(self.base_url.to_string()))
+    }
+
+    /// Press and release the button at `page`/`row`/`column`, running its
+    /// actions as if it were pressed on the Stream Deck.
+    pub async fn press_button(&self, page: u32, row: u32, column: u32) -> Result<(), CompanionError> {
+        let url = self.url(&format!("/api/location/{page}/{row}/{column}/press"))?;
+        let response = crate::http::http_client().post(url).send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(CompanionError::Status {
+            status,
+            body: body.chars().take(BODY_PREVIEW_CHARS).collect(),
+        })
+    }
+}
@@ -1,12 +1,23 @@
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
 
 use super::ConnectorStatus;
+use crate::server::websocket::{
+    broadcast_broadlink_dry_run, broadcast_broadlink_sequence_progress, WsClientSender,
+};
 
 #[derive(Debug, Clone)]
 pub struct BroadlinkLearnEvent {
     pub code: Option<String>,
     pub error: Option<String>,
+    pub quality: Option<crate::broadlink::CodeQuality>,
 }
 
 pub struct BroadlinkConnector {
@@ -41,3 +52,223 @@ impl Default for BroadlinkConnector {
         Self::new()
     }
 }
+
+// ── Sequences ────────────────────────────────────────────────────────────────
+
+/// app_settings key holding the saved sequences as a JSON array.
+const SEQUENCES_KEY: &str = "broadlink_sequences";
+
+/// Longest pause allowed between two steps.
+const MAX_STEP_DELAY_MS: u64 = 60_000;
+
+/// A named macro of stored commands sent in order, e.g. "Start Service".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadlinkSequence {
+    pub id: Uuid,
+    pub name: String,
+    pub steps: Vec<SequenceStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceStep {
+    pub command_id: Uuid,
+    /// Pause before sending this step, giving slow devices time to react.
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+// Sequences currently executing, so a double tap doesn't run one twice.
+static RUNNING_SEQUENCES: LazyLock<Mutex<HashSet<Uuid>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+pub async fn load_sequences(pool: &PgPool) -> Vec<BroadlinkSequence> {
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM app_settings WHERE key = $1")
+        .bind(SEQUENCES_KEY)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten();
+
+    match value.map(|v| serde_json::from_str(&v)) {
+        Some(Ok(sequences)) => sequences,
+        Some(Err(e)) => {
+            tracing::warn!("Ignoring malformed {SEQUENCES_KEY} setting: {e}");
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Replace the saved sequences. Every step must reference an existing command.
+pub async fn save_sequences(pool: &PgPool, sequences: &[BroadlinkSequence]) -> anyhow::Result<()> {
+    let mut ids = HashSet::new();
+    for seq in sequences {
+        if seq.name.trim().is_empty() {
+            anyhow::bail!("sequence {} has an empty name", seq.id);
+        }
+        if !ids.insert(seq.id) {
+            anyhow::bail!("duplicate sequence id {}", seq.id);
+        }
+        if let Some(step) = seq.steps.iter().find(|s| s.delay_ms > MAX_STEP_DELAY_MS) {
+            anyhow::bail!(
+                "step delay {}ms in '{}' exceeds {MAX_STEP_DELAY_MS}ms",
+                step.delay_ms,
+                seq.name
+            );
+        }
+    }
+
+    let command_ids: Vec<Uuid> = sequences
+        .iter()
+        .flat_map(|s| s.steps.iter().map(|step| step.command_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let known: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM broadlink_commands WHERE id = ANY($1)")
+        .bind(&command_ids)
+        .fetch_all(pool)
+        .await?;
+    if let Some(missing) = command_ids.iter().find(|id| !known.contains(id)) {
+        anyhow::bail!("unknown command {missing}");
+    }
+
+    sqlx::query(
+        "INSERT INTO app_settings (key, value) VALUES ($1, $2) \
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = now()",
+    )
+    .bind(SEQUENCES_KEY)
+    .bind(serde_json::to_string(sequences)?)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Start sending a saved sequence's steps in the background, broadcasting
+/// `broadlink.sequence.progress` after each one. Stops at the first failed
+/// step. Returns the number of steps queued.
+pub async fn execute_sequence(
+    pool: &PgPool,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+    id: Uuid,
+) -> Result<usize, String> {
+    let sequence = load_sequences(pool)
+        .await
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| "not_found".to_string())?;
+
+    if !RUNNING_SEQUENCES.lock().unwrap_or_else(|e| e.into_inner()).insert(id) {
+        return Err("already_running".to_string());
+    }
+
+    let total = sequence.steps.len();
+    let pool = pool.clone();
+    let clients = clients.clone();
+    tokio::spawn(async move {
+        for (index, step) in sequence.steps.iter().enumerate() {
+            if step.delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(step.delay_ms)).await;
+            }
+            let result = send_stored_command(&pool, step.command_id).await;
+            if let Err(e) = &result {
+                tracing::warn!(
+                    "Sequence '{}' step {} of {total} failed: {e}",
+                    sequence.name,
+                    index + 1
+                );
+            }
+            broadcast_broadlink_sequence_progress(
+                &clients,
+                id,
+                index + 1,
+                total,
+                step.command_id,
+                result.as_ref().err().map(String::as_str),
+            )
+            .await;
+            if result.is_err() {
+                break;
+            }
+        }
+        RUNNING_SEQUENCES.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+    });
+
+    Ok(total)
+}
+
+/// What a send would have done, reported by a dry run instead of firing the
+/// blaster.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+    pub command_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub code_type: String,
+    /// Length of the stored code in bytes.
+    pub code_bytes: usize,
+    pub device_id: Uuid,
+    pub device_name: String,
+    pub host: String,
+    pub reachable: bool,
+}
+
+/// Resolve a stored command and check its device answers, without sending
+/// the code. Broadcasts `broadlink.dry_run` with the result. `Ok(None)` when
+/// the command doesn't exist.
+pub async fn dry_run_command(
+    pool: &PgPool,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+    command_id: Uuid,
+) -> Result<Option<DryRunResult>, String> {
+    let row = sqlx::query_as::<_, (String, String, String, String, Uuid, String, String, String, String, Option<i32>)>(
+        "SELECT bc.name, bc.slug, bc.code_type, bc.code, bd.id, bd.name, bd.host, bd.mac, bd.device_type, bd.port \
+         FROM broadlink_commands bc \
+         JOIN broadlink_devices bd ON bc.device_id = bd.id \
+         WHERE bc.id = $1",
+    )
+    .bind(command_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((name, slug, code_type, code, device_id, device_name, host, mac, devtype, port)) = row else {
+        return Ok(None);
+    };
+
+    let reachable =
+        crate::broadlink::test_device(&host, &mac, &devtype, port.map(|p| p as u16)).await?;
+    let result = DryRunResult {
+        command_id,
+        name,
+        slug,
+        code_type,
+        code_bytes: code.len() / 2,
+        device_id,
+        device_name,
+        host,
+        reachable,
+    };
+    broadcast_broadlink_dry_run(clients, &result).await;
+    Ok(Some(result))
+}
+
+async fn send_stored_command(pool: &PgPool, command_id: Uuid) -> Result<(), String> {
+    let (code, host, mac, devtype, port) = sqlx::query_as::<_, (String, String, String, String, Option<i32>)>(
+        "SELECT bc.code, bd.host, bd.mac, bd.device_type, bd.port \
+         FROM broadlink_commands bc \
+         JOIN broadlink_devices bd ON bc.device_id = bd.id \
+         WHERE bc.id = $1",
+    )
+    .bind(command_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("command {command_id} not found"))?;
+
+    match crate::broadlink::send_code(&host, &mac, &devtype, &code, port.map(|p| p as u16)).await? {
+        r if r.success => Ok(()),
+        r => Err(r.error.unwrap_or_else(|| "send failed".to_string())),
+    }
+}
@@ -175,7 +175,7 @@ pub async fn exchange_long_lived_token(
     config: &FacebookConfig,
     short_lived_token: &str,
 ) -> anyhow::Result<StoredToken> {
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let resp = client
         .get("https://graph.facebook.com/oauth/access_token")
         .query(&[
@@ -209,7 +209,7 @@ pub async fn exchange_code(
     code: &str,
     redirect_uri: &str,
 ) -> anyhow::Result<StoredToken> {
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
 
     // Step 1: exchange code for short-lived token
     let resp = client
@@ -296,7 +296,7 @@ pub async fn schedule_event(
     page_id: &str,
     privacy_status: &str,
 ) -> anyhow::Result<FacebookScheduleResult> {
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let start_time = event_time.timestamp();
 
     // Create the Facebook Page Event
@@ -1,8 +1,35 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Tauri event emitted when Keynote quits or crashes while a document is open;
+/// payload `{ app, documentName }`.
+pub const PRESENTATION_APP_LOST_EVENT: &str = "presentation-app-lost";
+
+pub const DEFAULT_WATCHDOG_INTERVAL_SECS: u64 = 3;
+
+const APPLESCRIPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Named slideshow shortcuts accepted by [`KeynoteConnector::send_key`], as
+/// System Events key actions. Only these can be sent, so callers can't type
+/// arbitrary input into whatever app is frontmost. `None` marks presenter
+/// tools Keynote for Mac has no shortcut for; they are refused by name.
+const SLIDESHOW_KEYS: &[(&str, Option<&str>)] = &[
+    // Show or hide the arrow pointer.
+    ("arrow", Some(r#"keystroke "c""#)),
+    ("laser", None),
+    ("pen", None),
+    ("erase", None),
+    ("white", Some(r#"keystroke "w""#)),
+    ("slide_number", Some(r#"keystroke "s""#)),
+    ("swap_displays", Some(r#"keystroke "x""#)),
+    ("shortcuts", Some(r#"keystroke "?""#)),
+    ("escape", Some("key code 53")),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct KeynoteStatus {
     pub app_running: bool,
@@ -10,6 +37,10 @@ pub struct KeynoteStatus {
     pub current_slide: Option<u32>,
     pub total_slides: Option<u32>,
     pub document_name: Option<String>,
+    /// Best effort: Keynote doesn't expose its black screen to AppleScript or
+    /// accessibility, so this mirrors our own blank calls and resets whenever
+    /// the slideshow starts or stops. A `B` pressed on the Mac itself is missed.
+    pub blanked: bool,
 }
 
 impl Default for KeynoteStatus {
@@ -20,6 +51,7 @@ impl Default for KeynoteStatus {
             current_slide: None,
             total_slides: None,
             document_name: None,
+            blanked: false,
         }
     }
 }
@@ -27,6 +59,11 @@ impl Default for KeynoteStatus {
 pub struct KeynoteConnector {
     status: Arc<RwLock<KeynoteStatus>>,
     pub status_tx: broadcast::Sender<KeynoteStatus>,
+    app_handle: std::sync::Mutex<Option<tauri::AppHandle>>,
+    /// Seconds between watchdog checks; 0 disables the watchdog.
+    watchdog_interval_secs: AtomicU64,
+    watchdog: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    warmed_up: AtomicBool,
 }
 
 impl KeynoteConnector {
@@ -35,6 +72,97 @@ impl KeynoteConnector {
         Self {
             status: Arc::new(RwLock::new(KeynoteStatus::default())),
             status_tx,
+            app_handle: std::sync::Mutex::new(None),
+            watchdog_interval_secs: AtomicU64::new(DEFAULT_WATCHDOG_INTERVAL_SECS),
+            watchdog: std::sync::Mutex::new(None),
+            warmed_up: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_app_handle(&self, app: tauri::AppHandle) {
+        *self.app_handle.lock().unwrap_or_else(|e| e.into_inner()) = Some(app);
+    }
+
+    pub fn watchdog_interval_secs(&self) -> u64 {
+        self.watchdog_interval_secs.load(Ordering::Relaxed)
+    }
+
+    /// Change the watchdog poll interval; applies from the next opened file.
+    pub fn set_watchdog_interval_secs(&self, secs: u64) {
+        self.watchdog_interval_secs.store(secs, Ordering::Relaxed);
+    }
+
+    /// Whether the Keynote process is up. Unlike `tell application`, this
+    /// check never launches Keynote. Errs when the script itself fails (a
+    /// timeout or a busy Apple Events queue), which says nothing either way.
+    pub async fn is_app_running() -> Result<bool, String> {
+        Self::run_applescript(r#"application "Keynote" is running"#)
+            .await
+            .map(|out| out == "true")
+    }
+
+    /// Pay the first-call cost of `osascript` and Apple Events up front so the
+    /// first real command isn't slow. Never launches Keynote; runs once per
+    /// session and is retried only if the probe failed.
+    pub async fn warm_up(&self) {
+        if self.warmed_up.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        match Self::run_applescript(r#"application "Keynote" is running"#).await {
+            Ok(out) if out == "true" => {
+                let status = self.poll_status().await;
+                self.update_status(status).await;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("Keynote warm-up failed: {e}");
+                self.warmed_up.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Watch for Keynote disappearing while a document is open. Runs from
+    /// `open_file` until `close_all`, or until the loss has been reported.
+    fn arm_watchdog(&self) {
+        self.disarm_watchdog();
+        let secs = self.watchdog_interval_secs();
+        let Some(app) = self.app_handle.lock().unwrap_or_else(|e| e.into_inner()).clone() else {
+            return;
+        };
+        if secs == 0 {
+            return;
+        }
+        let status = Arc::clone(&self.status);
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match Self::is_app_running().await {
+                    Ok(true) => continue,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::debug!("Keynote watchdog check failed: {e}");
+                        continue;
+                    }
+                }
+                let document_name = status.read().await.document_name.clone();
+                tracing::warn!("Keynote is no longer running (document: {document_name:?})");
+                use tauri::Emitter;
+                let _ = app.emit(
+                    PRESENTATION_APP_LOST_EVENT,
+                    serde_json::json!({ "app": "keynote", "documentName": document_name }),
+                );
+                return;
+            }
+        });
+        *self.watchdog.lock().unwrap_or_else(|e| e.into_inner()) = Some(handle);
+    }
+
+    fn disarm_watchdog(&self) {
+        if let Some(handle) = self.watchdog.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            handle.abort();
         }
     }
 
@@ -53,12 +181,21 @@ impl KeynoteConnector {
         }
     }
 
+    /// Run `script` through `osascript`. A modal dialog in Keynote makes
+    /// scripts block forever, so the process is killed after
+    /// `APPLESCRIPT_TIMEOUT` instead of hanging the caller.
     async fn run_applescript(script: &str) -> Result<String, String> {
-        let output = tokio::process::Command::new("osascript")
+        let child = tokio::process::Command::new("osascript")
             .arg("-e")
             .arg(script)
-            .output()
+            .kill_on_drop(true)
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let output = tokio::time::timeout(APPLESCRIPT_TIMEOUT, child.wait_with_output())
             .await
+            .map_err(|_| "AppleScript timed out".to_string())?
             .map_err(|e| e.to_string())?;
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -79,9 +216,33 @@ end tell"#
         Self::run_applescript(&script).await?;
         let status = self.poll_status().await;
         self.update_status(status).await;
+        self.arm_watchdog();
         Ok(())
     }
 
+    /// Open `path`, start the slideshow and move to `start_slide`, clamped to
+    /// the last slide of the deck. Returns the slide actually shown.
+    pub async fn open_file_at(&self, path: &str, start_slide: u32) -> Result<u32, String> {
+        if start_slide == 0 {
+            return Err("Start slide must be at least 1".to_string());
+        }
+        self.open_file(path).await?;
+        let total = self
+            .get_status()
+            .await
+            .total_slides
+            .filter(|&t| t > 0)
+            .ok_or_else(|| "Opened presentation reports no slides".to_string())?;
+        let slide = start_slide.min(total);
+        if slide != start_slide {
+            tracing::warn!("Start slide {start_slide} is past the end of the deck; showing {slide} of {total}");
+        }
+        if slide > 1 {
+            self.goto(slide).await?;
+        }
+        Ok(slide)
+    }
+
     pub async fn next(&self) -> Result<(), String> {
         Self::run_applescript(r#"tell application "Keynote" to show next"#).await?;
         Ok(())
@@ -116,6 +277,21 @@ end tell"#
         Ok(())
     }
 
+    /// Move `offset` slides forward (or backward when negative), clamped to
+    /// the deck boundaries. Reads the live position first so the jump is
+    /// relative to where Keynote actually is, not the last polled status.
+    pub async fn jump(&self, offset: i32) -> Result<(), String> {
+        let status = self.poll_status().await;
+        let (Some(current), Some(total)) = (status.current_slide, status.total_slides) else {
+            return Err("No Keynote document is open".to_string());
+        };
+        if total == 0 {
+            return Err("No Keynote document is open".to_string());
+        }
+        let target = (i64::from(current) + i64::from(offset)).clamp(1, i64::from(total)) as u32;
+        self.goto(target).await
+    }
+
     pub async fn start_slideshow(&self) -> Result<(), String> {
         Self::run_applescript(
             r#"tell application "Keynote" to start slideshow of document 1"#,
@@ -136,11 +312,64 @@ end tell"#
         Ok(())
     }
 
+    /// Toggle Keynote's black screen by sending the `B` key to the running
+    /// slideshow. Keynote doesn't expose the blank state to AppleScript, so
+    /// callers can't know which way this flipped it.
+    pub async fn toggle_blank(&self) -> Result<(), String> {
+        Self::run_applescript(
+            r#"tell application "Keynote" to activate
+tell application "System Events" to keystroke "b""#,
+        )
+        .await?;
+        let mut status = self.get_status().await;
+        status.blanked = !status.blanked && status.slideshow_active;
+        let mut current = self.status.write().await;
+        if *current != status {
+            *current = status.clone();
+            let _ = self.status_tx.send(status);
+        }
+        Ok(())
+    }
+
+    /// Send one of the allowlisted [`SLIDESHOW_KEYS`] to the running slideshow.
+    /// Refused outside a slideshow, where the key would be typed into the
+    /// document instead.
+    pub async fn send_key(&self, name: &str) -> Result<(), String> {
+        let action = SLIDESHOW_KEYS
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, action)| *action)
+            .ok_or_else(|| {
+                let names: Vec<&str> = SLIDESHOW_KEYS.iter().map(|(k, _)| *k).collect();
+                format!("Unsupported key '{name}' (expected one of {})", names.join(", "))
+            })?
+            .ok_or_else(|| format!("Keynote has no '{name}' shortcut"))?;
+        if !self.poll_status().await.slideshow_active {
+            return Err("No Keynote slideshow is running".to_string());
+        }
+        Self::run_applescript(&format!(
+            r#"tell application "Keynote" to activate
+tell application "System Events" to {action}"#
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Blank or unblank the slideshow, based on the shadow state in
+    /// [`KeynoteStatus::blanked`]; does nothing if it already matches.
+    pub async fn set_blank(&self, blank: bool) -> Result<(), String> {
+        if self.get_status().await.blanked == blank {
+            return Ok(());
+        }
+        self.toggle_blank().await
+    }
+
     pub async fn close_all(&self) -> Result<(), String> {
         Self::run_applescript(
             r#"tell application "Keynote" to close every document saving no"#,
         )
         .await?;
+        self.disarm_watchdog();
         let status = self.poll_status().await;
         self.update_status(status).await;
         Ok(())
@@ -155,7 +384,12 @@ end tell"#
         // `current slide` is a document property.
         // `slide number` is read inside a `tell curSlide` block to avoid the
         // ambiguity between the `slide` class name and the `slide number` property.
-        let script = r#"tell application "Keynote"
+        // Bail out first when Keynote isn't running: `tell application` would
+        // relaunch it, hiding a crash from the watchdog.
+        let script = r#"if application "Keynote" is not running then
+  return "false|false|0|0|"
+end if
+tell application "Keynote"
   if (count of documents) is 0 then
     return "false|false|0|0|"
   end if
@@ -194,6 +428,7 @@ end tell"#;
                         current_slide,
                         total_slides,
                         document_name,
+                        blanked: false,
                     }
                 } else {
                     KeynoteStatus::default()
@@ -203,8 +438,12 @@ end tell"#;
         }
     }
 
-    async fn update_status(&self, new_status: KeynoteStatus) {
+    async fn update_status(&self, mut new_status: KeynoteStatus) {
         let mut current = self.status.write().await;
+        // Polling can't see the blank state; keep our shadow of it for as
+        // long as the same slideshow keeps running.
+        new_status.blanked =
+            current.blanked && current.slideshow_active && new_status.slideshow_active;
         if *current != new_status {
             *current = new_status.clone();
             let _ = self.status_tx.send(new_status);
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub mod atem;
@@ -10,7 +11,7 @@ pub mod obs;
 pub mod vmix;
 pub mod youtube;
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum ConnectorStatus {
     Disconnected,
@@ -177,6 +178,12 @@ impl ConnectorConfig for DiscordConfig {
 #[serde(rename_all = "camelCase")]
 pub struct BroadlinkConfig {
     pub enabled: bool,
+    /// Interface name patterns to use even if normally skipped as virtual.
+    #[serde(default)]
+    pub interface_include: Vec<String>,
+    /// Interface name patterns never used for discovery or sending.
+    #[serde(default)]
+    pub interface_exclude: Vec<String>,
 }
 
 impl ConnectorConfig for BroadlinkConfig {
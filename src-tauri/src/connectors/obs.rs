@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use futures_util::StreamExt;
 use obws::events::Event;
+use serde::Serialize;
 use tauri::Emitter;
 use tokio::sync::{broadcast, watch, Mutex, RwLock};
 use tokio::time::Duration;
@@ -28,6 +29,26 @@ pub struct ObsOutputState {
     pub is_recording: bool,
 }
 
+/// Live streaming/recording status including OBS timecodes (`HH:MM:SS.mmm`).
+#[derive(Debug, Clone, Serialize, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsOutputStatus {
+    pub is_streaming: bool,
+    pub is_recording: bool,
+    pub is_recording_paused: bool,
+    pub stream_timecode: String,
+    pub record_timecode: String,
+}
+
+/// Scenes defined in OBS, top to bottom as in the OBS scene list, plus the
+/// live program scene.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsSceneList {
+    pub current_scene: Option<String>,
+    pub scenes: Vec<String>,
+}
+
 pub struct ObsConnector {
     pub status: Arc<RwLock<ConnectorStatus>>,
     /// Last known streaming/recording state; `None` when OBS is disconnected.
@@ -44,6 +65,8 @@ pub struct ObsConnector {
     pub output_state_tx: broadcast::Sender<ObsOutputState>,
     /// Broadcast channel — fires when a device rescan should be triggered.
     pub devices_tx: broadcast::Sender<()>,
+    /// Broadcast channel — name of the new program scene whenever it changes.
+    pub scene_tx: broadcast::Sender<String>,
     stop_tx: Mutex<Option<watch::Sender<bool>>>,
 }
 
@@ -54,6 +77,7 @@ impl ObsConnector {
         let (state_tx, _) = broadcast::channel(16);
         let (output_state_tx, _) = broadcast::channel(16);
         let (devices_tx, _) = broadcast::channel(8);
+        let (scene_tx, _) = broadcast::channel(16);
         Self {
             status: Arc::new(RwLock::new(ConnectorStatus::Disconnected)),
             output_state: Arc::new(RwLock::new(None)),
@@ -63,6 +87,7 @@ impl ObsConnector {
             state_tx,
             output_state_tx,
             devices_tx,
+            scene_tx,
             stop_tx: Mutex::new(None),
         }
     }
@@ -81,8 +106,9 @@ impl ObsConnector {
         let state_tx = self.state_tx.clone();
         let output_state_tx = self.output_state_tx.clone();
         let devices_tx = self.devices_tx.clone();
+        let scene_tx = self.scene_tx.clone();
         tauri::async_runtime::spawn(async move {
-            run_obs_loop(config, app, status, output_state, client_arc, status_tx, recording_tx, state_tx, output_state_tx, devices_tx, stop_rx).await;
+            run_obs_loop(config, app, status, output_state, client_arc, status_tx, recording_tx, state_tx, output_state_tx, devices_tx, scene_tx, stop_rx).await;
         });
     }
 
@@ -104,6 +130,178 @@ impl ObsConnector {
     pub async fn get_status(&self) -> ConnectorStatus {
         self.status.read().await.clone()
     }
+
+    async fn connected_client(&self) -> Result<Arc<obws::Client>, ObsError> {
+        self.client.lock().await.clone().ok_or(ObsError::NotConnected)
+    }
+
+    pub async fn start_streaming(&self) -> Result<(), ObsError> {
+        let client = self.connected_client().await?;
+        client.streaming().start().await.map_err(ObsError::from)
+    }
+
+    pub async fn stop_streaming(&self) -> Result<(), ObsError> {
+        let client = self.connected_client().await?;
+        client.streaming().stop().await.map_err(ObsError::from)
+    }
+
+    pub async fn start_recording(&self) -> Result<(), ObsError> {
+        let client = self.connected_client().await?;
+        client.recording().start().await.map_err(ObsError::from)
+    }
+
+    /// Stop recording and return the path of the file OBS wrote.
+    pub async fn stop_recording(&self) -> Result<String, ObsError> {
+        let client = self.connected_client().await?;
+        client.recording().stop().await.map_err(ObsError::from)
+    }
+
+    /// List all scenes and the current program scene (`GetSceneList`).
+    pub async fn list_scenes(&self) -> Result<ObsSceneList, ObsError> {
+        let client = self.connected_client().await?;
+        let list = client.scenes().list().await?;
+        Ok(ObsSceneList {
+            current_scene: list.current_program_scene.map(|id| id.name),
+            // obs-websocket reports the bottom scene first.
+            scenes: list.scenes.into_iter().rev().map(|scene| scene.id.name).collect(),
+        })
+    }
+
+    /// Switch the program output to `scene_name` (`SetCurrentProgramScene`).
+    pub async fn set_scene(&self, scene_name: &str) -> Result<(), ObsError> {
+        let client = self.connected_client().await?;
+        client
+            .scenes()
+            .set_current_program_scene(scene_name)
+            .await
+            .map_err(ObsError::from)
+    }
+
+    /// Show or hide `source` within `scene` (`GetSceneItemId` + `SetSceneItemEnabled`).
+    pub async fn set_source_enabled(&self, scene: &str, source: &str, enabled: bool) -> Result<(), ObsError> {
+        use obws::requests::scene_items::{Id, SetEnabled};
+
+        let client = self.connected_client().await?;
+        let item_id = client
+            .scene_items()
+            .id(Id { scene: scene.into(), source, search_offset: None })
+            .await
+            .map_err(|e| not_found_or(e, || format!("Source '{source}' in scene '{scene}' not found")))?;
+        client
+            .scene_items()
+            .set_enabled(SetEnabled { scene: scene.into(), item_id, enabled })
+            .await
+            .map_err(ObsError::from)
+    }
+
+    /// Replace the text shown by the GDI+/FreeType text input `source_name`
+    /// (`SetInputSettings`). Control characters other than newlines and tabs
+    /// are stripped; inputs that aren't text sources are rejected.
+    pub async fn set_text(&self, source_name: &str, text: &str) -> Result<(), ObsError> {
+        use obws::requests::inputs::SetSettings;
+
+        let text = clean_text_input(source_name, text).map_err(ObsError::Invalid)?;
+        let client = self.connected_client().await?;
+        let current = client
+            .inputs()
+            .settings::<serde_json::Value>(source_name.into())
+            .await
+            .map_err(|e| not_found_or(e, || format!("Text source '{source_name}' not found")))?;
+        if !current.kind.starts_with("text_") {
+            return Err(ObsError::Invalid(format!(
+                "Source '{source_name}' is not a text source ({})",
+                current.kind
+            )));
+        }
+        client
+            .inputs()
+            .set_settings(SetSettings {
+                input: source_name.into(),
+                settings: &serde_json::json!({ "text": text }),
+                overlay: Some(true),
+            })
+            .await
+            .map_err(ObsError::from)
+    }
+
+    /// Ask OBS where it writes recordings (`GetRecordDirectory`).
+    pub async fn record_directory(&self) -> Result<String, ObsError> {
+        let client = self.connected_client().await?;
+        client.config().record_directory().await.map_err(ObsError::from)
+    }
+
+    /// Query OBS for the current output status, including timecodes.
+    pub async fn output_status(&self) -> Result<ObsOutputStatus, ObsError> {
+        let client = self.connected_client().await?;
+        let stream = client.streaming().status().await?;
+        let record = client.recording().status().await?;
+        Ok(ObsOutputStatus {
+            is_streaming: stream.active,
+            is_recording: record.active,
+            is_recording_paused: record.paused,
+            stream_timecode: format_timecode(stream.timecode.whole_milliseconds()),
+            record_timecode: format_timecode(record.timecode.whole_milliseconds()),
+        })
+    }
+}
+
+/// Longest text accepted by `ObsConnector::set_text`, in characters.
+const MAX_TEXT_LEN: usize = 4096;
+
+/// Validate a `set_text` request without talking to OBS, returning the text
+/// with control characters other than newlines and tabs stripped.
+pub fn clean_text_input(source_name: &str, text: &str) -> Result<String, String> {
+    if source_name.trim().is_empty() {
+        return Err("Source name is required".to_string());
+    }
+    if text.chars().count() > MAX_TEXT_LEN {
+        return Err(format!("Text is longer than {MAX_TEXT_LEN} characters"));
+    }
+    Ok(text
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect())
+}
+
+/// Failure of an OBS control request, kept typed so HTTP callers can pick a
+/// status code without parsing messages.
+#[derive(Debug, thiserror::Error)]
+pub enum ObsError {
+    #[error("OBS is not connected")]
+    NotConnected,
+    /// A referenced scene, source or input doesn't exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// The request was rejected before or by OBS as malformed.
+    #[error("{0}")]
+    Invalid(String),
+    #[error(transparent)]
+    Obs(#[from] obws::error::Error),
+}
+
+impl From<ObsError> for String {
+    fn from(e: ObsError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Describe an obs-websocket error, using `missing` when OBS reports that a
+/// referenced scene, source or input doesn't exist.
+fn not_found_or(e: obws::error::Error, missing: impl FnOnce() -> String) -> ObsError {
+    match e {
+        obws::error::Error::Api { code: obws::responses::StatusCode::ResourceNotFound, .. } => {
+            ObsError::NotFound(missing())
+        }
+        e => ObsError::Obs(e),
+    }
+}
+
+/// Format milliseconds the way OBS displays timecodes: `HH:MM:SS.mmm`.
+fn format_timecode(total_ms: i128) -> String {
+    let total_ms = total_ms.max(0);
+    let ms = total_ms % 1000;
+    let secs = total_ms / 1000;
+    format!("{:02}:{:02}:{:02}.{:03}", secs / 3600, (secs / 60) % 60, secs % 60, ms)
 }
 
 impl Default for ObsConnector {
@@ -137,6 +335,7 @@ async fn run_obs_loop(
     state_tx: broadcast::Sender<ObsStateEvent>,
     output_state_tx: broadcast::Sender<ObsOutputState>,
     devices_tx: broadcast::Sender<()>,
+    scene_tx: broadcast::Sender<String>,
     mut stop_rx: watch::Receiver<bool>,
 ) {
     loop {
@@ -220,6 +419,9 @@ async fn run_obs_loop(
                                                 | obws::events::Event::InputSettingsChanged { .. } => {
                                                     let _ = devices_tx.send(());
                                                 }
+                                                obws::events::Event::CurrentProgramSceneChanged { id } => {
+                                                    let _ = scene_tx.send(id.name.clone());
+                                                }
                                                 _ => {}
                                             }
                                             handle_event(&event, &current_output, &output_state_tx).await;
@@ -287,6 +489,87 @@ async fn run_obs_loop(
     }
 }
 
+/// How long `check_connection` waits for each step before giving up.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a one-off obs-websocket connection attempt, classified so the
+/// UI can give specific setup guidance.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ObsConnectionCheck {
+    /// Nothing accepted a TCP connection on host:port (OBS closed, wrong port, firewall).
+    NotReachable { message: String },
+    /// obs-websocket answered but rejected or required a password.
+    AuthFailed { message: String },
+    /// obs-websocket answered but its version or RPC version isn't supported.
+    VersionUnsupported { message: String },
+    /// Reachable but the handshake failed for another reason.
+    Failed { message: String },
+    Connected {
+        #[serde(rename = "obsVersion")]
+        obs_version: String,
+        #[serde(rename = "websocketVersion")]
+        websocket_version: String,
+        #[serde(rename = "rpcVersion")]
+        rpc_version: u32,
+    },
+}
+
+/// obs-websocket close codes (`obws::responses::WebSocketCloseCode`) for a
+/// failed `Identify` and an unsupported RPC version.
+const CLOSE_AUTHENTICATION_FAILED: u16 = 4009;
+const CLOSE_UNSUPPORTED_RPC_VERSION: u16 = 4010;
+
+/// Map an `obws::Client::connect` failure to the check result. A rejected
+/// password surfaces as the handshake's close code, not in the message.
+fn classify_connect_error(e: obws::error::Error) -> ObsConnectionCheck {
+    use obws::client::HandshakeError;
+    use obws::error::Error;
+
+    match &e {
+        Error::Handshake(HandshakeError::ConnectionClosed(Some(details)))
+            if u16::from(details.code) == CLOSE_AUTHENTICATION_FAILED =>
+        {
+            ObsConnectionCheck::AuthFailed { message: details.reason.clone() }
+        }
+        Error::Handshake(HandshakeError::ConnectionClosed(Some(details)))
+            if u16::from(details.code) == CLOSE_UNSUPPORTED_RPC_VERSION =>
+        {
+            ObsConnectionCheck::VersionUnsupported { message: details.reason.clone() }
+        }
+        Error::ObsWebsocketVersion(..) | Error::RpcVersion { .. } | Error::ObsStudioVersion(..) => {
+            ObsConnectionCheck::VersionUnsupported { message: e.to_string() }
+        }
+        Error::Handshake(inner) => ObsConnectionCheck::Failed { message: inner.to_string() },
+        _ => ObsConnectionCheck::Failed { message: e.to_string() },
+    }
+}
+
+/// Try a throwaway connection to obs-websocket at `host:port` and classify
+/// the result. Independent of the running connector.
+pub async fn check_connection(host: &str, port: u16, password: Option<&str>) -> ObsConnectionCheck {
+    match tokio::time::timeout(CHECK_TIMEOUT, tokio::net::TcpStream::connect((host, port))).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => return ObsConnectionCheck::NotReachable { message: e.to_string() },
+        Err(_) => return ObsConnectionCheck::NotReachable { message: "connection timed out".to_string() },
+    }
+
+    let client = match tokio::time::timeout(CHECK_TIMEOUT, obws::Client::connect(host, port, password)).await {
+        Ok(Ok(client)) => client,
+        Ok(Err(e)) => return classify_connect_error(e),
+        Err(_) => return ObsConnectionCheck::Failed { message: "handshake timed out".to_string() },
+    };
+
+    match client.general().version().await {
+        Ok(v) => ObsConnectionCheck::Connected {
+            obs_version: v.obs_version.to_string(),
+            websocket_version: v.obs_web_socket_version.to_string(),
+            rpc_version: v.rpc_version,
+        },
+        Err(e) => ObsConnectionCheck::Failed { message: e.to_string() },
+    }
+}
+
 async fn query_output_state(client: &obws::Client) -> ObsOutputState {
     let is_streaming = client
         .streaming()
@@ -186,7 +186,7 @@ pub async fn refresh_tokens(
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("No refresh token available"))?;
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let raw = client
         .post("https://oauth2.googleapis.com/token")
         .form(&[
@@ -226,7 +226,7 @@ pub async fn exchange_code(
     code: &str,
     redirect_uri: &str,
 ) -> anyhow::Result<StoredToken> {
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let resp = client
         .post("https://oauth2.googleapis.com/token")
         .form(&[
@@ -366,7 +366,7 @@ pub async fn schedule_event(
     existing_broadcast_id: Option<&str>,
     privacy_status: &str,
 ) -> anyhow::Result<BroadcastResult> {
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
 
     let scheduled_start = event_time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
 
@@ -469,6 +469,18 @@ pub struct ChannelContent {
     pub videos: Vec<ChannelVideoItem>,
 }
 
+/// One entry of [`list_recent_uploads`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentUpload {
+    pub video_id: String,
+    pub title: String,
+    pub published_at: Option<String>,
+    pub privacy_status: String, // "public" | "private" | "unlisted"
+    pub thumbnail_url: Option<String>,
+    pub watch_url: String,
+}
+
 // Private serde types for YouTube Data API v3
 
 #[derive(Deserialize)]
@@ -505,6 +517,28 @@ struct PlaylistItemContentDetails {
     video_id: String,
 }
 
+#[derive(Deserialize)]
+struct UploadPlaylistItem {
+    snippet: UploadPlaylistSnippet,
+    status: Option<VideoStatus>,
+}
+
+#[derive(Deserialize)]
+struct UploadPlaylistSnippet {
+    title: String,
+    thumbnails: Option<Thumbnails>,
+    #[serde(rename = "publishedAt")]
+    published_at: Option<String>,
+    #[serde(rename = "resourceId")]
+    resource_id: ResourceId,
+}
+
+#[derive(Deserialize)]
+struct ResourceId {
+    #[serde(rename = "videoId")]
+    video_id: String,
+}
+
 #[derive(Deserialize)]
 struct VideoItem {
     id: String,
@@ -609,37 +643,49 @@ fn parse_duration(iso: &str) -> String {
     }
 }
 
-/// Fetch the channel's uploads and split them into live broadcasts vs regular videos.
-///
-/// Uses `liveStreamingDetails` from `videos.list` to reliably detect live events,
-/// including completed ones (where `liveBroadcastContent` would be "none").
-pub async fn fetch_channel_content(
+/// Load the stored token, refreshing it when it expires within five minutes
+/// (or unconditionally with `force`).
+async fn fresh_token(
     pool: &PgPool,
     config: &YouTubeConfig,
-) -> anyhow::Result<ChannelContent> {
-    let mut token = load_tokens(pool)
+    force: bool,
+) -> anyhow::Result<StoredToken> {
+    let token = load_tokens(pool)
         .await
         .ok_or_else(|| anyhow::anyhow!("No YouTube token stored"))?;
 
-    let needs_refresh = token.expires_at.map_or(false, |exp| {
-        exp - Utc::now() < chrono::Duration::minutes(5)
-    });
+    let needs_refresh = force
+        || token.expires_at.map_or(false, |exp| {
+            exp - Utc::now() < chrono::Duration::minutes(5)
+        });
+    if !needs_refresh {
+        return Ok(token);
+    }
 
-    if needs_refresh {
-        token = match refresh_tokens(pool, config, &token).await {
-            Ok(t) => t,
-            Err(e) => {
-                // Tokens are now invalid — remove them so the connector won't
-                // appear connected on the next startup.
-                if e.is::<AuthRequired>() {
-                    let _ = delete_tokens(pool).await;
-                }
-                return Err(e);
+    match refresh_tokens(pool, config, &token).await {
+        Ok(t) => Ok(t),
+        Err(e) => {
+            // Tokens are now invalid — remove them so the connector won't
+            // appear connected on the next startup.
+            if e.is::<AuthRequired>() {
+                let _ = delete_tokens(pool).await;
             }
-        };
+            Err(e)
+        }
     }
+}
 
-    let client = reqwest::Client::new();
+/// Fetch the channel's uploads and split them into live broadcasts vs regular videos.
+///
+/// Uses `liveStreamingDetails` from `videos.list` to reliably detect live events,
+/// including completed ones (where `liveBroadcastContent` would be "none").
+pub async fn fetch_channel_content(
+    pool: &PgPool,
+    config: &YouTubeConfig,
+) -> anyhow::Result<ChannelContent> {
+    let token = fresh_token(pool, config, false).await?;
+
+    let client = crate::http::http_client();
     let all_items = fetch_all_uploads(&client, &token.access_token).await?;
 
     let mut live_broadcasts = Vec::new();
@@ -724,6 +770,96 @@ async fn fetch_all_uploads(
     Ok(videos_resp.items.unwrap_or_default())
 }
 
+/// Latest `max_results` uploads (1–50) of the authenticated channel.
+///
+/// A 401 from the Data API is returned as [`AuthRequired`] so callers can
+/// prompt for re-authentication instead of showing a generic error.
+pub async fn list_recent_uploads(
+    access_token: &str,
+    max_results: u32,
+) -> anyhow::Result<Vec<RecentUpload>> {
+    let client = crate::http::http_client();
+
+    let channel_resp = client
+        .get("https://www.googleapis.com/youtube/v3/channels")
+        .query(&[("part", "contentDetails"), ("mine", "true")])
+        .bearer_auth(access_token)
+        .send()
+        .await?;
+    let uploads_playlist_id = check_auth(channel_resp)?
+        .json::<YtList<ChannelItem>>()
+        .await?
+        .items
+        .and_then(|items| items.into_iter().next())
+        .map(|c| c.content_details.related_playlists.uploads)
+        .ok_or_else(|| anyhow::anyhow!("No YouTube channel found for this account"))?;
+
+    let max_results = max_results.clamp(1, 50).to_string();
+    let playlist_resp = client
+        .get("https://www.googleapis.com/youtube/v3/playlistItems")
+        .query(&[
+            ("part", "snippet,status"),
+            ("playlistId", uploads_playlist_id.as_str()),
+            ("maxResults", max_results.as_str()),
+        ])
+        .bearer_auth(access_token)
+        .send()
+        .await?;
+    let items = check_auth(playlist_resp)?
+        .json::<YtList<UploadPlaylistItem>>()
+        .await?
+        .items
+        .unwrap_or_default();
+
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            let video_id = item.snippet.resource_id.video_id;
+            RecentUpload {
+                watch_url: format!("https://www.youtube.com/watch?v={video_id}"),
+                video_id,
+                title: item.snippet.title,
+                published_at: item.snippet.published_at,
+                privacy_status: item
+                    .status
+                    .map(|s| s.privacy_status)
+                    .unwrap_or_else(|| "public".to_string()),
+                thumbnail_url: item
+                    .snippet
+                    .thumbnails
+                    .as_ref()
+                    .and_then(|t| t.best_url())
+                    .map(String::from),
+            }
+        })
+        .collect())
+}
+
+/// [`list_recent_uploads`] using the stored token. A rejected access token is
+/// refreshed once and retried before giving up with [`AuthRequired`].
+pub async fn fetch_recent_uploads(
+    pool: &PgPool,
+    config: &YouTubeConfig,
+    max_results: u32,
+) -> anyhow::Result<Vec<RecentUpload>> {
+    let token = fresh_token(pool, config, false).await?;
+    match list_recent_uploads(&token.access_token, max_results).await {
+        Err(e) if e.is::<AuthRequired>() => {
+            let token = fresh_token(pool, config, true).await?;
+            list_recent_uploads(&token.access_token, max_results).await
+        }
+        other => other,
+    }
+}
+
+/// Map a 401 to [`AuthRequired`]; other error statuses become plain errors.
+fn check_auth(resp: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+    if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(AuthRequired.into());
+    }
+    Ok(resp.error_for_status()?)
+}
+
 fn to_channel_video_item(v: VideoItem) -> ChannelVideoItem {
     let snippet = v.snippet.as_ref();
     let title = snippet.map(|s| s.title.clone()).unwrap_or_default();
@@ -0,0 +1,2 @@
+ALTER TABLE ppt_folders
+    ADD COLUMN IF NOT EXISTS extensions TEXT[] NOT NULL DEFAULT ARRAY['ppt', 'pptx', 'odp'];
@@ -0,0 +1,17 @@
+-- ============================================================
+-- 017_upload_history.sql
+-- Remember which files were uploaded so re-scanned recordings
+-- can be recognised before they are uploaded a second time.
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS upload_history (
+    id          UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    file_path   TEXT        NOT NULL,
+    checksum    TEXT        NOT NULL,
+    platform    TEXT        NOT NULL,
+    video_id    TEXT        NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_history_checksum
+    ON upload_history (checksum);
@@ -0,0 +1,8 @@
+-- ============================================================
+-- 018_broadlink_device_port.sql
+-- Remember the UDP port a device answered a probe on, so learn
+-- and send reach it without probing again. NULL means port 80.
+-- ============================================================
+
+ALTER TABLE broadlink_devices
+    ADD COLUMN IF NOT EXISTS port INTEGER CHECK (port BETWEEN 1 AND 65535);
@@ -0,0 +1,73 @@
+use serde::Serialize;
+use xcap::Monitor;
+
+/// One connected monitor as reported by the OS.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    pub index: usize,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub is_primary: bool,
+}
+
+/// Enumerate connected monitors in OS order.
+pub fn list_displays() -> Result<Vec<DisplayInfo>, String> {
+    let monitors = Monitor::all().map_err(|e| e.to_string())?;
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, m)| DisplayInfo {
+            index,
+            name: m.name().unwrap_or_default(),
+            width: m.width().unwrap_or(0),
+            height: m.height().unwrap_or(0),
+            x: m.x().unwrap_or(0),
+            y: m.y().unwrap_or(0),
+            is_primary: m.is_primary().unwrap_or(false),
+        })
+        .collect())
+}
+
+/// A PNG capture of one display.
+pub struct DisplayCapture {
+    pub png: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The display a slideshow is most likely projected on: the first
+/// non-primary monitor, or the primary one when only a single display exists.
+fn presentation_monitor() -> Result<Monitor, String> {
+    let monitors = Monitor::all().map_err(|e| e.to_string())?;
+    let mut primary = None;
+    for monitor in monitors {
+        if monitor.is_primary().unwrap_or(false) {
+            primary.get_or_insert(monitor);
+        } else {
+            return Ok(monitor);
+        }
+    }
+    primary.ok_or_else(|| "No display found".to_string())
+}
+
+/// Capture the presentation display as PNG, downscaled to at most
+/// `max_width` pixels wide (aspect ratio kept). Blocking; call from
+/// `spawn_blocking`.
+pub fn capture_presentation_display(max_width: Option<u32>) -> Result<DisplayCapture, String> {
+    let monitor = presentation_monitor()?;
+    let mut img = monitor.capture_image().map_err(|e| e.to_string())?;
+    if let Some(max) = max_width.filter(|&m| m > 0 && m < img.width()) {
+        let height = (u64::from(img.height()) * u64::from(max) / u64::from(img.width())).max(1) as u32;
+        img = image::imageops::resize(&img, max, height, image::imageops::FilterType::Triangle);
+    }
+    let (width, height) = img.dimensions();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(DisplayCapture { png, width, height })
+}
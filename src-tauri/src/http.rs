@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+const USER_AGENT: &str = "sermon-helper-tauri";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Always bypassed so the local API, discovery and OAuth callback servers
+/// are never routed through the proxy.
+const LOCAL_NO_PROXY: &str = "localhost,127.0.0.1,::1";
+
+/// Outbound HTTP proxy, persisted in the store under `http_proxy`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpProxyConfig {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+// No overall request timeout: chunked uploads can legitimately run for
+// minutes. Callers that need one set it per request with `.timeout(..)`.
+static CLIENT: LazyLock<RwLock<reqwest::Client>> = LazyLock::new(|| {
+    let client = build_client(None).unwrap_or_else(|e| {
+        tracing::error!("http_client: falling back to default client: {e}");
+        reqwest::Client::new()
+    });
+    RwLock::new(client)
+});
+
+fn build_client(proxy: Option<&HttpProxyConfig>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT);
+
+    if let Some(cfg) = proxy.filter(|c| !c.url.trim().is_empty()) {
+        let mut p = reqwest::Proxy::all(cfg.url.trim()).map_err(|e| format!("Invalid proxy URL: {e}"))?;
+        if let Some(user) = cfg.username.as_deref().filter(|u| !u.is_empty()) {
+            p = p.basic_auth(user, cfg.password.as_deref().unwrap_or(""));
+        }
+        let no_proxy = match std::env::var("NO_PROXY").or_else(|_| std::env::var("no_proxy")) {
+            Ok(env) if !env.trim().is_empty() => format!("{env},{LOCAL_NO_PROXY}"),
+            _ => LOCAL_NO_PROXY.to_string(),
+        };
+        p = p.no_proxy(reqwest::NoProxy::from_string(&no_proxy));
+        builder = builder.proxy(p);
+    }
+
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Shared outbound HTTP client. Cloning is cheap and reuses the same
+/// connection pool and TLS sessions.
+pub fn http_client() -> reqwest::Client {
+    CLIENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Rebuild the shared client with `proxy` (or without one when `None`).
+/// Clients already handed out keep their old settings until dropped.
+pub fn configure_proxy(proxy: Option<&HttpProxyConfig>) -> Result<(), String> {
+    let client = build_client(proxy)?;
+    *CLIENT.write().unwrap_or_else(|e| e.into_inner()) = client;
+    Ok(())
+}
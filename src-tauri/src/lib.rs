@@ -2,6 +2,8 @@
 mod badge;
 mod bible;
 mod commands;
+mod http;
+mod logs;
 
 // Models, database, server, and connectors are desktop-only.
 #[cfg(desktop)]
@@ -17,9 +19,13 @@ pub mod scheduler;
 #[cfg(desktop)]
 mod broadlink;
 #[cfg(desktop)]
+mod companion;
+#[cfg(desktop)]
 pub(crate) mod uploader;
 #[cfg(desktop)]
 mod obs_devices;
+#[cfg(desktop)]
+mod displays;
 
 use std::sync::Arc;
 use tauri::Manager;
@@ -33,6 +39,8 @@ use connectors::ConnectorConfig;
 pub struct AppRuntime {
     pub mode: Option<String>,
     pub server_port: u16,
+    /// Interface the Axum server binds to; `0.0.0.0` listens on all of them.
+    pub server_bind_address: std::net::IpAddr,
     pub client_url: Option<String>,
     pub auth_token: Arc<RwLock<String>>,
     #[cfg(desktop)]
@@ -61,6 +69,14 @@ pub struct AppRuntime {
     /// CSRF tokens generated by Tauri IPC commands are visible to the HTTP callback.
     #[cfg(desktop)]
     pub oauth_states: Arc<RwLock<std::collections::HashMap<String, (String, std::time::Instant)>>>,
+    /// When set, the Axum server rejects requests from non-LAN peers.
+    /// Shared with AppState so toggling takes effect without a restart.
+    #[cfg(desktop)]
+    pub lan_only: Arc<std::sync::atomic::AtomicBool>,
+    /// Named API keys accepted alongside `auth_token`; shared with AppState
+    /// so creating or revoking a key applies without a restart.
+    #[cfg(desktop)]
+    pub api_keys: Arc<RwLock<Vec<server::auth::ApiKey>>>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -78,12 +94,35 @@ pub fn run() {
     #[cfg(desktop)]
     let builder = builder.invoke_handler(tauri::generate_handler![
         bible::fetch_bible_v2,
+        bible::fetch_bible_v2_stream,
         bible::fetch_bible_suggestions,
         bible::fetch_bible_legacy,
+        bible::search_bible_text,
+        bible::fetch_bible_by_ids,
+        bible::fetch_bible_plaintext,
+        bible::fetch_random_verse,
+        bible::normalize_reference,
+        bible::download_bible_translation,
+        bible::set_bible_api_url,
         commands::collections::save_bruno_collection,
+        commands::companion::patch_companion_config,
+        commands::companion::press_companion_button,
+        commands::caption::set_live_caption,
+        commands::caption::get_live_caption,
+        commands::logs::get_recent_logs,
+        commands::http::get_http_proxy,
+        commands::http::set_http_proxy,
+        commands::settings::backup_settings,
+        commands::settings::list_settings_backups,
+        commands::settings::restore_settings_backup,
         commands::token::get_token,
         commands::token::refresh_token,
+        commands::token::list_api_keys,
+        commands::token::create_api_key,
+        commands::token::revoke_api_key,
         commands::server::get_server_port,
+        commands::server::get_server_bind_address,
+        commands::server::set_server_bind_address,
         commands::server::get_app_mode,
         commands::server::set_app_mode,
         commands::server::complete_setup,
@@ -91,6 +130,15 @@ pub fn run() {
         commands::server::get_client_token,
         commands::server::reset_setup,
         commands::server::get_local_ip,
+        commands::server::get_lan_only,
+        commands::server::set_lan_only,
+        commands::server::restart_server,
+        commands::server::start_stopped_server,
+        commands::server::best_url_for_client,
+        commands::server::get_server_config,
+        commands::server::check_port_available,
+        commands::server::get_server_idle_timeout,
+        commands::server::set_server_idle_timeout,
         commands::connectors::get_obs_config,
         commands::connectors::save_obs_config,
         commands::connectors::get_obs_status,
@@ -122,10 +170,36 @@ pub fn run() {
         commands::connectors::broadlink_learn,
         commands::connectors::broadlink_cancel_learn,
         commands::connectors::broadlink_send,
+        commands::connectors::broadlink_send_last_learned,
+        commands::connectors::broadlink_convert_code,
+        commands::connectors::broadlink_validate_code,
         commands::connectors::broadlink_test_device,
+        commands::connectors::broadlink_probe_port,
+        commands::connectors::broadlink_ping,
         commands::connectors::broadlink_list_interfaces,
+        commands::connectors::list_network_interfaces_detailed,
+        commands::connectors::resolve_host,
         commands::connectors::get_obs_stream_settings,
         commands::connectors::set_obs_stream_settings,
+        commands::connectors::get_obs_recording_directory,
+        commands::connectors::check_obs_connection,
+        commands::connectors::list_obs_scenes,
+        commands::connectors::set_obs_scene,
+        commands::connectors::set_obs_source_enabled,
+        commands::connectors::set_obs_text,
+        commands::pairing::generate_pairing_qr,
+        commands::presentation::presentation_validate_file,
+        commands::presentation::presentation_get_watchdog_interval,
+        commands::presentation::presentation_set_watchdog_interval,
+        commands::presentation::send_presentation_key,
+        commands::presentation::presentation_warmup,
+        commands::presentation::capture_slideshow_screenshot,
+        commands::presentation::list_displays,
+        commands::recordings::watch_recording_directory,
+        commands::recordings::stop_watching_recording_directory,
+        commands::recordings::split_recording,
+        commands::recordings::extract_audio,
+        commands::recordings::generate_recording_thumbnail,
         commands::badge::install_badge,
         commands::badge::get_obs_scenes,
         commands::badge::create_badge_sources,
@@ -136,11 +210,27 @@ pub fn run() {
     #[cfg(mobile)]
     let builder = builder.invoke_handler(tauri::generate_handler![
         bible::fetch_bible_v2,
+        bible::fetch_bible_v2_stream,
         bible::fetch_bible_suggestions,
         bible::fetch_bible_legacy,
+        bible::search_bible_text,
+        bible::fetch_bible_by_ids,
+        bible::fetch_bible_plaintext,
+        bible::fetch_random_verse,
+        bible::normalize_reference,
+        bible::download_bible_translation,
+        bible::set_bible_api_url,
+        commands::logs::get_recent_logs,
+        commands::http::get_http_proxy,
+        commands::http::set_http_proxy,
+        commands::settings::backup_settings,
+        commands::settings::list_settings_backups,
+        commands::settings::restore_settings_backup,
         commands::token::get_token,
         commands::token::refresh_token,
         commands::server::get_server_port,
+        commands::server::get_server_bind_address,
+        commands::server::set_server_bind_address,
         commands::server::get_app_mode,
         commands::server::set_app_mode,
         commands::server::complete_setup,
@@ -152,17 +242,41 @@ pub fn run() {
 
     builder
         .setup(|app| {
-            tracing_subscriber::fmt()
-                .with_env_filter(
+            use tracing_subscriber::prelude::*;
+            tracing_subscriber::registry()
+                .with(
                     tracing_subscriber::EnvFilter::try_from_default_env()
                         .unwrap_or_else(|_| "info".into()),
                 )
+                .with(tracing_subscriber::fmt::layer())
+                .with(logs::RingBufferLayer)
                 .init();
 
             // Load settings synchronously so AppRuntime is managed before the
             // UI can call any Tauri command.
             let store = app.store("app-settings.json")?;
 
+            if let Some(proxy) = store
+                .get("http_proxy")
+                .and_then(|v| serde_json::from_value::<http::HttpProxyConfig>(v).ok())
+            {
+                if let Err(e) = http::configure_proxy(Some(&proxy)) {
+                    tracing::warn!("Ignoring stored HTTP proxy: {e}");
+                }
+            }
+
+            if let Ok(dir) = app.path().app_data_dir() {
+                bible::set_offline_dir(dir.join("bible"));
+            }
+
+            #[cfg(desktop)]
+            if let Some(cfg) = store
+                .get("broadlink_config")
+                .and_then(|v| serde_json::from_value::<connectors::BroadlinkConfig>(v).ok())
+            {
+                broadlink::configure_interface_filter(&cfg.interface_include, &cfg.interface_exclude);
+            }
+
             let mode = store
                 .get("mode")
                 .and_then(|v| v.as_str().map(String::from));
@@ -208,6 +322,11 @@ pub fn run() {
                 .map(|p| p as u16)
                 .unwrap_or(3737);
 
+            let bind_address: std::net::IpAddr = store
+                .get("server_bind_address")
+                .and_then(|v| v.as_str().and_then(|s| s.parse().ok()))
+                .unwrap_or(std::net::IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED));
+
             let auth_token_arc = Arc::new(RwLock::new(auth_token));
 
             // Create connectors before AppRuntime so we can share the Arcs
@@ -228,6 +347,16 @@ pub fn run() {
             let discord_connector = Arc::new(connectors::discord::DiscordConnector::new());
             #[cfg(target_os = "macos")]
             let keynote_connector = Arc::new(connectors::keynote::KeynoteConnector::new());
+            #[cfg(target_os = "macos")]
+            {
+                keynote_connector.set_app_handle(app.handle().clone());
+                if let Some(secs) = store
+                    .get("presentation_watchdog_interval_secs")
+                    .and_then(|v| v.as_u64())
+                {
+                    keynote_connector.set_watchdog_interval_secs(secs);
+                }
+            }
 
             // Config Arcs created here so both AppRuntime (Tauri) and AppState
             // (Axum) hold the same Arc — writing via a Tauri command is
@@ -256,9 +385,23 @@ pub fn run() {
                 std::collections::HashMap::<String, (String, std::time::Instant)>::new(),
             ));
 
+            #[cfg(desktop)]
+            let lan_only_arc = Arc::new(std::sync::atomic::AtomicBool::new(
+                store.get("lan_only").and_then(|v| v.as_bool()).unwrap_or(false),
+            ));
+
+            #[cfg(desktop)]
+            let api_keys_arc: Arc<RwLock<Vec<server::auth::ApiKey>>> = Arc::new(RwLock::new(
+                store
+                    .get(server::auth::API_KEYS_STORE_KEY)
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default(),
+            ));
+
             let runtime = Arc::new(RwLock::new(AppRuntime {
                 mode: mode.clone(),
                 server_port: port,
+                server_bind_address: bind_address,
                 client_url,
                 auth_token: auth_token_arc.clone(),
                 #[cfg(desktop)]
@@ -283,6 +426,10 @@ pub fn run() {
                 facebook_config: Arc::clone(&fb_config_arc),
                 #[cfg(desktop)]
                 oauth_states: Arc::clone(&oauth_states_arc),
+                #[cfg(desktop)]
+                lan_only: Arc::clone(&lan_only_arc),
+                #[cfg(desktop)]
+                api_keys: Arc::clone(&api_keys_arc),
             }));
 
             // Managed here — guaranteed to exist before any invoke() call.
@@ -303,6 +450,8 @@ pub fn run() {
                 let yt_cfg = Arc::clone(&yt_config_arc);
                 let fb_cfg = Arc::clone(&fb_config_arc);
                 let oauth = Arc::clone(&oauth_states_arc);
+                let lan_only = Arc::clone(&lan_only_arc);
+                let api_keys = Arc::clone(&api_keys_arc);
                 #[cfg(target_os = "macos")]
                 let kn = Arc::clone(&keynote_connector);
 
@@ -310,6 +459,8 @@ pub fn run() {
                     if let Err(e) = start_server(
                         handle,
                         auth_token_arc,
+                        api_keys,
+                        bind_address,
                         port,
                         obs,
                         vmix,
@@ -319,6 +470,7 @@ pub fn run() {
                         yt_cfg,
                         fb_cfg,
                         oauth,
+                        lan_only,
                         #[cfg(target_os = "macos")]
                         kn,
                     )
@@ -355,14 +507,35 @@ pub fn run() {
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Give WebSocket clients a 1001 close so they back off instead of
+            // reconnect-storming the port while the app is gone: hold the exit
+            // until the server has drained, then exit for real.
+            #[cfg(desktop)]
+            if let tauri::RunEvent::ExitRequested { api, .. } = &event {
+                static DRAINED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+                if server::is_running() && !DRAINED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                    api.prevent_exit();
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        server::shutdown(std::time::Duration::from_secs(2)).await;
+                        app.exit(0);
+                    });
+                }
+            }
+            #[cfg(mobile)]
+            let _ = (app, event);
+        });
 }
 
 #[cfg(desktop)]
 pub(crate) async fn start_server(
     app: tauri::AppHandle,
     auth_token: Arc<RwLock<String>>,
+    api_keys: Arc<RwLock<Vec<server::auth::ApiKey>>>,
+    bind_address: std::net::IpAddr,
     port: u16,
     obs_connector: Arc<connectors::obs::ObsConnector>,
     vmix_connector: Arc<connectors::vmix::VmixConnector>,
@@ -372,6 +545,7 @@ pub(crate) async fn start_server(
     youtube_config: Arc<RwLock<connectors::YouTubeConfig>>,
     facebook_config: Arc<RwLock<connectors::FacebookConfig>>,
     oauth_states: Arc<RwLock<std::collections::HashMap<String, (String, std::time::Instant)>>>,
+    lan_only: Arc<std::sync::atomic::AtomicBool>,
     #[cfg(target_os = "macos")] keynote_connector: Arc<connectors::keynote::KeynoteConnector>,
 ) -> anyhow::Result<()> {
     use std::path::PathBuf;
@@ -445,11 +619,21 @@ pub(crate) async fn start_server(
 
     let cron_scheduler = Arc::new(scheduler::CronScheduler::new());
 
-    tracing::info!("Starting Axum on port {port}");
+    let idle_timeout = app
+        .store("app-settings.json")
+        .ok()
+        .and_then(|s| s.get("server_idle_timeout_secs"))
+        .and_then(|v| v.as_u64())
+        .filter(|&secs| secs > 0)
+        .map(std::time::Duration::from_secs);
+
+    tracing::info!("Starting Axum on {bind_address}:{port}");
     server::build_and_serve(
         pool,
         auth_token,
+        api_keys,
         connection_url,
+        bind_address,
         port,
         static_dir,
         obs_connector,
@@ -460,6 +644,8 @@ pub(crate) async fn start_server(
         youtube_config,
         facebook_config,
         oauth_states,
+        lan_only,
+        idle_timeout,
         Some(app.clone()),
         cron_scheduler,
         #[cfg(target_os = "macos")]
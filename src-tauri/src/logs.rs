@@ -0,0 +1,107 @@
+//! In-memory log sink.
+//!
+//! A `tracing` layer that keeps the most recent log lines in a bounded ring
+//! buffer so the support screen can show them without digging through files.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{LazyLock, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Maximum number of entries kept in memory; older entries are dropped first.
+const CAPACITY: usize = 1000;
+
+static BUFFER: LazyLock<Mutex<VecDeque<LogEntry>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CAPACITY)));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    pub ts: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Layer that copies every event that passes the global filter into [`BUFFER`].
+pub struct RingBufferLayer;
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let meta = event.metadata();
+        let entry = LogEntry {
+            ts: Utc::now(),
+            level: meta.level().to_string(),
+            target: meta.target().to_string(),
+            message: visitor.finish(),
+        };
+
+        let mut buf = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+        if buf.len() == CAPACITY {
+            buf.pop_front();
+        }
+        buf.push_back(entry);
+    }
+}
+
+/// Collects the `message` field plus any extra `key=value` fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields
+        } else {
+            format!("{} {}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={value:?}", field.name());
+        }
+    }
+}
+
+/// Return up to `limit` of the newest entries at `min_level` or more severe,
+/// oldest first.
+pub fn recent(min_level: Level, limit: usize) -> Vec<LogEntry> {
+    let buf = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries: Vec<LogEntry> = buf
+        .iter()
+        .rev()
+        .filter(|e| e.level.parse::<Level>().is_ok_and(|l| l <= min_level))
+        .take(limit)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
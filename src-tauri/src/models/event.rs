@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
@@ -149,14 +150,14 @@ pub async fn find_current_event(pool: &PgPool) -> anyhow::Result<Option<EventSum
 }
 
 /// Connection spec in a create/update request body.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateConnection {
     pub platform: String,
     pub privacy_status: Option<String>,
 }
 
 /// Bible reference spec in a create/update request body.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateBibleReference {
     pub r#type: String,
     pub reference: Option<String>,
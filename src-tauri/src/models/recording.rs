@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -68,7 +69,7 @@ pub struct FlagUploadRequest {
     pub recordings: Vec<FlagUploadItem>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct FlagUploadItem {
     pub recording_id: Uuid,
     pub custom_title: Option<String>,
@@ -76,4 +77,7 @@ pub struct FlagUploadItem {
     pub youtube_visibility: Option<String>,
     pub facebook_visibility: Option<String>,
     pub platforms: Vec<String>,
+    /// Fill an absent `custom_description` from the stored description template.
+    #[serde(default)]
+    pub use_description_template: bool,
 }
@@ -34,14 +34,14 @@ fn property_for_kind(kind: &str) -> &'static str {
 
 // ── Public types ──────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ObsDeviceItem {
     pub item_name: String,
     pub item_value: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ObsAvailableDevices {
     pub displays: Vec<ObsDeviceItem>,
@@ -52,7 +52,7 @@ pub struct ObsAvailableDevices {
     pub scanned_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceListenerStatus {
     pub listener_id: Uuid,
@@ -4,13 +4,14 @@ use std::sync::Arc;
 use axum::extract::ws::Message;
 use serde_json::json;
 use sqlx::PgPool;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use uuid::Uuid;
 
 use crate::connectors::youtube;
 use crate::models::cron_job;
 use crate::models::event::{fetch_event, Event};
+use crate::server::websocket::WsClientSender;
 use crate::uploader::UploadService;
 
 pub struct CronScheduler {
@@ -29,7 +30,7 @@ impl CronScheduler {
     pub async fn reload(
         &self,
         pool: PgPool,
-        ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+        ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
         youtube_connector: Arc<crate::connectors::youtube::YouTubeConnector>,
         upload_service: Arc<UploadService>,
     ) {
@@ -118,7 +119,7 @@ impl Default for CronScheduler {
 async fn run_job(
     job: cron_job::CronJob,
     pool: PgPool,
-    ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     youtube_connector: Arc<crate::connectors::youtube::YouTubeConnector>,
     upload_service: Arc<UploadService>,
 ) {
@@ -140,7 +141,7 @@ async fn run_job(
 /// directly so connected clients get real-time updates.
 async fn pull_youtube_live(
     pool: PgPool,
-    ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     _youtube_connector: Arc<crate::connectors::youtube::YouTubeConnector>,
 ) {
     let token = match youtube::load_tokens(&pool).await {
@@ -151,7 +152,7 @@ async fn pull_youtube_live(
         }
     };
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let resp = client
         .get("https://www.googleapis.com/youtube/v3/liveBroadcasts")
         .query(&[
@@ -409,7 +410,7 @@ async fn pull_youtube_live(
 async fn emit_event_changed(
     operation: &str,
     event: Event,
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
 ) {
     let msg = json!({
         "type": "event.changed",
@@ -424,7 +425,7 @@ async fn emit_event_changed(
 
 async fn broadcast_cron_status(
     has_live: bool,
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
 ) {
     let msg = json!({ "type": "cron.youtube_pull", "hasLive": has_live }).to_string();
     let guard = ws_clients.read().await;
@@ -1,12 +1,164 @@
 use axum::{
     extract::{Request, State},
-    http::{header, StatusCode},
+    http::{header, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::server::AppState;
 
+/// Settings-store key holding the list of named API keys.
+pub const API_KEYS_STORE_KEY: &str = "api_keys";
+
+/// Read-only access: every `GET` endpoint except those that return secrets.
+pub const SCOPE_STATUS_READ: &str = "status:read";
+/// Fire stored RF/IR commands and sequences.
+pub const SCOPE_RFIR_EXECUTE: &str = "rfir:execute";
+/// Drive slideshows and overlays: `/ppt`, `/presentation`, `/keynote`,
+/// `/presenter` and `/caption`.
+pub const SCOPE_PRESENTATION_CONTROL: &str = "presentation:control";
+/// Start/stop streaming and recording, switch scenes, edit sources.
+pub const SCOPE_OBS_CONTROL: &str = "obs:control";
+/// Every other write: events, recordings, uploads, settings, device management.
+pub const SCOPE_ADMIN: &str = "admin";
+
+/// Every scope a key can hold; new keys get all of them unless told otherwise.
+pub const ALL_SCOPES: &[&str] = &[
+    SCOPE_STATUS_READ,
+    SCOPE_RFIR_EXECUTE,
+    SCOPE_PRESENTATION_CONTROL,
+    SCOPE_OBS_CONTROL,
+    SCOPE_ADMIN,
+];
+
+fn default_scopes() -> Vec<String> {
+    ALL_SCOPES.iter().map(|s| s.to_string()).collect()
+}
+
+/// A long-lived credential accepted alongside the primary auth token.
+/// The `id` is the secret presented as the bearer token, so rotating the
+/// primary token leaves integrations such as Companion working.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Keys stored before scopes existed deserialize with full scope.
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+}
+
+impl ApiKey {
+    pub fn has_all_scopes(&self) -> bool {
+        ALL_SCOPES.iter().all(|s| self.scopes.iter().any(|own| own == s))
+    }
+}
+
+/// Check requested scopes against `ALL_SCOPES`; `None` means full scope.
+pub fn validate_scopes(scopes: Option<Vec<String>>) -> Result<Vec<String>, String> {
+    let Some(scopes) = scopes else { return Ok(default_scopes()) };
+    if let Some(unknown) = scopes.iter().find(|s| !ALL_SCOPES.contains(&s.as_str())) {
+        return Err(format!(
+            "unknown scope '{unknown}' (expected one of {})",
+            ALL_SCOPES.join(", ")
+        ));
+    }
+    if scopes.is_empty() {
+        return Err("an API key needs at least one scope".to_string());
+    }
+    Ok(scopes)
+}
+
+/// Who presented a bearer token.
+pub enum Credential {
+    /// The primary auth token; always has full access.
+    Primary,
+    ApiKey(ApiKey),
+}
+
+impl Credential {
+    pub fn allows(&self, scope: &str) -> bool {
+        match self {
+            Credential::Primary => true,
+            Credential::ApiKey(key) => key.scopes.iter().any(|s| s == scope),
+        }
+    }
+
+    /// Full command access over WebSocket; limited keys stay read-only there
+    /// because WS commands aren't mapped to scopes.
+    pub fn is_unrestricted(&self) -> bool {
+        match self {
+            Credential::Primary => true,
+            Credential::ApiKey(key) => key.has_all_scopes(),
+        }
+    }
+}
+
+/// Resolve `provided` to the primary token or one of the named API keys.
+pub async fn authenticate(state: &AppState, provided: &str) -> Option<Credential> {
+    if provided == *state.auth_token.read().await {
+        return Some(Credential::Primary);
+    }
+    let id = provided.parse::<Uuid>().ok()?;
+    let keys = state.api_keys.read().await;
+    keys.iter().find(|key| key.id == id).cloned().map(Credential::ApiKey)
+}
+
+/// Routes that hand out secrets (stream keys, every stored IR/RF code) or
+/// widen what the server may read from disk. These need `admin` whatever
+/// the method, so a read-only or presentation key can't reach them.
+fn is_admin_only(method: &Method, path: &str) -> bool {
+    path.ends_with("/stream-key")
+        || path == "/connectors/broadlink/pack"
+        || ((path == "/ppt/folders" || path.starts_with("/ppt/folders/"))
+            && method != Method::GET
+            && method != Method::HEAD)
+}
+
+/// Scope needed for `method` on `path` (relative to `/api`).
+pub fn required_scope(method: &Method, path: &str) -> &'static str {
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    if is_admin_only(method, path) {
+        return SCOPE_ADMIN;
+    }
+    if method == Method::GET || method == Method::HEAD {
+        return SCOPE_STATUS_READ;
+    }
+    if path.starts_with("/connectors/broadlink/")
+        && (path.ends_with("/send") || path.ends_with("/execute"))
+    {
+        SCOPE_RFIR_EXECUTE
+    } else if ["/ppt/", "/presentation/", "/keynote/", "/presenter/", "/caption/"]
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+    {
+        SCOPE_PRESENTATION_CONTROL
+    } else if path.starts_with("/connectors/obs/") {
+        SCOPE_OBS_CONTROL
+    } else {
+        SCOPE_ADMIN
+    }
+}
+
+/// Routes that also take the token as `?token=`, like `/ws`: browser
+/// `EventSource` can't set an `Authorization` header.
+const QUERY_TOKEN_PATHS: &[&str] = &["/v1/events"];
+
+fn query_token(req: &Request) -> Option<String> {
+    let path = req.uri().path();
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    if !QUERY_TOKEN_PATHS.contains(&path) {
+        return None;
+    }
+    axum::extract::Query::<std::collections::HashMap<String, String>>::try_from_uri(req.uri())
+        .ok()
+        .and_then(|q| q.0.get("token").cloned())
+}
+
 pub async fn auth_middleware(
     State(state): State<AppState>,
     req: Request,
@@ -17,16 +169,69 @@ pub async fn auth_middleware(
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .and_then(|v| v.strip_prefix("Bearer "))
-        .map(|s| s.to_string());
+        .map(|s| s.to_string())
+        .or_else(|| query_token(&req));
 
     let Some(provided) = token else {
         return Err(StatusCode::UNAUTHORIZED);
     };
 
-    let current = state.auth_token.read().await;
-    if provided != *current {
+    let Some(credential) = authenticate(&state, &provided).await else {
         return Err(StatusCode::UNAUTHORIZED);
+    };
+    if !credential.allows(required_scope(req.method(), req.uri().path())) {
+        return Err(StatusCode::FORBIDDEN);
     }
 
     Ok(next.run(req).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_need_status_read() {
+        assert_eq!(required_scope(&Method::GET, "/api/events"), SCOPE_STATUS_READ);
+        assert_eq!(required_scope(&Method::GET, "/api/ppt/folders"), SCOPE_STATUS_READ);
+        assert_eq!(required_scope(&Method::HEAD, "/api/connectors/obs/status"), SCOPE_STATUS_READ);
+    }
+
+    #[test]
+    fn secrets_need_admin_even_for_get() {
+        for path in [
+            "/api/connectors/youtube/stream-key",
+            "/api/connectors/facebook/stream-key",
+            "/api/connectors/broadlink/pack",
+        ] {
+            assert_eq!(required_scope(&Method::GET, path), SCOPE_ADMIN, "{path}");
+        }
+    }
+
+    #[test]
+    fn ppt_folder_changes_need_admin() {
+        assert_eq!(required_scope(&Method::POST, "/api/ppt/folders"), SCOPE_ADMIN);
+        assert_eq!(required_scope(&Method::DELETE, "/api/ppt/folders/3"), SCOPE_ADMIN);
+        assert_eq!(
+            required_scope(&Method::POST, "/api/keynote/next"),
+            SCOPE_PRESENTATION_CONTROL
+        );
+    }
+
+    #[test]
+    fn writes_map_to_their_scope() {
+        assert_eq!(
+            required_scope(&Method::POST, "/api/connectors/broadlink/commands/1/send"),
+            SCOPE_RFIR_EXECUTE
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/connectors/obs/stream/start"),
+            SCOPE_OBS_CONTROL
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/connectors/broadlink/pack/import"),
+            SCOPE_ADMIN
+        );
+        assert_eq!(required_scope(&Method::POST, "/api/events"), SCOPE_ADMIN);
+    }
+}
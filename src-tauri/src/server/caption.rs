@@ -3,14 +3,29 @@
 //! Provides two unauthenticated endpoints:
 //! - `GET /caption?...`       — returns HTML for OBS browser source
 //! - `GET /caption/logo`      — returns the SVG logo from caption-settings.json
+//! - `GET /caption/scripture?ref=...&translation=...` — fetches a Bible
+//!   passage and renders it with the full-screen caption layout
+//!
+//! A live caption set via `set_live_caption` (or `POST /api/caption/set`)
+//! replaces the query text on `/caption` until it expires or is cleared.
+//! Changes are broadcast as `caption.changed`; add `live=true` to the OBS
+//! source URL so the page reloads on them.
 
 use axum::{
     extract::{Query, State},
     http::{header, StatusCode},
     response::{Html, IntoResponse},
+    Json,
 };
-use serde::Deserialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use tauri_plugin_store::StoreExt;
+use tokio::sync::broadcast;
 
 use crate::server::AppState;
 
@@ -34,6 +49,9 @@ pub struct CaptionQuery {
     width: Option<u32>,
     #[serde(default)]
     height: Option<u32>,
+    /// Reload the page whenever the live caption changes.
+    #[serde(default)]
+    live: bool,
 }
 
 fn default_caption_type() -> String {
@@ -60,7 +78,104 @@ fn html_escape(s: &str) -> String {
         .replace('\'', "&#39;")
 }
 
-pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String> {
+/// Caption text currently overriding `/caption`.
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveCaption {
+    pub text: String,
+    /// Layout the text targets (`caption`, `full`, `preview`); `None` applies
+    /// it to every `/caption` source.
+    #[serde(rename = "type")]
+    pub caption_type: Option<String>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Current live caption, tagged with the generation that set it.
+static LIVE_CAPTION: Mutex<Option<(u64, LiveCaption)>> = Mutex::new(None);
+static LIVE_CAPTION_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Fires with the new state on every live caption change, including expiry.
+pub static LIVE_CAPTION_TX: LazyLock<broadcast::Sender<Option<LiveCaption>>> =
+    LazyLock::new(|| broadcast::channel(16).0);
+
+/// Show `text` on `/caption` instead of the query text. With a `duration`,
+/// the caption clears itself afterwards unless it was replaced in between.
+/// Empty text clears the live caption. Returns the new state.
+pub fn set_live_caption(
+    text: String,
+    caption_type: Option<String>,
+    duration: Option<Duration>,
+) -> Option<LiveCaption> {
+    let generation = LIVE_CAPTION_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let text = text.trim().to_string();
+    let caption = (!text.is_empty()).then(|| LiveCaption {
+        text,
+        caption_type: caption_type.filter(|t| !t.is_empty()),
+        expires_at: duration
+            .and_then(|d| chrono::Duration::from_std(d).ok())
+            .map(|d| chrono::Utc::now() + d),
+    });
+    *LIVE_CAPTION.lock().unwrap_or_else(|e| e.into_inner()) =
+        caption.clone().map(|c| (generation, c));
+    let _ = LIVE_CAPTION_TX.send(caption.clone());
+
+    if let (Some(duration), Some(_)) = (duration, &caption) {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(duration).await;
+            let mut live = LIVE_CAPTION.lock().unwrap_or_else(|e| e.into_inner());
+            if live.as_ref().is_some_and(|(g, _)| *g == generation) {
+                *live = None;
+                let _ = LIVE_CAPTION_TX.send(None);
+            }
+        });
+    }
+    caption
+}
+
+pub fn live_caption() -> Option<LiveCaption> {
+    let live = LIVE_CAPTION.lock().unwrap_or_else(|e| e.into_inner());
+    live.as_ref().map(|(_, caption)| caption.clone())
+}
+
+pub async fn current_caption_handler() -> impl IntoResponse {
+    Json(json!({ "caption": live_caption() }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCaptionBody {
+    #[serde(default)]
+    pub text: String,
+    #[serde(rename = "type")]
+    pub caption_type: Option<String>,
+    /// Clear automatically after this many milliseconds; omit or 0 to keep.
+    pub duration_ms: Option<u64>,
+}
+
+pub async fn set_caption_handler(Json(body): Json<SetCaptionBody>) -> impl IntoResponse {
+    let duration = body.duration_ms.filter(|&ms| ms > 0).map(Duration::from_millis);
+    let caption = set_live_caption(body.text, body.caption_type, duration);
+    Json(json!({ "caption": caption }))
+}
+
+pub async fn caption_handler(Query(mut params): Query<CaptionQuery>) -> Html<String> {
+    let live = live_caption().filter(|c| {
+        c.caption_type.as_ref().is_none_or(|t| *t == params.caption_type)
+    });
+    if let Some(live) = live {
+        if params.caption_type == "full" || params.caption_type == "preview" {
+            params.title = live.text;
+        } else {
+            params.bold = live.text;
+            params.light.clear();
+        }
+    }
+    Html(render_caption(&params, None))
+}
+
+// Render the caption HTML. `title_size` overrides the full-screen title's
+// font size (in 1080p pixels) for callers with longer text.
+fn render_caption(params: &CaptionQuery, title_size: Option<u32>) -> String {
     // Resolution-based base dimensions
     let (base_width, base_height) = match params.resolution.as_str() {
         "4k" => (3840u32, 2160u32),
@@ -88,7 +203,24 @@ pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String>
     // Scale factor: 1 for 1080p, 2 for 4K
     let scale: u32 = if params.resolution == "4k" { 2 } else { 1 };
 
-    let html = if params.caption_type == "full" || params.caption_type == "preview" {
+    // Reload on `caption.changed`; unauthenticated WS connections are read-only
+    // but still receive broadcasts.
+    let refresh_html = if params.live {
+        r#"<script>
+    (function connect() {
+        var ws = new WebSocket((location.protocol === 'https:' ? 'wss://' : 'ws://') + location.host + '/ws');
+        ws.onmessage = function (e) {
+            if (JSON.parse(e.data).type === 'caption.changed') location.reload();
+        };
+        ws.onclose = function () { setTimeout(connect, 2000); };
+    })();
+    </script>"#
+            .to_string()
+    } else {
+        String::new()
+    };
+
+    if params.caption_type == "full" || params.caption_type == "preview" {
         // Preview / full-screen layout
         let title_html = if !params.title.is_empty() {
             format!(
@@ -127,7 +259,7 @@ pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String>
             String::new()
         };
 
-        let title_size = 200 * scale;
+        let title_size = title_size.unwrap_or(200) * scale;
         let title_margin = 50 * scale;
         let dot_size = 15 * scale;
         let dot_margin = 16 * scale;
@@ -139,6 +271,7 @@ pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String>
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    {refresh_html}
     <title>OBS Caption</title>
     <link href="https://fonts.googleapis.com/css2?family=Oswald:wght@300;600&display=swap" rel="stylesheet">
     <style>
@@ -271,6 +404,7 @@ pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String>
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    {refresh_html}
     <title>OBS Caption</title>
     <link href="https://fonts.googleapis.com/css2?family=Oswald:wght@300;600&display=swap" rel="stylesheet">
     <style>
@@ -354,9 +488,133 @@ pub async fn caption_handler(Query(params): Query<CaptionQuery>) -> Html<String>
 </body>
 </html>"#
         )
-    };
+    }
+}
 
-    Html(html)
+#[derive(Deserialize)]
+pub struct ScriptureQuery {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(default = "default_translation")]
+    translation: String,
+    #[serde(default = "default_color")]
+    color: String,
+    #[serde(rename = "showLogo", default = "default_show_logo")]
+    show_logo: String,
+    #[serde(default = "default_resolution")]
+    resolution: String,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+}
+
+fn default_translation() -> String {
+    "RUF".to_string()
+}
+
+#[derive(Clone)]
+struct Passage {
+    label: String,
+    text: String,
+}
+
+/// How long a fetched passage is reused. OBS reloads browser sources on
+/// scene changes, so this keeps repeated loads off the Bible API.
+const SCRIPTURE_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Passages kept at most; this route is open to the LAN, so the cache must
+/// not grow with every reference a peer asks for.
+const SCRIPTURE_CACHE_MAX: usize = 64;
+
+// Passages keyed by (reference, translation), with their fetch time.
+static SCRIPTURE_CACHE: LazyLock<Mutex<HashMap<(String, String), (Instant, Passage)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Title font size for a passage of `len` characters: full size for a short
+// verse, shrinking with the square root of the length so the wrapped text
+// keeps roughly the same area on screen.
+fn passage_title_size(len: usize) -> u32 {
+    const FULL_SIZE_CHARS: f64 = 60.0;
+    if len as f64 <= FULL_SIZE_CHARS {
+        return 200;
+    }
+    ((200.0 * (FULL_SIZE_CHARS / len as f64).sqrt()) as u32).max(40)
+}
+
+async fn fetch_passage(reference: &str, translation: &str, api_url: String) -> Result<Passage, String> {
+    let key = (reference.to_string(), translation.to_string());
+    {
+        let cache = SCRIPTURE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((fetched_at, passage)) = cache.get(&key) {
+            if fetched_at.elapsed() < SCRIPTURE_CACHE_TTL {
+                return Ok(passage.clone());
+            }
+        }
+    }
+
+    let data = crate::bible::fetch_v2(
+        reference.to_string(),
+        translation.to_string(),
+        api_url,
+        crate::bible::CleanOptions::default(),
+    )
+    .await?;
+    if data.verses.is_empty() {
+        return Err(format!("No verses found for '{reference}'"));
+    }
+    let text = data
+        .verses
+        .iter()
+        .map(|v| v.text.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let passage = Passage { label: data.label, text };
+
+    let mut cache = SCRIPTURE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|_, (fetched_at, _)| fetched_at.elapsed() < SCRIPTURE_CACHE_TTL);
+    while cache.len() >= SCRIPTURE_CACHE_MAX {
+        let Some(oldest) = cache.iter().min_by_key(|(_, (at, _))| *at).map(|(k, _)| k.clone()) else { break };
+        cache.remove(&oldest);
+    }
+    cache.insert(key, (Instant::now(), passage.clone()));
+    Ok(passage)
+}
+
+/// Render a Bible passage as a full-screen caption. When the passage can't be
+/// fetched the reference itself is shown, so the browser source never goes
+/// blank mid-service.
+pub async fn scripture_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ScriptureQuery>,
+) -> Html<String> {
+    let reference = params.reference.trim();
+    // The V2 API takes the bare code; the frontend's translation ids carry a suffix.
+    let translation = params.translation.trim().trim_end_matches("_v2").to_string();
+    let api_url = crate::bible::configured_v2_api_url(state.app_handle.as_ref());
+
+    let (title, bold) = match fetch_passage(reference, &translation, api_url).await {
+        Ok(passage) => (passage.text, passage.label),
+        Err(e) => {
+            tracing::warn!("Failed to fetch scripture caption for '{reference}': {e}");
+            (String::new(), reference.to_string())
+        }
+    };
+    let title_size = passage_title_size(title.chars().count());
+
+    let caption = CaptionQuery {
+        caption_type: "full".to_string(),
+        title,
+        bold,
+        light: translation,
+        color: params.color,
+        show_logo: params.show_logo,
+        resolution: params.resolution,
+        width: params.width,
+        height: params.height,
+        live: false,
+    };
+    Html(render_caption(&caption, Some(title_size)))
 }
 
 pub async fn caption_logo_handler(State(state): State<AppState>) -> impl IntoResponse {
@@ -0,0 +1,52 @@
+//! `Path`/`Query` wrappers whose rejections use the same JSON error envelope
+//! as the handlers, instead of axum's plain-text bodies.
+
+use axum::{
+    extract::{FromRequestParts, Path, Query},
+    http::{request::Parts, StatusCode},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+type JsonRejection = (StatusCode, Json<Value>);
+
+fn rejection(status: StatusCode, message: String) -> JsonRejection {
+    (status, Json(json!({ "success": false, "error": message })))
+}
+
+/// Drop-in for `axum::extract::Path` with a JSON rejection body.
+pub struct ApiPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(Self(value)),
+            Err(e) => Err(rejection(e.status(), e.body_text())),
+        }
+    }
+}
+
+/// Drop-in for `axum::extract::Query` with a JSON rejection body.
+pub struct ApiQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<T>::from_request_parts(parts, state).await {
+            Ok(Query(value)) => Ok(Self(value)),
+            Err(e) => Err(rejection(e.status(), e.body_text())),
+        }
+    }
+}
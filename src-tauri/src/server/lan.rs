@@ -0,0 +1,70 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::Ordering;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::server::AppState;
+
+/// Whether `ip` belongs to a private, loopback, or link-local network.
+///
+/// IPv4-mapped IPv6 addresses (`::ffff:192.168.1.5`) are unwrapped first so
+/// dual-stack listeners classify them the same as plain IPv4 peers.
+pub fn is_lan_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_lan_address(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                // fc00::/7 unique local
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10 link-local
+                || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// Base URL (`http://ip:port`) a client at `client_ip` can reach the server on.
+///
+/// When bound to a single interface that address is the only option. When
+/// listening on all interfaces the local address on the client's subnet is
+/// chosen, so phones on a second NIC don't get an unreachable URL. Blocks
+/// while enumerating interfaces.
+pub fn best_url_for_client(client_ip: IpAddr, listen_addr: SocketAddr) -> Option<String> {
+    let port = listen_addr.port();
+    if !listen_addr.ip().is_unspecified() {
+        return Some(format!("http://{}", SocketAddr::new(listen_addr.ip(), port)));
+    }
+
+    let client_ip = match client_ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(client_ip),
+        ip => ip,
+    };
+    let local_ip = match client_ip {
+        IpAddr::V4(v4) if v4.is_loopback() => v4,
+        IpAddr::V4(v4) => crate::broadlink::get_local_ip_for_device(v4)?,
+        IpAddr::V6(_) => crate::broadlink::get_local_ipv4_addresses().into_iter().next()?,
+    };
+    Some(format!("http://{local_ip}:{port}"))
+}
+
+/// Rejects non-LAN peers with 403 while `AppState::lan_only` is set.
+pub async fn lan_only_middleware(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if state.lan_only.load(Ordering::Relaxed) && !is_lan_address(peer.ip()) {
+        tracing::warn!("lan_only_middleware: rejected request from {}", peer.ip());
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(req).await)
+}
@@ -1,5 +1,7 @@
 pub mod auth;
 pub mod caption;
+pub mod extract;
+pub mod lan;
 pub mod openapi;
 pub mod ppt;
 pub mod presenter;
@@ -7,6 +9,7 @@ pub mod routes;
 pub mod websocket;
 
 use axum::{
+    extract::{ConnectInfo, State},
     middleware,
     routing::{delete, get, post, put},
     Router,
@@ -42,11 +45,337 @@ pub(crate) const OAUTH_CALLBACK_PORT: u16 = 8766;
 /// Exact redirect URI registered in both Google and Facebook Cloud Consoles.
 pub(crate) const OAUTH_REDIRECT_URI: &str = "http://127.0.0.1:8766/callback";
 
+static SHUTDOWN: std::sync::LazyLock<tokio::sync::watch::Sender<bool>> =
+    std::sync::LazyLock::new(|| tokio::sync::watch::channel(false).0);
+
+/// Ask a running `build_and_serve` to stop: WebSocket clients get a 1001
+/// close, then the listener drains in-flight requests and returns.
+pub fn request_shutdown() {
+    SHUTDOWN.send_replace(true);
+}
+
+/// Request a shutdown and wait up to `timeout` for the server to finish
+/// draining, e.g. before the app exits.
+pub async fn shutdown(timeout: std::time::Duration) {
+    request_shutdown();
+    let drained = tokio::time::timeout(timeout, async {
+        while is_running() {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    if drained.is_err() {
+        tracing::warn!("Server did not stop within {timeout:?}; exiting anyway");
+    }
+}
+
+struct RebindRequest {
+    addr: SocketAddr,
+    reply: tokio::sync::oneshot::Sender<Result<(), String>>,
+}
+
+type RebindSender = mpsc::UnboundedSender<RebindRequest>;
+
+static REBIND: std::sync::LazyLock<std::sync::Mutex<Option<RebindSender>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(None));
+
+/// Move the running server to `addr` without restarting the database or
+/// connectors. The new listener is bound before the old one stops accepting,
+/// so on failure the server keeps running on its current address.
+pub async fn rebind(addr: SocketAddr) -> Result<(), String> {
+    let tx = REBIND
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .ok_or("Server is not running")?;
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    tx.send(RebindRequest { addr, reply })
+        .map_err(|_| "Server is not running".to_string())?;
+    rx.await
+        .map_err(|_| "Server stopped before it could rebind".to_string())?
+}
+
+/// The running server's effective settings, consolidated for support — e.g.
+/// diagnosing why a phone can't connect without reading the logs.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerConfig {
+    /// Address the listener is bound to right now (reflects rebinds).
+    pub bind_address: String,
+    pub port: u16,
+    /// `/api` routes need the auth token or an API key, except those listed
+    /// in `open_routes`.
+    pub auth_required: bool,
+    /// `/ws` also accepts connections without a token. They receive every
+    /// broadcast but are refused commands that change anything.
+    pub ws_allows_anonymous_readonly: bool,
+    /// Routes served to anyone who can reach the server (subject to `lan_only`).
+    pub open_routes: Vec<String>,
+    pub api_key_count: usize,
+    /// The server only speaks plain HTTP; TLS is left to a reverse proxy.
+    pub tls: bool,
+    pub lan_only: bool,
+    pub cors_origins: Vec<String>,
+    /// Requests per minute per client; `None` means unlimited.
+    pub rate_limit_per_minute: Option<u32>,
+    pub idle_timeout_secs: Option<u64>,
+    /// The server isn't advertised over mDNS; clients pair via URL/QR code.
+    pub mdns: bool,
+    pub ws_clients: usize,
+}
+
+/// Routes mounted outside `auth::auth_middleware` in `build_and_serve`.
+pub(crate) const OPEN_ROUTES: &[&str] = &[
+    "/health",
+    "/caption",
+    "/caption/logo",
+    "/caption/scripture",
+    "/openapi.json",
+    "/collection.json",
+    "/docs",
+    "/ws-schema",
+    "/api/pair",
+    "/api/auth/youtube/url",
+    "/api/auth/youtube/logout",
+    "/api/auth/facebook/url",
+    "/api/auth/facebook/logout",
+];
+
+struct RunningServer {
+    state: AppState,
+    idle_timeout: Option<std::time::Duration>,
+}
+
+static RUNNING: std::sync::LazyLock<std::sync::Mutex<Option<RunningServer>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(None));
+
+/// Whether `build_and_serve` is currently serving.
+pub fn is_running() -> bool {
+    RUNNING.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Effective configuration of the running server.
+pub async fn effective_config() -> Result<ServerConfig, String> {
+    let (state, idle_timeout) = {
+        let running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        let running = running.as_ref().ok_or("Server is not running")?;
+        (running.state.clone(), running.idle_timeout)
+    };
+    let addr = *state.listen_addr.read().unwrap_or_else(|e| e.into_inner());
+    Ok(ServerConfig {
+        bind_address: addr.ip().to_string(),
+        port: addr.port(),
+        auth_required: true,
+        ws_allows_anonymous_readonly: true,
+        open_routes: OPEN_ROUTES.iter().map(|r| r.to_string()).collect(),
+        api_key_count: state.api_keys.read().await.len(),
+        tls: false,
+        lan_only: state.lan_only.load(std::sync::atomic::Ordering::Relaxed),
+        // Matches the `allow_origin(Any)` CorsLayer in `build_and_serve`.
+        cors_origins: vec!["*".to_string()],
+        rate_limit_per_minute: None,
+        idle_timeout_secs: idle_timeout.map(|t| t.as_secs()),
+        mdns: false,
+        ws_clients: state.ws_clients.read().await.len(),
+    })
+}
+
+/// Tauri event emitted whenever the listener comes up (initially and after a
+/// rebind); payload `{ address, port }`.
+pub const SERVER_STARTED_EVENT: &str = "server://started";
+
+/// Tauri event emitted when the server stops on its own; payload `{ reason }`.
+pub const SERVER_STOPPED_EVENT: &str = "server://stopped";
+
+/// Tauri event emitted when the LAN address clients should use changes (e.g.
+/// switching from WiFi to Ethernet); payload `{ address, addresses, port }`.
+pub const SERVER_ADDRESS_CHANGED_EVENT: &str = "server://address-changed";
+
+/// Poll the machine's addresses and emit [`SERVER_ADDRESS_CHANGED_EVENT`]
+/// when they change, so pairing info and QR codes don't keep showing a stale
+/// IP. Only relevant when listening on all interfaces.
+async fn address_watcher(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut last: Option<(Option<String>, Vec<String>)> = None;
+    loop {
+        interval.tick().await;
+        let listen_addr = *state.listen_addr.read().unwrap_or_else(|e| e.into_inner());
+        if !listen_addr.ip().is_unspecified() {
+            continue;
+        }
+        let current = tokio::task::spawn_blocking(|| {
+            let addresses: Vec<String> = crate::broadlink::get_local_ipv4_addresses()
+                .into_iter()
+                .map(|ip| ip.to_string())
+                .collect();
+            (crate::commands::server::get_local_ip(), addresses)
+        })
+        .await
+        .unwrap_or_default();
+
+        if last.as_ref().is_some_and(|prev| *prev != current) {
+            tracing::info!(
+                "Advertised address changed to {:?} (interfaces: {:?})",
+                current.0,
+                current.1
+            );
+            if let Some(app) = &state.app_handle {
+                use tauri::Emitter;
+                let _ = app.emit(
+                    SERVER_ADDRESS_CHANGED_EVENT,
+                    json!({
+                        "address": current.0,
+                        "addresses": current.1,
+                        "port": listen_addr.port(),
+                    }),
+                );
+            }
+        }
+        last = Some(current);
+    }
+}
+
+/// Record that the server is in use; read by the idle auto-stop watcher.
+/// Requests from this machine (the desktop UI) don't count.
+async fn track_activity(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    req: axum::extract::Request,
+    next: middleware::Next,
+) -> axum::response::Response {
+    if !peer.ip().is_loopback() {
+        *state.last_activity.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+    next.run(req).await
+}
+
+/// Stop the server once it has seen no HTTP requests and had no WebSocket
+/// clients from other machines for `timeout`.
+async fn idle_watcher(state: AppState, timeout: std::time::Duration) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(15));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        if state.ws_client_info.read().await.values().any(|c| !c.loopback) {
+            *state.last_activity.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+            continue;
+        }
+        let idle = state.last_activity.lock().unwrap_or_else(|e| e.into_inner()).elapsed();
+        if idle < timeout {
+            continue;
+        }
+        tracing::info!("No activity for {}s — stopping server", idle.as_secs());
+        if let Some(app) = &state.app_handle {
+            use tauri::Emitter;
+            let _ = app.emit(SERVER_STOPPED_EVENT, json!({ "reason": "idle_timeout" }));
+        }
+        request_shutdown();
+        return;
+    }
+}
+
+async fn shutdown_signal(state: AppState) {
+    let _ = SHUTDOWN.subscribe().wait_for(|stop| *stop).await;
+    tracing::info!("Shutting down Axum server");
+    websocket::close_all_for_shutdown(&state).await;
+}
+
+/// Serve `app` on `addr`, moving to a new listener whenever a rebind request
+/// arrives. Returns once a shutdown is requested or the listener fails.
+async fn serve_with_rebind(
+    app: Router,
+    state: AppState,
+    mut addr: SocketAddr,
+    mut rebind_rx: mpsc::UnboundedReceiver<RebindRequest>,
+) -> std::io::Result<()> {
+    let mut listener = TcpListener::bind(addr).await?;
+    loop {
+        tracing::info!("Axum server listening on {addr}");
+        *state.listen_addr.write().unwrap_or_else(|e| e.into_inner()) = addr;
+        if let Some(handle) = &state.app_handle {
+            use tauri::Emitter;
+            let _ = handle.emit(
+                SERVER_STARTED_EVENT,
+                json!({ "address": addr.ip().to_string(), "port": addr.port() }),
+            );
+        }
+
+        let (handover_tx, handover_rx) = tokio::sync::oneshot::channel::<()>();
+        let st = state.clone();
+        let serve = axum::serve(
+            listener,
+            app.clone().into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            tokio::select! {
+                _ = shutdown_signal(st) => {}
+                _ = handover_rx => {}
+            }
+        });
+        let mut server = tokio::spawn(async move { serve.await });
+
+        loop {
+            let req = tokio::select! {
+                res = &mut server => return res.unwrap_or_else(|e| Err(std::io::Error::other(e))),
+                Some(req) = rebind_rx.recv() => req,
+            };
+            if req.addr == addr {
+                let _ = req.reply.send(Ok(()));
+                continue;
+            }
+            match TcpListener::bind(req.addr).await {
+                Ok(next) => {
+                    // The old server drains in the background while the new
+                    // one is already accepting.
+                    let _ = req.reply.send(Ok(()));
+                    websocket::close_all_for_restart(&state, req.addr).await;
+                    let _ = handover_tx.send(());
+                    listener = next;
+                    addr = req.addr;
+                    break;
+                }
+                // Same port on an overlapping interface (e.g. 0.0.0.0 → 127.0.0.1):
+                // the old socket has to be released first, so a short gap is unavoidable.
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::AddrInUse
+                        && req.addr.port() == addr.port() =>
+                {
+                    websocket::close_all_for_restart(&state, req.addr).await;
+                    let _ = handover_tx.send(());
+                    let _ = server.await;
+                    match TcpListener::bind(req.addr).await {
+                        Ok(next) => {
+                            let _ = req.reply.send(Ok(()));
+                            listener = next;
+                            addr = req.addr;
+                        }
+                        Err(e) => {
+                            let _ = req
+                                .reply
+                                .send(Err(format!("Could not bind {}: {e}", req.addr)));
+                            listener = TcpListener::bind(addr).await?;
+                        }
+                    }
+                    break;
+                }
+                Err(e) => {
+                    let _ = req
+                        .reply
+                        .send(Err(format!("Could not bind {}: {e}", req.addr)));
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
     pub auth_token: Arc<RwLock<String>>,
-    pub ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    /// Named API keys accepted in addition to `auth_token`; shared with AppRuntime.
+    pub api_keys: Arc<RwLock<Vec<auth::ApiKey>>>,
+    pub ws_clients: Arc<RwLock<HashMap<Uuid, websocket::WsClientSender>>>,
     pub server_id: String,
     pub obs_connector: Arc<ObsConnector>,
     pub vmix_connector: Arc<VmixConnector>,
@@ -67,8 +396,16 @@ pub struct AppState {
     pub presenter_state: Arc<tokio::sync::RwLock<presenter::PresenterState>>,
     /// Whether to use the web presenter instead of Keynote; persisted in app_settings.
     pub use_web_presenter: Arc<AtomicBool>,
+    /// Subscriber count and interval for the `presentation.status` change poller.
+    pub presentation_watch: Arc<websocket::PresentationWatch>,
     /// Metadata for every currently-connected WebSocket client.
     pub ws_client_info: Arc<tokio::sync::RwLock<HashMap<Uuid, websocket::WsClientInfo>>>,
+    /// Address the main HTTP/WS listener is bound to; updated on rebind.
+    pub listen_addr: Arc<std::sync::RwLock<SocketAddr>>,
+    /// Reject requests from non-LAN peers (see `lan::lan_only_middleware`).
+    pub lan_only: Arc<AtomicBool>,
+    /// Time of the last HTTP request, for the idle auto-stop.
+    pub last_activity: Arc<std::sync::Mutex<Instant>>,
     #[cfg(target_os = "macos")]
     pub keynote_connector: Arc<KeynoteConnector>,
 }
@@ -76,7 +413,9 @@ pub struct AppState {
 pub async fn build_and_serve(
     pool: PgPool,
     auth_token: Arc<RwLock<String>>,
+    api_keys: Arc<RwLock<Vec<auth::ApiKey>>>,
     connection_url: String,
+    bind_address: std::net::IpAddr,
     port: u16,
     static_dir: Option<String>,
     obs_connector: Arc<ObsConnector>,
@@ -87,11 +426,13 @@ pub async fn build_and_serve(
     youtube_config: Arc<RwLock<YouTubeConfig>>,
     facebook_config: Arc<RwLock<FacebookConfig>>,
     oauth_states: Arc<RwLock<std::collections::HashMap<String, (String, std::time::Instant)>>>,
+    lan_only: Arc<AtomicBool>,
+    idle_timeout: Option<std::time::Duration>,
     app_handle: Option<tauri::AppHandle>,
     cron_scheduler: Arc<CronScheduler>,
     #[cfg(target_os = "macos")] keynote_connector: Arc<KeynoteConnector>,
 ) -> anyhow::Result<()> {
-    let ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>> =
+    let ws_clients: Arc<RwLock<HashMap<Uuid, websocket::WsClientSender>>> =
         Arc::new(RwLock::new(HashMap::new()));
     let server_id = Uuid::new_v4().to_string();
 
@@ -103,6 +444,7 @@ pub async fn build_and_serve(
         Arc::clone(&obs_connector),
         Arc::clone(&facebook_config),
         Arc::clone(&ws_clients),
+        app_handle.clone(),
     ));
 
     // Initial scheduler load — runs with the real ws_clients so broadcasts reach clients.
@@ -133,12 +475,22 @@ pub async fn build_and_serve(
     .unwrap_or(false);
     let use_web_presenter = Arc::new(AtomicBool::new(use_web_presenter_val));
 
+    let presentation_poll_ms: u64 = sqlx::query_scalar(
+        "SELECT value FROM app_settings WHERE key = 'presentation_poll_interval_ms'",
+    )
+    .fetch_optional(&pool)
+    .await
+    .unwrap_or(None)
+    .and_then(|v: String| v.parse().ok())
+    .unwrap_or(websocket::DEFAULT_PRESENTATION_POLL_MS);
+
     let ws_client_info: Arc<tokio::sync::RwLock<HashMap<Uuid, websocket::WsClientInfo>>> =
         Arc::new(tokio::sync::RwLock::new(HashMap::new()));
 
     let state = AppState {
         pool,
         auth_token,
+        api_keys,
         ws_clients: ws_clients.clone(),
         server_id,
         obs_connector: obs_connector.clone(),
@@ -156,25 +508,37 @@ pub async fn build_and_serve(
         obs_available_devices: obs_available_devices.clone(),
         presenter_state: presenter_state.clone(),
         use_web_presenter: use_web_presenter.clone(),
+        presentation_watch: Arc::new(websocket::PresentationWatch::new(presentation_poll_ms)),
         ws_client_info: ws_client_info.clone(),
+        listen_addr: Arc::new(std::sync::RwLock::new(SocketAddr::new(bind_address, port))),
+        lan_only,
+        last_activity: Arc::new(std::sync::Mutex::new(Instant::now())),
         #[cfg(target_os = "macos")]
         keynote_connector: keynote_connector.clone(),
     };
 
+    // A previous run may have left the shutdown flag set.
+    SHUTDOWN.send_replace(false);
+    // Forwarders and listeners that must not outlive the server, so a later
+    // start can rebind the OAuth port and doesn't double-forward.
+    let mut background: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+    let idle_watcher = idle_timeout.map(|t| tokio::spawn(idle_watcher(state.clone(), t)));
+    let address_watcher = tokio::spawn(address_watcher(state.clone()));
+
     {
         let clients = ws_clients.clone();
         let url = connection_url.clone();
         let st = state.clone();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             websocket::start_notify_listener(url, clients, st).await;
-        });
+        }));
     }
 
     // Forward OBS status broadcasts to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut obs_rx = obs_connector.status_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(status) = obs_rx.recv().await {
                 let msg = json!({
                     "type": "connector.status",
@@ -187,14 +551,14 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward OBS streaming/recording state changes to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut obs_state_rx = obs_connector.output_state_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(state) = obs_state_rx.recv().await {
                 let msg = json!({
                     "type": "connector.state",
@@ -208,14 +572,14 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward YouTube status broadcasts to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut yt_rx = youtube_connector.status_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(status) = yt_rx.recv().await {
                 let msg = json!({
                     "type": "connector.status",
@@ -228,14 +592,14 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward Facebook status broadcasts to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut fb_rx = facebook_connector.status_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(status) = fb_rx.recv().await {
                 let msg = json!({
                     "type": "connector.status",
@@ -248,14 +612,14 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward Broadlink status broadcasts to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut bl_rx = broadlink_connector.status_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(status) = bl_rx.recv().await {
                 let msg = json!({
                     "type": "connector.status",
@@ -268,19 +632,20 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward Broadlink learn results to all connected WS clients.
     {
         let clients = ws_clients.clone();
         let mut learn_rx = broadlink_connector.learn_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(ev) = learn_rx.recv().await {
                 let msg = json!({
                     "type": "broadlink.learn.result",
                     "code": ev.code,
                     "error": ev.error,
+                    "quality": ev.quality,
                 })
                 .to_string();
                 let guard = clients.read().await;
@@ -288,7 +653,7 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Forward Keynote status broadcasts to all connected WS clients (macOS only).
@@ -296,7 +661,7 @@ pub async fn build_and_serve(
     {
         let clients = ws_clients.clone();
         let mut kn_rx = keynote_connector.status_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(status) = kn_rx.recv().await {
                 let msg = json!({
                     "type": "keynote.status",
@@ -308,7 +673,7 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     // Start Keynote adaptive polling loop (macOS only).
@@ -319,7 +684,7 @@ pub async fn build_and_serve(
     {
         let clients = ws_clients.clone();
         let mut obs_state_rx = obs_connector.state_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             while let Ok(ev) = obs_state_rx.recv().await {
                 let msg = json!({
                     "type": "obs.state",
@@ -332,17 +697,85 @@ pub async fn build_and_serve(
                     let _ = tx.send(Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
+    // Forward live caption changes to all connected WS clients.
+    {
+        let clients = ws_clients.clone();
+        let mut caption_rx = caption::LIVE_CAPTION_TX.subscribe();
+        background.push(tokio::spawn(async move {
+            loop {
+                let caption = match caption_rx.recv().await {
+                    Ok(caption) => caption,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                let msg = json!({ "type": "caption.changed", "caption": caption }).to_string();
+                let guard = clients.read().await;
+                for tx in guard.values() {
+                    let _ = tx.send(Message::Text(msg.clone().into()));
+                }
+            }
+        }));
+    }
+
+    // Forward OBS program scene changes to all connected WS clients.
+    {
+        let clients = ws_clients.clone();
+        let mut scene_rx = obs_connector.scene_tx.subscribe();
+        background.push(tokio::spawn(async move {
+            loop {
+                let scene_name = match scene_rx.recv().await {
+                    Ok(name) => name,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                let msg = json!({ "type": "obs.scene", "sceneName": scene_name }).to_string();
+                let guard = clients.read().await;
+                for tx in guard.values() {
+                    let _ = tx.send(Message::Text(msg.clone().into()));
+                }
+            }
+        }));
+    }
+
+    // Poll OBS output status (with timecodes) and broadcast `obs.status` when it changes.
+    // Idle while OBS is disconnected; aborted when the server stops.
+    let obs_status_poller = {
+        let clients = ws_clients.clone();
+        let obs_c = obs_connector.clone();
+        tokio::spawn(async move {
+            let mut last: Option<crate::connectors::obs::ObsOutputStatus> = None;
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let Ok(status) = obs_c.output_status().await else {
+                    last = None;
+                    continue;
+                };
+                if last.as_ref() == Some(&status) {
+                    continue;
+                }
+                let msg = json!({ "type": "obs.status", "status": &status }).to_string();
+                last = Some(status);
+                let guard = clients.read().await;
+                for tx in guard.values() {
+                    let _ = tx.send(Message::Text(msg.clone().into()));
+                }
+            }
+        })
+    };
+
     // Detect OBS recordings and auto-assign to the current event.
     {
         let pool_c = state.pool.clone();
         let clients_c = ws_clients.clone();
         let mut recording_rx = obs_connector.recording_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             handle_obs_recording_events(pool_c, clients_c, &mut recording_rx).await;
-        });
+        }));
     }
 
     // Rescan OBS devices whenever devices_tx fires (input added/removed/changed or initial connect).
@@ -352,7 +785,7 @@ pub async fn build_and_serve(
         let devices_cache = obs_available_devices.clone();
         let clients_c = ws_clients.clone();
         let mut devices_rx = obs_connector.devices_tx.subscribe();
-        tokio::spawn(async move {
+        background.push(tokio::spawn(async move {
             loop {
                 match devices_rx.recv().await {
                     Ok(()) => {}
@@ -380,7 +813,7 @@ pub async fn build_and_serve(
                     let _ = tx.send(axum::extract::ws::Message::Text(msg.clone().into()));
                 }
             }
-        });
+        }));
     }
 
     let cors = CorsLayer::new()
@@ -395,14 +828,23 @@ pub async fn build_and_serve(
         .route("/auth/facebook/url", get(routes::facebook_auth_url))
         .route("/auth/facebook/logout", post(routes::facebook_logout));
 
+    // Pairing bootstrap — no auth, but the handler only answers LAN peers.
+    let pairing_routes = Router::new().route("/pair", get(routes::pair_info));
+
     // PPT folder and file search routes (all platforms).
     let ppt_routes = Router::new()
         .route(
             "/ppt/folders",
             get(ppt::list_folders).post(ppt::add_folder),
         )
+        .route("/ppt/folders/validate", get(ppt::validate_folders))
         .route("/ppt/folders/{id}", delete(ppt::remove_folder))
-        .route("/ppt/files", get(ppt::search_files));
+        .route("/ppt/files", get(ppt::search_files))
+        .route("/presentation/first", post(ppt::presentation_first))
+        .route("/presentation/last", post(ppt::presentation_last))
+        .route("/presentation/toggle_mute", post(ppt::presentation_toggle_mute))
+        .route("/presentation/notes", get(ppt::presentation_notes))
+        .route("/presentation/screenshot", get(ppt::presentation_screenshot));
 
     // Keynote control routes (macOS only; 501 stub on other platforms).
     #[cfg(target_os = "macos")]
@@ -414,6 +856,7 @@ pub async fn build_and_serve(
         .route("/keynote/first", post(ppt::keynote_first))
         .route("/keynote/last", post(ppt::keynote_last))
         .route("/keynote/goto", post(ppt::keynote_goto))
+        .route("/keynote/jump", post(ppt::keynote_jump))
         .route("/keynote/start", post(ppt::keynote_start))
         .route("/keynote/stop", post(ppt::keynote_stop))
         .route("/keynote/close_all", post(ppt::keynote_close_all));
@@ -427,11 +870,13 @@ pub async fn build_and_serve(
         .route("/keynote/first", post(ppt::keynote_not_implemented))
         .route("/keynote/last", post(ppt::keynote_not_implemented))
         .route("/keynote/goto", post(ppt::keynote_not_implemented))
+        .route("/keynote/jump", post(ppt::keynote_not_implemented))
         .route("/keynote/start", post(ppt::keynote_not_implemented))
         .route("/keynote/stop", post(ppt::keynote_not_implemented))
         .route("/keynote/close_all", post(ppt::keynote_not_implemented));
 
     let api_routes = Router::new()
+        .route("/v1/events", get(websocket::sse_handler))
         .route(
             "/events",
             get(routes::list_events).post(routes::create_event),
@@ -457,6 +902,7 @@ pub async fn build_and_serve(
             delete(routes::delete_event_activity),
         )
         .route("/recordings", get(routes::list_all_recordings))
+        .route("/recordings/rename", post(routes::rename_recording))
         .route(
             "/recordings/untracked",
             get(routes::list_untracked_recordings),
@@ -478,6 +924,10 @@ pub async fn build_and_serve(
             "/connectors/broadlink/devices/{id}",
             delete(routes::broadlink_remove_device),
         )
+        .route(
+            "/connectors/broadlink/devices/{id}/probe-port",
+            post(routes::broadlink_probe_device_port),
+        )
         .route(
             "/connectors/broadlink/discover",
             post(routes::broadlink_discover),
@@ -486,6 +936,10 @@ pub async fn build_and_serve(
             "/connectors/broadlink/commands",
             get(routes::broadlink_list_commands).post(routes::broadlink_add_command),
         )
+        .route(
+            "/connectors/broadlink/commands/validate",
+            get(routes::broadlink_validate_commands),
+        )
         .route(
             "/connectors/broadlink/commands/{id}",
             put(routes::broadlink_update_command).delete(routes::broadlink_remove_command),
@@ -502,9 +956,40 @@ pub async fn build_and_serve(
             "/connectors/broadlink/commands/{id}/send",
             post(routes::broadlink_send_command),
         )
+        .route(
+            "/connectors/broadlink/sequences",
+            get(routes::broadlink_list_sequences).put(routes::broadlink_save_sequences),
+        )
+        .route(
+            "/connectors/broadlink/sequences/{id}/execute",
+            post(routes::broadlink_execute_sequence),
+        )
+        .route(
+            "/connectors/broadlink/pack",
+            get(routes::broadlink_export_pack),
+        )
+        .route(
+            "/connectors/broadlink/report",
+            get(routes::broadlink_rfir_report),
+        )
+        .route(
+            "/connectors/broadlink/pack/import",
+            post(routes::broadlink_import_pack),
+        )
         .route("/connectors/state", get(routes::get_connector_state))
+        .route("/connectors/obs/status", get(routes::obs_output_status))
+        .route("/connectors/obs/stream/start", post(routes::obs_stream_start))
+        .route("/connectors/obs/stream/stop", post(routes::obs_stream_stop))
+        .route("/connectors/obs/record/start", post(routes::obs_record_start))
+        .route("/connectors/obs/record/stop", post(routes::obs_record_stop))
+        .route("/connectors/obs/scenes", get(routes::obs_list_scenes))
+        .route("/connectors/obs/scene", post(routes::obs_set_scene))
+        .route("/connectors/obs/source/toggle", post(routes::obs_toggle_source))
+        .route("/connectors/obs/text", post(routes::obs_set_text))
         .route("/connectors/status", get(routes::get_connector_statuses))
+        .route("/dashboard", get(routes::get_dashboard))
         .route("/connectors/youtube/content", get(routes::get_youtube_content))
+        .route("/connectors/youtube/uploads", get(routes::get_youtube_recent_uploads))
         .route("/connectors/youtube/stream-key", get(routes::get_youtube_stream_key))
         .route("/connectors/facebook/stream-key", get(routes::get_facebook_stream_key))
         .route(
@@ -528,6 +1013,14 @@ pub async fn build_and_serve(
             post(routes::flag_upload),
         )
         .route("/uploads/trigger", post(routes::trigger_upload_cycle))
+        .route("/uploads/cancel-all", post(routes::cancel_all_uploads))
+        .route("/uploads/check", post(routes::check_already_uploaded))
+        .route("/caption/current", get(caption::current_caption_handler))
+        .route("/caption/set", post(caption::set_caption_handler))
+        .route(
+            "/uploads/defaults",
+            get(routes::get_upload_defaults).put(routes::update_upload_defaults),
+        )
         .merge(ppt_routes)
         .merge(keynote_routes)
         .route("/presenter/parse", post(presenter::parse_presentation))
@@ -535,28 +1028,42 @@ pub async fn build_and_serve(
             state.clone(),
             auth::auth_middleware,
         ))
-        .merge(oauth_routes);
+        // Unauthenticated from here on; keep OPEN_ROUTES in step.
+        .merge(oauth_routes)
+        .merge(pairing_routes);
 
     // CorsLayer must be the outermost layer so it intercepts OPTIONS preflight
     // requests before they reach the auth middleware. tower-http's CorsLayer
     // only adds response headers and never modifies request headers, so it is
     // safe to apply to all routes including /ws.
     let mut app = Router::new()
-        .route("/health", get(|| async { axum::http::StatusCode::OK }))
         .route("/caption", get(caption::caption_handler))
         .route("/caption/logo", get(caption::caption_logo_handler))
+        .route("/caption/scripture", get(caption::scripture_handler))
         .route("/openapi.json", get(openapi::serve_spec))
+        .route("/collection.json", get(openapi::serve_collection))
         .route("/docs", get(openapi::serve_docs))
+        .route("/ws-schema", get(openapi::serve_ws_schema))
         .route("/ws", get(websocket::ws_handler))
-        .nest("/api", api_routes)
-        .with_state(state.clone());
+        .nest("/api", api_routes);
 
     if let Some(dir) = static_dir {
         let fallback = ServeFile::new(format!("{dir}/index.html"));
         app = app.fallback_service(ServeDir::new(&dir).fallback(fallback));
     }
 
-    let app = app.layer(cors);
+    // The LAN-only filter and activity tracking wrap everything except
+    // /health, which stays open so external uptime checks keep working and
+    // don't count as use.
+    let app = app
+        .layer(middleware::from_fn_with_state(state.clone(), track_activity))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            lan::lan_only_middleware,
+        ))
+        .route("/health", get(|| async { axum::http::StatusCode::OK }))
+        .with_state(state.clone())
+        .layer(cors);
 
     // Dedicated OAuth callback listener on the fixed port 8766.
     // This keeps the redirect URI stable (matching the Cloud Console config)
@@ -568,25 +1075,38 @@ pub async fn build_and_serve(
                 .route("/callback", get(routes::oauth_callback))
                 .with_state(state.clone());
             tracing::info!("OAuth callback server listening on {callback_addr}");
-            tokio::spawn(async move {
+            background.push(tokio::spawn(async move {
                 let _ = axum::serve(cb_listener, cb_app).await;
-            });
+            }));
         }
         Err(e) => {
             tracing::warn!("Could not bind OAuth callback port {OAUTH_CALLBACK_PORT}: {e}");
         }
     }
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    let listener = TcpListener::bind(addr).await?;
-    tracing::info!("Axum server listening on {addr}");
-    axum::serve(listener, app).await?;
+    let (rebind_tx, rebind_rx) = mpsc::unbounded_channel();
+    *REBIND.lock().unwrap_or_else(|e| e.into_inner()) = Some(rebind_tx);
+    *RUNNING.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some(RunningServer { state: state.clone(), idle_timeout });
+    let result =
+        serve_with_rebind(app, state.clone(), SocketAddr::new(bind_address, port), rebind_rx).await;
+    REBIND.lock().unwrap_or_else(|e| e.into_inner()).take();
+    RUNNING.lock().unwrap_or_else(|e| e.into_inner()).take();
+    obs_status_poller.abort();
+    address_watcher.abort();
+    for task in background {
+        task.abort();
+    }
+    if let Some(w) = idle_watcher {
+        w.abort();
+    }
+    result?;
 
     Ok(())
 }
 
 /// Probe the video file duration via `ffprobe`. Returns 0.0 if unavailable.
-async fn probe_duration(path: &std::path::Path) -> f64 {
+pub(crate) async fn probe_duration(path: &std::path::Path) -> f64 {
     let out = tokio::process::Command::new("ffprobe")
         .args(["-v", "quiet", "-of", "json", "-show_entries", "format=duration", "-i"])
         .arg(path)
@@ -608,7 +1128,7 @@ async fn probe_duration(path: &std::path::Path) -> f64 {
 /// (if a current event exists) or `untracked_recordings`.
 async fn handle_obs_recording_events(
     pool: PgPool,
-    clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: Arc<RwLock<HashMap<Uuid, websocket::WsClientSender>>>,
     recording_rx: &mut tokio::sync::broadcast::Receiver<crate::connectors::obs::ObsRecordingEvent>,
 ) {
     loop {
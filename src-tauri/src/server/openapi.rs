@@ -1,7 +1,10 @@
+use axum::extract::{ConnectInfo, State};
 use axum::response::{Html, IntoResponse};
 use axum::Json;
 use serde_json::{json, Value};
 
+use crate::server::{websocket, AppState};
+
 const DOCS_HTML: &str = r#"<!doctype html>
 <html lang="en">
   <head>
@@ -23,13 +26,272 @@ pub async fn serve_docs() -> Html<&'static str> {
     Html(DOCS_HTML)
 }
 
-pub fn spec() -> Value {
+/// Serves the JSON schema for WebSocket commands and server messages.
+pub async fn serve_ws_schema() -> impl IntoResponse {
+    Json(websocket::protocol_schema())
+}
+
+/// The OpenAPI spec as an importable Postman Collection v2.1 (Insomnia
+/// reads the same format). `baseUrl` points at the address the requesting
+/// client reached us on; `token` is left for the user to fill in.
+pub async fn serve_collection(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<std::net::SocketAddr>,
+) -> impl IntoResponse {
+    let listen_addr = *state.listen_addr.read().unwrap_or_else(|e| e.into_inner());
+    let base_url = tokio::task::spawn_blocking(move || {
+        crate::server::lan::best_url_for_client(peer.ip(), listen_addr)
+    })
+    .await
+    .ok()
+    .flatten()
+    .unwrap_or_else(|| format!("http://localhost:{}", listen_addr.port()));
+    Json(postman_collection(&spec(), &base_url))
+}
+
+const HTTP_METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// Convert an OpenAPI document into a Postman Collection v2.1, one folder per
+/// tag. WebSocket operations are skipped since Postman imports them as HTTP.
+pub fn postman_collection(spec: &Value, base_url: &str) -> Value {
+    let mut folders: Vec<(String, Vec<Value>)> = Vec::new();
+    let paths = spec["paths"].as_object().cloned().unwrap_or_default();
+    for (path, item) in &paths {
+        for method in HTTP_METHODS {
+            let Some(op) = item.get(method) else { continue };
+            let tag = op["tags"][0].as_str().unwrap_or("Other").to_string();
+            if tag == "WebSocket" {
+                continue;
+            }
+            let request = postman_request(path, method, op);
+            match folders.iter_mut().find(|(name, _)| *name == tag) {
+                Some((_, items)) => items.push(request),
+                None => folders.push((tag, vec![request])),
+            }
+        }
+    }
+
     json!({
+        "info": {
+            "name": spec["info"]["title"],
+            "description": spec["info"]["description"],
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json",
+        },
+        "auth": {
+            "type": "bearer",
+            "bearer": [{ "key": "token", "value": "{{token}}", "type": "string" }],
+        },
+        "variable": [
+            { "key": "baseUrl", "value": base_url },
+            { "key": "token", "value": "" },
+        ],
+        "item": folders
+            .into_iter()
+            .map(|(name, items)| json!({ "name": name, "item": items }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn postman_request(path: &str, method: &str, op: &Value) -> Value {
+    // OpenAPI `{id}` segments become Postman `:id` path variables.
+    let segments: Vec<String> = path
+        .trim_start_matches('/')
+        .split('/')
+        .map(|seg| match seg.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => format!(":{name}"),
+            None => seg.to_string(),
+        })
+        .collect();
+    let variables: Vec<Value> = segments
+        .iter()
+        .filter_map(|seg| seg.strip_prefix(':'))
+        .map(|name| json!({ "key": name, "value": "" }))
+        .collect();
+
+    let mut request = json!({
+        "method": method.to_uppercase(),
+        "header": [],
+        "url": {
+            "raw": format!("{{{{baseUrl}}}}/{}", segments.join("/")),
+            "host": ["{{baseUrl}}"],
+            "path": segments,
+            "variable": variables,
+        },
+    });
+    if op["security"].as_array().is_some_and(|s| s.is_empty()) {
+        request["auth"] = json!({ "type": "noauth" });
+    }
+    if let Some(example) = op["requestBody"]["content"]["application/json"].get("example") {
+        request["header"] = json!([{ "key": "Content-Type", "value": "application/json" }]);
+        request["body"] = json!({
+            "mode": "raw",
+            "raw": serde_json::to_string_pretty(example).unwrap_or_default(),
+            "options": { "raw": { "language": "json" } },
+        });
+    }
+
+    let name = op["summary"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {path}", method.to_uppercase()));
+    json!({ "name": name, "request": request })
+}
+
+/// Every route the server registers, as `(method, path, tag, summary)`.
+/// `spec()` documents the handful of routes above in full and fills in a
+/// basic operation for the rest from this table, so the spec and the
+/// Postman collection cover the whole API. Keep it in step with
+/// `build_and_serve`; the `routes_match_router` test fails otherwise.
+const ROUTES: &[(&str, &str, &str, &str)] = &[
+    ("get", "/health", "System", "Health check"),
+    ("get", "/openapi.json", "System", "OpenAPI document"),
+    ("get", "/collection.json", "System", "Postman collection"),
+    ("get", "/docs", "System", "API reference page"),
+    ("get", "/ws-schema", "WebSocket", "WebSocket protocol schema"),
+    ("get", "/ws", "WebSocket", "WebSocket live stream"),
+    ("get", "/caption", "Caption", "Caption overlay page"),
+    ("get", "/caption/logo", "Caption", "Caption logo image"),
+    ("get", "/caption/scripture", "Caption", "Scripture caption overlay page"),
+    ("get", "/api/pair", "System", "Pairing bootstrap info"),
+    ("get", "/api/auth/youtube/url", "Auth", "YouTube OAuth URL"),
+    ("post", "/api/auth/youtube/logout", "Auth", "Log out of YouTube"),
+    ("get", "/api/auth/facebook/url", "Auth", "Facebook OAuth URL"),
+    ("post", "/api/auth/facebook/logout", "Auth", "Log out of Facebook"),
+    ("get", "/api/v1/events", "System", "Server-sent event stream"),
+    ("get", "/api/events", "Events", "List events"),
+    ("post", "/api/events", "Events", "Create event"),
+    ("get", "/api/events/{id}", "Events", "Get event"),
+    ("put", "/api/events/{id}", "Events", "Update event"),
+    ("delete", "/api/events/{id}", "Events", "Delete event"),
+    ("get", "/api/events/{id}/recordings", "Recordings", "List recordings"),
+    ("post", "/api/events/{id}/recordings", "Recordings", "Add recording"),
+    ("delete", "/api/events/{id}/recordings/{recording_id}", "Recordings", "Delete recording"),
+    ("post", "/api/events/{id}/recordings/flag-upload", "Uploads", "Flag recordings for upload"),
+    ("get", "/api/events/{id}/activities", "Events", "List event activities"),
+    ("post", "/api/events/{id}/activities", "Events", "Add event activity"),
+    ("delete", "/api/events/{id}/activities/{activity_id}", "Events", "Delete event activity"),
+    ("get", "/api/recordings", "Recordings", "List all recordings"),
+    ("post", "/api/recordings/rename", "Recordings", "Rename a recording file"),
+    ("get", "/api/recordings/untracked", "Recordings", "List untracked recordings"),
+    ("post", "/api/recordings/untracked/{id}/assign", "Recordings", "Assign untracked recording to an event"),
+    ("delete", "/api/recordings/untracked/{id}", "Recordings", "Delete untracked recording"),
+    ("get", "/api/connectors/broadlink/status", "Broadlink", "Broadlink status"),
+    ("get", "/api/connectors/broadlink/devices", "Broadlink", "List devices"),
+    ("post", "/api/connectors/broadlink/devices", "Broadlink", "Add device"),
+    ("delete", "/api/connectors/broadlink/devices/{id}", "Broadlink", "Remove device"),
+    ("post", "/api/connectors/broadlink/devices/{id}/probe-port", "Broadlink", "Probe device port"),
+    ("post", "/api/connectors/broadlink/devices/{id}/learn", "Broadlink", "Start learning a code"),
+    ("post", "/api/connectors/broadlink/discover", "Broadlink", "Discover devices"),
+    ("get", "/api/connectors/broadlink/commands", "Broadlink", "List commands"),
+    ("post", "/api/connectors/broadlink/commands", "Broadlink", "Add command"),
+    ("get", "/api/connectors/broadlink/commands/validate", "Broadlink", "Validate stored commands"),
+    ("put", "/api/connectors/broadlink/commands/{id}", "Broadlink", "Update command"),
+    ("delete", "/api/connectors/broadlink/commands/{id}", "Broadlink", "Remove command"),
+    ("post", "/api/connectors/broadlink/commands/{id}/send", "Broadlink", "Send command"),
+    ("post", "/api/connectors/broadlink/learn/cancel", "Broadlink", "Cancel learning"),
+    ("get", "/api/connectors/broadlink/sequences", "Broadlink", "List sequences"),
+    ("put", "/api/connectors/broadlink/sequences", "Broadlink", "Save sequences"),
+    ("post", "/api/connectors/broadlink/sequences/{id}/execute", "Broadlink", "Execute sequence"),
+    ("get", "/api/connectors/broadlink/pack", "Broadlink", "Export command pack"),
+    ("post", "/api/connectors/broadlink/pack/import", "Broadlink", "Import command pack"),
+    ("get", "/api/connectors/broadlink/report", "Broadlink", "Command report"),
+    ("get", "/api/connectors/state", "Connectors", "Get connector state"),
+    ("get", "/api/connectors/status", "Connectors", "Get connector statuses"),
+    ("get", "/api/dashboard", "Connectors", "Get dashboard snapshot"),
+    ("get", "/api/connectors/obs/status", "OBS", "OBS output status"),
+    ("post", "/api/connectors/obs/stream/start", "OBS", "Start streaming"),
+    ("post", "/api/connectors/obs/stream/stop", "OBS", "Stop streaming"),
+    ("post", "/api/connectors/obs/record/start", "OBS", "Start recording"),
+    ("post", "/api/connectors/obs/record/stop", "OBS", "Stop recording"),
+    ("get", "/api/connectors/obs/scenes", "OBS", "List scenes"),
+    ("post", "/api/connectors/obs/scene", "OBS", "Switch scene"),
+    ("post", "/api/connectors/obs/source/toggle", "OBS", "Toggle source visibility"),
+    ("post", "/api/connectors/obs/text", "OBS", "Set text source"),
+    ("get", "/api/connectors/youtube/content", "Streaming", "YouTube content"),
+    ("get", "/api/connectors/youtube/uploads", "Streaming", "Recent YouTube uploads"),
+    ("get", "/api/connectors/youtube/stream-key", "Streaming", "YouTube stream key"),
+    ("get", "/api/connectors/facebook/stream-key", "Streaming", "Facebook stream key"),
+    ("post", "/api/connectors/youtube/schedule/{event_id}", "Streaming", "Schedule YouTube broadcast"),
+    ("post", "/api/connectors/facebook/schedule/{event_id}", "Streaming", "Schedule Facebook broadcast"),
+    ("get", "/api/cron-jobs", "Cron jobs", "List cron jobs"),
+    ("post", "/api/cron-jobs", "Cron jobs", "Create cron job"),
+    ("put", "/api/cron-jobs/{id}", "Cron jobs", "Update cron job"),
+    ("delete", "/api/cron-jobs/{id}", "Cron jobs", "Delete cron job"),
+    ("post", "/api/uploads/trigger", "Uploads", "Run an upload cycle"),
+    ("post", "/api/uploads/cancel-all", "Uploads", "Cancel all uploads"),
+    ("post", "/api/uploads/check", "Uploads", "Check for a previous upload"),
+    ("get", "/api/uploads/defaults", "Uploads", "Get upload defaults"),
+    ("put", "/api/uploads/defaults", "Uploads", "Update upload defaults"),
+    ("get", "/api/caption/current", "Caption", "Current caption"),
+    ("post", "/api/caption/set", "Caption", "Set caption"),
+    ("get", "/api/ppt/folders", "Presentation", "List presentation folders"),
+    ("post", "/api/ppt/folders", "Presentation", "Add presentation folder"),
+    ("get", "/api/ppt/folders/validate", "Presentation", "Find overlapping folders"),
+    ("delete", "/api/ppt/folders/{id}", "Presentation", "Remove presentation folder"),
+    ("get", "/api/ppt/files", "Presentation", "Search presentation files"),
+    ("post", "/api/presentation/first", "Presentation", "First slide"),
+    ("post", "/api/presentation/last", "Presentation", "Last slide"),
+    ("post", "/api/presentation/toggle_mute", "Presentation", "Toggle black screen"),
+    ("get", "/api/presentation/notes", "Presentation", "Speaker notes"),
+    ("get", "/api/presentation/screenshot", "Presentation", "Slideshow screenshot"),
+    ("get", "/api/keynote/status", "Keynote", "Keynote status"),
+    ("post", "/api/keynote/open", "Keynote", "Open presentation"),
+    ("post", "/api/keynote/next", "Keynote", "Next slide"),
+    ("post", "/api/keynote/prev", "Keynote", "Previous slide"),
+    ("post", "/api/keynote/first", "Keynote", "First slide"),
+    ("post", "/api/keynote/last", "Keynote", "Last slide"),
+    ("post", "/api/keynote/goto", "Keynote", "Go to slide"),
+    ("post", "/api/keynote/jump", "Keynote", "Jump by slides"),
+    ("post", "/api/keynote/start", "Keynote", "Start slideshow"),
+    ("post", "/api/keynote/stop", "Keynote", "Stop slideshow"),
+    ("post", "/api/keynote/close_all", "Keynote", "Close all presentations"),
+    ("post", "/api/presenter/parse", "Presenter", "Parse a .pptx file"),
+];
+
+/// Adds a basic operation for every `ROUTES` entry `spec()` doesn't document
+/// in full, plus any tags it introduces.
+fn merge_route_table(spec: &mut Value) {
+    for (method, path, tag, summary) in ROUTES {
+        let item = &mut spec["paths"][*path];
+        if item.get(*method).is_some() {
+            continue;
+        }
+        let open = crate::server::OPEN_ROUTES.contains(path);
+        let mut responses = json!({ "200": { "description": "Success" } });
+        if !open {
+            responses["401"] = json!({ "description": "Unauthorized" });
+            responses["403"] = json!({ "description": "API key lacks the required scope" });
+        }
+        let parameters: Vec<Value> = path
+            .split('/')
+            .filter_map(|seg| seg.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .map(|name| {
+                json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } })
+            })
+            .collect();
+        let mut op = json!({ "tags": [tag], "summary": summary, "responses": responses });
+        if !parameters.is_empty() && item.get("parameters").is_none() {
+            op["parameters"] = json!(parameters);
+        }
+        if open {
+            op["security"] = json!([]);
+        }
+        item[*method] = op;
+
+        let Some(tags) = spec["tags"].as_array_mut() else { continue };
+        if !tags.iter().any(|t| t["name"] == *tag) {
+            tags.push(json!({ "name": tag }));
+        }
+    }
+}
+
+pub fn spec() -> Value {
+    let mut spec = json!({
         "openapi": "3.1.0",
         "info": {
             "title": "Sermon Helper API",
             "version": "1.0.0",
-            "description": "REST API and WebSocket interface for the Sermon Helper desktop application.\n\n## Authentication\n\nAll `/api/*` endpoints require a **Bearer token** in the `Authorization` header:\n```\nAuthorization: Bearer <token>\n```\nThe token is displayed in the app's *Connection Guide* screen. It rotates on every server restart.\n\n## WebSocket — real-time push stream\n\n> **Note:** WebSocket is not an HTTP operation and cannot be tested from this page. Use a WebSocket client (e.g. [Hoppscotch](https://hoppscotch.io), [websocat](https://github.com/vi/websocat), or Bruno's socket type).\n\n**Endpoint:** `ws://<host>/ws?token=<token>`\n\nAuthentication uses the same bearer token passed as a **query parameter** (headers are not available during the WebSocket handshake).\n\n### Initial messages (sent immediately on connect)\n\n```json\n{ \"type\": \"connected\", \"serverId\": \"<uuid>\" }\n{ \"type\": \"connector.status\", \"connector\": \"obs\",  \"status\": { \"type\": \"connected\" } }\n{ \"type\": \"connector.status\", \"connector\": \"vmix\", \"status\": { \"type\": \"disconnected\" } }\n```\n\n### Push messages (broadcast on change)\n\n| `type` | Trigger | Schema |\n|---|---|---|\n| `connector.status` | OBS or VMix connection state changes | `WsConnectorStatusMessage` |\n| `event.changed` | Event created, updated, or deleted | `WsEventChangedMessage` |\n| `recording.changed` | Recording created or updated | `WsRecordingChangedMessage` |\n\n```json\n{ \"type\": \"connector.status\", \"connector\": \"obs\", \"status\": { \"type\": \"error\", \"message\": \"connection refused\" } }\n{ \"type\": \"event.changed\",     \"data\": { \"operation\": \"INSERT\", \"record\": { ...Event } } }\n{ \"type\": \"recording.changed\", \"data\": { \"operation\": \"UPDATE\", \"record\": { ...Recording } } }\n```\n\nFull payload definitions are in the `Ws*Message` schemas below."
+            "description": "REST API and WebSocket interface for the Sermon Helper desktop application.\n\n## Authentication\n\nAll `/api/*` endpoints require a **Bearer token** in the `Authorization` header:\n```\nAuthorization: Bearer <token>\n```\nThe token is displayed in the app's *Connection Guide* screen. It rotates on every server restart.\n\n### API keys and scopes\n\nNamed API keys (created in the app) are accepted in place of the token and survive token rotation. Each key has a list of scopes; a request the key isn't scoped for gets **403**. New keys get every scope unless created with a narrower list.\n\n| Scope | Grants |\n|---|---|\n| `status:read` | Every `GET` endpoint except stream keys and the Broadlink pack export |\n| `rfir:execute` | Firing Broadlink commands and sequences (`.../send`, `.../execute`) |\n| `presentation:control` | Non-`GET` requests under `/api/ppt` (except `/api/ppt/folders`), `/api/presentation`, `/api/keynote`, `/api/presenter`, `/api/caption` |\n| `obs:control` | Non-`GET` requests under `/api/connectors/obs` |\n| `admin` | Every other non-`GET` request, stream keys, the pack export and `/api/ppt/folders` changes |\n\nOver WebSocket, keys without every scope connect read-only.\n\n## WebSocket — real-time push stream\n\n> **Note:** WebSocket is not an HTTP operation and cannot be tested from this page. Use a WebSocket client (e.g. [Hoppscotch](https://hoppscotch.io), [websocat](https://github.com/vi/websocat), or Bruno's socket type).\n\n**Endpoint:** `ws://<host>/ws?token=<token>`\n\nAuthentication uses the same bearer token passed as a **query parameter** (headers are not available during the WebSocket handshake).\n\n### Initial messages (sent immediately on connect)\n\n```json\n{ \"type\": \"connected\", \"serverId\": \"<uuid>\" }\n{ \"type\": \"connector.status\", \"connector\": \"obs\",  \"status\": { \"type\": \"connected\" } }\n{ \"type\": \"connector.status\", \"connector\": \"vmix\", \"status\": { \"type\": \"disconnected\" } }\n```\n\n### Push messages (broadcast on change)\n\n| `type` | Trigger | Schema |\n|---|---|---|\n| `connector.status` | OBS or VMix connection state changes | `WsConnectorStatusMessage` |\n| `event.changed` | Event created, updated, or deleted | `WsEventChangedMessage` |\n| `recording.changed` | Recording created or updated | `WsRecordingChangedMessage` |\n\n```json\n{ \"type\": \"connector.status\", \"connector\": \"obs\", \"status\": { \"type\": \"error\", \"message\": \"connection refused\" } }\n{ \"type\": \"event.changed\",     \"data\": { \"operation\": \"INSERT\", \"record\": { ...Event } } }\n{ \"type\": \"recording.changed\", \"data\": { \"operation\": \"UPDATE\", \"record\": { ...Recording } } }\n```\n\nFull payload definitions are in the `Ws*Message` schemas below.\n\n### Commands (client → server)\n\nEvery command a client may send is described by the `WsCommand` schema, and every message the server pushes by `WsServerMessage`, both discriminated on `type`. Both are served standalone at `/ws-schema` for client code generation."
         },
         "servers": [
             {
@@ -52,7 +314,14 @@ pub fn spec() -> Value {
                 "bearerAuth": {
                     "type": "http",
                     "scheme": "bearer",
-                    "description": "Token shown in the app Connection Guide. Rotates on every server restart."
+                    "description": "Token shown in the app Connection Guide. Rotates on every server restart. Named API keys created in the app are also accepted; each carries a list of scopes (see below), and a key missing the scope an endpoint needs gets 403.",
+                    "x-scopes": {
+                        "status:read": "Every GET endpoint except /stream-key and /api/connectors/broadlink/pack",
+                        "rfir:execute": "POST /api/connectors/broadlink/commands/{id}/send and /api/connectors/broadlink/sequences/{id}/execute",
+                        "presentation:control": "Non-GET requests under /api/ppt (except /api/ppt/folders), /api/presentation, /api/keynote, /api/presenter and /api/caption",
+                        "obs:control": "Non-GET requests under /api/connectors/obs",
+                        "admin": "Every other non-GET request, the stream keys, the pack export and /api/ppt/folders changes"
+                    }
                 }
             },
             "schemas": {
@@ -558,11 +827,35 @@ pub fn spec() -> Value {
                     }
                 }
             },
+            "/api/dashboard": {
+                "get": {
+                    "tags": ["Connectors"],
+                    "summary": "Get dashboard snapshot",
+                    "description": "Connector statuses, OBS output status, presentation status and Broadlink device reachability in one response. Sections are fetched concurrently with a 3 s limit each; every section is `{ data, error }`, where `error` is `\"timeout\"` or a message when that section could not be loaded.",
+                    "operationId": "getDashboard",
+                    "responses": {
+                        "200": {
+                            "description": "Dashboard sections",
+                            "content": {
+                                "application/json": {
+                                    "example": {
+                                        "connectors": { "data": { "obs": { "type": "connected" } }, "error": null },
+                                        "obs": { "data": null, "error": "OBS is not connected" },
+                                        "presentation": { "data": { "appRunning": false, "slideshowActive": false }, "error": null },
+                                        "devices": { "data": [{ "id": "…", "name": "Projector", "host": "192.168.1.50", "reachable": true }], "error": null }
+                                    }
+                                }
+                            }
+                        },
+                        "401": { "description": "Unauthorized" }
+                    }
+                }
+            },
             "/ws": {
                 "get": {
                     "tags": ["WebSocket"],
                     "summary": "WebSocket live stream",
-                    "description": "**This endpoint performs a WebSocket upgrade — it cannot be tested with the HTTP \"Send\" button.**\n\nUse a dedicated WebSocket client instead:\n- [Hoppscotch](https://hoppscotch.io) → New request → WebSocket\n- [websocat](https://github.com/vi/websocat): `websocat 'ws://<host>/ws?token=<token>'`\n- Bruno: add a request with type `socket`\n\n---\n\n**Connection URL:** `ws://<host>/ws?token=<token>`\n\nAuthentication uses the same bearer token passed as a **query parameter** (the `Authorization` header is not available during the WebSocket handshake).\n\n### Initial messages (pushed immediately on connect)\n\n```json\n{ \"type\": \"connected\", \"serverId\": \"<uuid>\" }\n{ \"type\": \"connector.status\", \"connector\": \"obs\",  \"status\": { \"type\": \"connected\" } }\n{ \"type\": \"connector.status\", \"connector\": \"vmix\", \"status\": { \"type\": \"disconnected\" } }\n```\n\n### Broadcast messages (sent when data changes)\n\n| `type` | Trigger | Schema |\n|---|---|---|\n| `connector.status` | OBS or VMix connection state changes | `WsConnectorStatusMessage` |\n| `event.changed` | Event created, updated, or deleted | `WsEventChangedMessage` |\n| `recording.changed` | Recording created or updated | `WsRecordingChangedMessage` |\n| `presenter.state` | Presentation loaded or unloaded | `{ type, state: { loaded, filePath, currentSlide, totalSlides, slides } }` |\n| `presenter.slide_changed` | Slide navigation | `{ type, currentSlide, totalSlides }` |\n\n### Presenter WS commands\n\n| Command | Fields | Description |\n|---|---|---|\n| `presenter.load` | `file_path` | Parse .pptx and load into presenter; broadcasts `presenter.state` |\n| `presenter.unload` | — | Clear the active presentation |\n| `presenter.next` | — | Advance one slide |\n| `presenter.prev` | — | Go back one slide |\n| `presenter.first` | — | Jump to slide 1 |\n| `presenter.last` | — | Jump to last slide |\n| `presenter.goto` | `slide` | Jump to a specific slide number |\n| `presenter.status` | — | Reply to requesting client with `presenter.state` |",
+                    "description": "**This endpoint performs a WebSocket upgrade — it cannot be tested with the HTTP \"Send\" button.**\n\nUse a dedicated WebSocket client instead:\n- [Hoppscotch](https://hoppscotch.io) → New request → WebSocket\n- [websocat](https://github.com/vi/websocat): `websocat 'ws://<host>/ws?token=<token>'`\n- Bruno: add a request with type `socket`\n\n---\n\n**Connection URL:** `ws://<host>/ws?token=<token>`\n\nAuthentication uses the same bearer token passed as a **query parameter** (the `Authorization` header is not available during the WebSocket handshake).\n\n### Initial messages (pushed immediately on connect)\n\n```json\n{ \"type\": \"connected\", \"serverId\": \"<uuid>\" }\n{ \"type\": \"connector.status\", \"connector\": \"obs\",  \"status\": { \"type\": \"connected\" } }\n{ \"type\": \"connector.status\", \"connector\": \"vmix\", \"status\": { \"type\": \"disconnected\" } }\n```\n\n### Broadcast messages (sent when data changes)\n\n| `type` | Trigger | Schema |\n|---|---|---|\n| `connector.status` | OBS or VMix connection state changes | `WsConnectorStatusMessage` |\n| `event.changed` | Event created, updated, or deleted | `WsEventChangedMessage` |\n| `recording.changed` | Recording created or updated | `WsRecordingChangedMessage` |\n| `presenter.state` | Presentation loaded or unloaded | `{ type, state: { loaded, filePath, currentSlide, totalSlides, slides } }` |\n| `presenter.slide_changed` | Slide navigation | `{ type, currentSlide, totalSlides }` |\n\n### Subscriptions\n\nSend `{ \"type\": \"subscribe\", \"types\": [\"obs.status\", \"connector.*\"] }` to receive only the listed message types on this connection (a trailing `.*` matches a prefix). `connected`, `ping`, `ok` and `error` are always delivered. An empty `types` list restores the default of receiving everything.\n\n### Presenter WS commands\n\n| Command | Fields | Description |\n|---|---|---|\n| `presenter.load` | `file_path` | Parse .pptx and load into presenter; broadcasts `presenter.state` |\n| `presenter.unload` | — | Clear the active presentation |\n| `presenter.next` | — | Advance one slide |\n| `presenter.prev` | — | Go back one slide |\n| `presenter.first` | — | Jump to slide 1 |\n| `presenter.last` | — | Jump to last slide |\n| `presenter.goto` | `slide` | Jump to a specific slide number |\n| `presenter.status` | — | Reply to requesting client with `presenter.state` |",
                     "operationId": "connectWebSocket",
                     "security": [],
                     "parameters": [
@@ -595,7 +888,143 @@ pub fn spec() -> Value {
                         "401": { "description": "Unauthorized — token missing or invalid" }
                     }
                 }
+            },
+            "/ws-schema": {
+                "get": {
+                    "tags": ["WebSocket"],
+                    "summary": "WebSocket protocol schema",
+                    "description": "Standalone JSON Schema for the `/ws` protocol: every command a client may send (`WsCommand`) and every message the server pushes (`WsServerMessage`), both discriminated on `type`. Generated from the server's message enums, so it is suitable for client code generation. The same definitions are embedded in this document.",
+                    "operationId": "getWsSchema",
+                    "security": [],
+                    "responses": {
+                        "200": {
+                            "description": "JSON Schema document",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "type": "object" }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
-    })
+    });
+    merge_route_table(&mut spec);
+    merge_ws_command_schemas(&mut spec);
+    spec
+}
+
+/// Adds the derived `WsCommand` and `WsServerMessage` schemas and the nested
+/// types they reference to `components.schemas`.
+fn merge_ws_command_schemas(spec: &mut Value) {
+    let Some(schemas) = spec
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    for (name, schema) in websocket::protocol_schemas_for_openapi() {
+        schemas.insert(name, json!(schema));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `(method, path)` for every `.route(..)` in `build_and_serve`, read from
+    /// the source. Routes before the top-level router are nested under `/api`;
+    /// the OAuth callback listener is separate and skipped.
+    fn registered_routes() -> BTreeSet<(String, String)> {
+        let src = include_str!("mod.rs");
+        let start = src.find("let oauth_routes = Router::new()").unwrap();
+        let top_level = src.find("let mut app = Router::new()").unwrap();
+        let end = src.find("let cb_app = Router::new()").unwrap();
+
+        let mut routes = BTreeSet::new();
+        let mut pos = start;
+        while let Some(found) = src[pos..end].find(".route(") {
+            let call = pos + found + ".route(".len();
+            let rest = &src[call..end];
+            let open = rest.find('"').unwrap();
+            let close = open + 1 + rest[open + 1..].find('"').unwrap();
+            let path = &rest[open + 1..close];
+            let prefix = if call < top_level { "/api" } else { "" };
+
+            // The method router runs to the paren closing `.route(`.
+            let mut depth = 1;
+            let mut body_end = close;
+            for (i, c) in rest[close..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    body_end = close + i;
+                    break;
+                }
+            }
+            let body = &rest[close..body_end];
+            for method in HTTP_METHODS {
+                let needle = format!("{method}(");
+                let called = body.match_indices(&needle).any(|(i, _)| {
+                    !body[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+                });
+                if called {
+                    routes.insert((method.to_string(), format!("{prefix}{path}")));
+                }
+            }
+            pos = call + body_end;
+        }
+        routes
+    }
+
+    #[test]
+    fn routes_match_router() {
+        let table: BTreeSet<_> = ROUTES
+            .iter()
+            .map(|(method, path, _, _)| (method.to_string(), path.to_string()))
+            .collect();
+        assert_eq!(table, registered_routes());
+    }
+
+    #[test]
+    fn collection_covers_registered_routes() {
+        let collection = postman_collection(&spec(), "http://localhost:3737");
+        let covered: BTreeSet<(String, String)> = collection["item"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|folder| folder["item"].as_array().unwrap().iter())
+            .map(|item| {
+                let request = &item["request"];
+                let raw = request["url"]["raw"].as_str().unwrap();
+                (
+                    request["method"].as_str().unwrap().to_lowercase(),
+                    raw.trim_start_matches("{{baseUrl}}").to_string(),
+                )
+            })
+            .collect();
+
+        for (method, path) in registered_routes() {
+            if path == "/ws" {
+                continue;
+            }
+            let postman_path = path
+                .split('/')
+                .map(|seg| match seg.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(name) => format!(":{name}"),
+                    None => seg.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            assert!(
+                covered.contains(&(method.clone(), postman_path)),
+                "{method} {path} missing from the collection"
+            );
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::State,
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::server::extract::{ApiPath, ApiQuery};
 use crate::server::{websocket, AppState};
 
 // ── Folder management ────────────────────────────────────────────────────────
@@ -19,6 +20,8 @@ pub struct PptFolder {
     pub path: String,
     pub name: String,
     pub sort_order: i32,
+    /// Lowercase file extensions (without the dot) surfaced by search.
+    pub extensions: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,15 +29,252 @@ pub struct PptFolder {
 pub struct AddFolderBody {
     pub path: String,
     pub name: String,
+    /// Defaults to `DEFAULT_EXTENSIONS` when omitted.
+    pub extensions: Option<Vec<String>>,
 }
 
-pub async fn list_folders(State(state): State<AppState>) -> impl IntoResponse {
-    match sqlx::query_as::<_, PptFolder>(
-        "SELECT id, path, name, sort_order FROM ppt_folders ORDER BY sort_order, name",
-    )
-    .fetch_all(&state.pool)
+/// Extensions a folder scans when none are configured.
+pub const DEFAULT_EXTENSIONS: &[&str] = &["ppt", "pptx", "odp"];
+
+/// Formats Keynote or the web presenter can drive; anything else (e.g. PDF)
+/// is handed to the system default app instead.
+const PRESENTATION_EXTENSIONS: &[&str] = &["ppt", "pptx", "odp", "key"];
+
+/// Every extension a folder may surface and a network client may open.
+/// Anything else (executables, scripts) is never handed to the system opener.
+pub const ALLOWED_DOCUMENT_EXTENSIONS: &[&str] = &["ppt", "pptx", "odp", "key", "pdf"];
+
+pub(crate) const FOLDER_COLUMNS: &str = "id, path, name, sort_order, extensions";
+
+/// Lowercase, strip leading dots and drop empties, duplicates and anything
+/// outside `ALLOWED_DOCUMENT_EXTENSIONS`, keeping the first-seen order; falls
+/// back to the defaults.
+pub(crate) fn normalize_extensions(extensions: Option<Vec<String>>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let out: Vec<String> = extensions
+        .unwrap_or_default()
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .filter(|e| {
+            let allowed = ALLOWED_DOCUMENT_EXTENSIONS.contains(&e.as_str());
+            if !allowed {
+                tracing::warn!("Ignoring folder extension '{e}': not a document type");
+            }
+            allowed
+        })
+        .filter(|e| seen.insert(e.clone()))
+        .collect();
+    if out.is_empty() {
+        DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+    } else {
+        out
+    }
+}
+
+pub(crate) fn is_allowed_document(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| ALLOWED_DOCUMENT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+pub(crate) fn is_presentation_file(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| PRESENTATION_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FileAccessError {
+    #[error("File not found: {0}")]
+    NotFound(String),
+    #[error("File is not inside a configured presentation folder")]
+    OutsideFolders,
+    #[error("File type '{0}' is not enabled for this folder")]
+    ExtensionNotAllowed(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+impl FileAccessError {
+    pub(crate) fn status(&self) -> StatusCode {
+        match self {
+            FileAccessError::NotFound(_) => StatusCode::NOT_FOUND,
+            FileAccessError::OutsideFolders | FileAccessError::ExtensionNotAllowed(_) => {
+                StatusCode::FORBIDDEN
+            }
+            FileAccessError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Gate for file paths arriving over the network before they are opened or
+/// parsed: the canonicalized file must be a document type from
+/// `ALLOWED_DOCUMENT_EXTENSIONS`, sit inside a configured PPT folder and
+/// have one of that folder's extensions (or be a presentation), so a LAN
+/// client can't launch arbitrary files. Returns the canonical path.
+pub(crate) async fn resolve_allowed_file(
+    pool: &sqlx::PgPool,
+    file_path: &str,
+) -> Result<String, FileAccessError> {
+    let canonical = tokio::fs::canonicalize(file_path)
+        .await
+        .map_err(|_| FileAccessError::NotFound(file_path.to_string()))?;
+    let ext = canonical
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !ALLOWED_DOCUMENT_EXTENSIONS.contains(&ext.as_str()) {
+        tracing::warn!("Rejected open of '{file_path}': not a document type");
+        return Err(FileAccessError::ExtensionNotAllowed(ext));
+    }
+
+    let mut inside_folder = false;
+    for folder in list_folders_internal(pool).await? {
+        let Ok(root) = tokio::fs::canonicalize(&folder.path).await else { continue };
+        if !canonical.starts_with(&root) {
+            continue;
+        }
+        inside_folder = true;
+        if folder.extensions.contains(&ext) || PRESENTATION_EXTENSIONS.contains(&ext.as_str()) {
+            return Ok(canonical.to_string_lossy().into_owned());
+        }
+    }
+    if inside_folder {
+        Err(FileAccessError::ExtensionNotAllowed(ext))
+    } else {
+        tracing::warn!("Rejected open of '{file_path}': outside configured PPT folders");
+        Err(FileAccessError::OutsideFolders)
+    }
+}
+
+pub(crate) async fn list_folders_internal(pool: &sqlx::PgPool) -> Result<Vec<PptFolder>, sqlx::Error> {
+    sqlx::query_as::<_, PptFolder>(&format!(
+        "SELECT {FOLDER_COLUMNS} FROM ppt_folders ORDER BY sort_order, name"
+    ))
+    .fetch_all(pool)
     .await
-    {
+}
+
+pub(crate) async fn upsert_folder(
+    pool: &sqlx::PgPool,
+    path: &str,
+    name: &str,
+    extensions: Option<Vec<String>>,
+) -> Result<PptFolder, sqlx::Error> {
+    sqlx::query_as::<_, PptFolder>(&format!(
+        "INSERT INTO ppt_folders (path, name, extensions) VALUES ($1, $2, $3) \
+         ON CONFLICT (path) DO UPDATE SET name = EXCLUDED.name, extensions = EXCLUDED.extensions \
+         RETURNING {FOLDER_COLUMNS}"
+    ))
+    .bind(path)
+    .bind(name)
+    .bind(normalize_extensions(extensions))
+    .fetch_one(pool)
+    .await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderOverlapKind {
+    /// Both entries point at the same directory.
+    Duplicate,
+    /// `path` lies inside `other_path`, so its files are listed twice.
+    Nested,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderOverlap {
+    pub kind: FolderOverlapKind,
+    pub path: String,
+    pub other_path: String,
+}
+
+/// Resolve symlinks and `..` when the folder exists; otherwise compare the
+/// path as typed, minus trailing separators.
+fn comparable_path(path: &str) -> std::path::PathBuf {
+    let trimmed = path.trim();
+    std::fs::canonicalize(trimmed)
+        .unwrap_or_else(|_| std::path::PathBuf::from(trimmed.trim_end_matches(['/', '\\'])))
+}
+
+fn overlap(path: &str, other: &str) -> Option<FolderOverlap> {
+    let (a, b) = (comparable_path(path), comparable_path(other));
+    let (kind, path, other_path) = if a == b {
+        (FolderOverlapKind::Duplicate, path, other)
+    } else if a.starts_with(&b) {
+        (FolderOverlapKind::Nested, path, other)
+    } else if b.starts_with(&a) {
+        (FolderOverlapKind::Nested, other, path)
+    } else {
+        return None;
+    };
+    Some(FolderOverlap { kind, path: path.to_string(), other_path: other_path.to_string() })
+}
+
+/// How `path` would overlap the folders already configured.
+pub(crate) fn overlaps_with(path: &str, folders: &[PptFolder]) -> Vec<FolderOverlap> {
+    folders.iter().filter_map(|f| overlap(path, &f.path)).collect()
+}
+
+/// Every pair of configured folders that are identical or nested.
+pub(crate) fn find_folder_overlaps(folders: &[PptFolder]) -> Vec<FolderOverlap> {
+    folders
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| folders[i + 1..].iter().filter_map(|b| overlap(&a.path, &b.path)))
+        .collect()
+}
+
+/// Canonical form of a folder path sent by a client; it must be an existing
+/// directory, so `..` tricks and typos never reach the database.
+pub(crate) async fn normalize_folder_path(path: &str) -> Result<String, String> {
+    let canonical = tokio::fs::canonicalize(path.trim())
+        .await
+        .map_err(|e| format!("Folder '{path}' is not accessible: {e}"))?;
+    if !canonical.is_dir() {
+        return Err(format!("'{path}' is not a folder"));
+    }
+    // A drive or filesystem root would expose every document on the machine.
+    if canonical.parent().is_none() {
+        return Err(format!("'{path}' is a filesystem root; choose a specific folder"));
+    }
+    Ok(canonical.to_string_lossy().into_owned())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AddFolderError {
+    #[error("Folder is already added as '{0}'")]
+    Duplicate(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Add a folder unless it duplicates one already configured. Nested folders
+/// are allowed; their overlaps are returned as warnings.
+pub(crate) async fn add_folder_checked(
+    pool: &sqlx::PgPool,
+    path: &str,
+    name: &str,
+    extensions: Option<Vec<String>>,
+) -> Result<(PptFolder, Vec<FolderOverlap>), AddFolderError> {
+    let existing = list_folders_internal(pool).await?;
+    let warnings = overlaps_with(path, &existing);
+    if let Some(dup) = warnings.iter().find(|w| w.kind == FolderOverlapKind::Duplicate) {
+        return Err(AddFolderError::Duplicate(dup.other_path.clone()));
+    }
+    let folder = upsert_folder(pool, path, name, extensions).await?;
+    Ok((folder, warnings))
+}
+
+pub async fn list_folders(State(state): State<AppState>) -> impl IntoResponse {
+    match list_folders_internal(&state.pool).await {
         Ok(folders) => (
             StatusCode::OK,
             Json(json!({ "success": true, "data": folders })),
@@ -50,24 +290,42 @@ pub async fn add_folder(
     State(state): State<AppState>,
     Json(body): Json<AddFolderBody>,
 ) -> impl IntoResponse {
-    let result = sqlx::query_as::<_, PptFolder>(
-        "INSERT INTO ppt_folders (path, name) VALUES ($1, $2) \
-         ON CONFLICT (path) DO UPDATE SET name = EXCLUDED.name \
-         RETURNING id, path, name, sort_order",
-    )
-    .bind(&body.path)
-    .bind(&body.name)
-    .fetch_one(&state.pool)
-    .await;
-
-    match result {
-        Ok(folder) => {
+    let path = match normalize_folder_path(&body.path).await {
+        Ok(path) => path,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "success": false, "error": e })),
+            )
+        }
+    };
+    match add_folder_checked(&state.pool, &path, &body.name, body.extensions).await {
+        Ok((folder, warnings)) => {
             websocket::broadcast_ppt_folders_changed(&state.ws_clients).await;
             (
                 StatusCode::CREATED,
-                Json(json!({ "success": true, "data": folder })),
+                Json(json!({ "success": true, "data": folder, "warnings": warnings })),
             )
         }
+        Err(e @ AddFolderError::Duplicate(_)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "success": false, "error": e.to_string() })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "success": false, "error": e.to_string() })),
+        ),
+    }
+}
+
+/// `GET /api/ppt/folders/validate` — identical or nested folder pairs, which
+/// make the same files show up under more than one folder.
+pub async fn validate_folders(State(state): State<AppState>) -> impl IntoResponse {
+    match list_folders_internal(&state.pool).await {
+        Ok(folders) => (
+            StatusCode::OK,
+            Json(json!({ "success": true, "data": find_folder_overlaps(&folders) })),
+        ),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "success": false, "error": e.to_string() })),
@@ -77,7 +335,7 @@ pub async fn add_folder(
 
 pub async fn remove_folder(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     match sqlx::query("DELETE FROM ppt_folders WHERE id = $1")
         .bind(id)
@@ -113,12 +371,7 @@ pub struct SearchQuery {
 
 /// Internal search helper used by both the HTTP handler and WS command handler.
 pub async fn search_files_internal(pool: &sqlx::PgPool, filter: &str) -> Vec<PptFile> {
-    let folders = match sqlx::query_as::<_, PptFolder>(
-        "SELECT id, path, name, sort_order FROM ppt_folders ORDER BY sort_order, name",
-    )
-    .fetch_all(pool)
-    .await
-    {
+    let folders = match list_folders_internal(pool).await {
         Ok(f) => f,
         Err(_) => return Vec::new(),
     };
@@ -142,7 +395,7 @@ pub async fn search_files_internal(pool: &sqlx::PgPool, filter: &str) -> Vec<Ppt
                 .and_then(|e| e.to_str())
                 .map(|e| e.to_lowercase())
                 .unwrap_or_default();
-            if ext != "ppt" && ext != "pptx" {
+            if !folder.extensions.contains(&ext) {
                 continue;
             }
 
@@ -187,7 +440,7 @@ pub async fn search_files_internal(pool: &sqlx::PgPool, filter: &str) -> Vec<Ppt
 
 pub async fn search_files(
     State(state): State<AppState>,
-    Query(query): Query<SearchQuery>,
+    ApiQuery(query): ApiQuery<SearchQuery>,
 ) -> impl IntoResponse {
     let filter = query.filter.as_deref().unwrap_or("");
     let files = search_files_internal(&state.pool, filter).await;
@@ -197,6 +450,168 @@ pub async fn search_files(
     )
 }
 
+// ── Unified presentation navigation ──────────────────────────────────────────
+
+/// Jump to the first or last slide on whichever backend is active, so remote
+/// controls don't need to know the slide count.
+async fn presentation_goto_edge(state: &AppState, first: bool) -> (StatusCode, Json<serde_json::Value>) {
+    if state.use_web_presenter.load(std::sync::atomic::Ordering::Relaxed) {
+        {
+            let mut ps = state.presenter_state.write().await;
+            if first {
+                ps.go_first();
+            } else {
+                ps.go_last();
+            }
+        }
+        websocket::broadcast_presenter_slide_changed(&state.ws_clients, &*state.presenter_state.read().await).await;
+        websocket::broadcast_presentation_status(&state.ws_clients, state).await;
+        return (StatusCode::OK, Json(json!({ "success": true })));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let result = if first {
+            state.keynote_connector.first().await
+        } else {
+            state.keynote_connector.last().await
+        };
+        websocket::broadcast_presentation_status(&state.ws_clients, state).await;
+        match result {
+            Ok(()) => (StatusCode::OK, Json(json!({ "success": true }))),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "success": false, "error": e })),
+            ),
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        (
+            StatusCode::NOT_IMPLEMENTED,
+            Json(json!({ "success": false, "error": "Keynote is only available on macOS" })),
+        )
+    }
+}
+
+/// Speaker notes of the current and next slide, for phones used as a
+/// confidence monitor. Only the web presenter parses notes; Keynote and other
+/// backends report empty notes.
+pub async fn presentation_notes(State(state): State<AppState>) -> impl IntoResponse {
+    let (slide, notes, next_notes) =
+        if state.use_web_presenter.load(std::sync::atomic::Ordering::Relaxed) {
+            let ps = state.presenter_state.read().await;
+            let next = (ps.current_slide < ps.total_slides)
+                .then(|| ps.notes_for(ps.current_slide + 1).to_string());
+            (ps.current_slide, ps.notes_for(ps.current_slide).to_string(), next)
+        } else {
+            (0, String::new(), None)
+        };
+    (
+        StatusCode::OK,
+        Json(json!({ "success": true, "slide": slide, "notes": notes, "nextNotes": next_notes })),
+    )
+}
+
+/// Flip the blank (mute) state of the active presentation and return the new
+/// state. For Keynote this is our best-effort shadow (see `KeynoteStatus::blanked`).
+pub(crate) async fn toggle_mute_internal(state: &AppState) -> Result<Option<bool>, String> {
+    if state.use_web_presenter.load(std::sync::atomic::Ordering::Relaxed) {
+        let muted = {
+            let mut ps = state.presenter_state.write().await;
+            if ps.muted {
+                ps.unmute();
+            } else {
+                ps.mute();
+            }
+            ps.muted
+        };
+        websocket::broadcast_presenter_state(&state.ws_clients, &*state.presenter_state.read().await).await;
+        websocket::broadcast_presentation_status(&state.ws_clients, state).await;
+        return Ok(Some(muted));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        state.keynote_connector.toggle_blank().await?;
+        Ok(Some(state.keynote_connector.get_status().await.blanked))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err("Keynote is only available on macOS".to_string())
+    }
+}
+
+pub async fn presentation_toggle_mute(State(state): State<AppState>) -> impl IntoResponse {
+    match toggle_mute_internal(&state).await {
+        Ok(muted) => (StatusCode::OK, Json(json!({ "success": true, "muted": muted }))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "success": false, "error": e })),
+        ),
+    }
+}
+
+pub async fn presentation_first(State(state): State<AppState>) -> impl IntoResponse {
+    presentation_goto_edge(&state, true).await
+}
+
+pub async fn presentation_last(State(state): State<AppState>) -> impl IntoResponse {
+    presentation_goto_edge(&state, false).await
+}
+
+// ── Screenshot ───────────────────────────────────────────────────────────────
+
+/// Minimum gap between screenshot captures; polling faster gets 429.
+const SCREENSHOT_MIN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+static LAST_SCREENSHOT: std::sync::Mutex<Option<std::time::Instant>> = std::sync::Mutex::new(None);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotQuery {
+    pub max_width: Option<u32>,
+}
+
+/// PNG of the display the slideshow is projected on, for remote previews.
+/// Rate-limited to one capture per `SCREENSHOT_MIN_INTERVAL`.
+pub async fn presentation_screenshot(ApiQuery(params): ApiQuery<ScreenshotQuery>) -> impl IntoResponse {
+    {
+        let mut last = LAST_SCREENSHOT.lock().unwrap_or_else(|e| e.into_inner());
+        let now = std::time::Instant::now();
+        if let Some(wait) = last.and_then(|t| SCREENSHOT_MIN_INTERVAL.checked_sub(now - t)) {
+            let retry_after = wait.as_secs().max(1).to_string();
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after)],
+                Json(json!({ "success": false, "error": "rate_limited" })),
+            )
+                .into_response();
+        }
+        *last = Some(now);
+    }
+
+    let capture = tokio::task::spawn_blocking(move || {
+        crate::displays::capture_presentation_display(params.max_width)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    match capture {
+        Ok(capture) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "image/png"), (header::CACHE_CONTROL, "no-store")],
+            capture.png,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "success": false, "error": e })),
+        )
+            .into_response(),
+    }
+}
+
 // ── Keynote control (macOS only) ─────────────────────────────────────────────
 
 #[cfg(target_os = "macos")]
@@ -204,6 +619,9 @@ pub async fn search_files(
 #[serde(rename_all = "camelCase")]
 pub struct OpenBody {
     pub file_path: String,
+    /// Slide to show once the slideshow starts; past-the-end values clamp to
+    /// the last slide.
+    pub start_slide: Option<u32>,
 }
 
 #[cfg(target_os = "macos")]
@@ -212,6 +630,12 @@ pub struct GotoBody {
     pub slide: u32,
 }
 
+#[cfg(target_os = "macos")]
+#[derive(Debug, Deserialize)]
+pub struct JumpBody {
+    pub offset: i32,
+}
+
 #[cfg(target_os = "macos")]
 pub async fn keynote_status(State(state): State<AppState>) -> impl IntoResponse {
     let status = state.keynote_connector.get_status().await;
@@ -223,8 +647,30 @@ pub async fn keynote_open(
     State(state): State<AppState>,
     Json(body): Json<OpenBody>,
 ) -> impl IntoResponse {
-    match state.keynote_connector.open_file(&body.file_path).await {
-        Ok(()) => (StatusCode::OK, Json(json!({ "success": true }))),
+    let file_path = match resolve_allowed_file(&state.pool, &body.file_path).await {
+        Ok(path) => path,
+        Err(e) => return (e.status(), Json(json!({ "success": false, "error": e.to_string() }))),
+    };
+    let Some(start_slide) = body.start_slide else {
+        return match state.keynote_connector.open_file(&file_path).await {
+            Ok(()) => (StatusCode::OK, Json(json!({ "success": true }))),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "success": false, "error": e })),
+            ),
+        };
+    };
+    if start_slide == 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "success": false, "error": "startSlide must be at least 1" })),
+        );
+    }
+    match state.keynote_connector.open_file_at(&file_path, start_slide).await {
+        Ok(slide) => (
+            StatusCode::OK,
+            Json(json!({ "success": true, "data": { "startSlide": slide } })),
+        ),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "success": false, "error": e })),
@@ -290,6 +736,20 @@ pub async fn keynote_goto(
     }
 }
 
+#[cfg(target_os = "macos")]
+pub async fn keynote_jump(
+    State(state): State<AppState>,
+    Json(body): Json<JumpBody>,
+) -> impl IntoResponse {
+    match state.keynote_connector.jump(body.offset).await {
+        Ok(()) => (StatusCode::OK, Json(json!({ "success": true }))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "success": false, "error": e })),
+        ),
+    }
+}
+
 #[cfg(target_os = "macos")]
 pub async fn keynote_start(State(state): State<AppState>) -> impl IntoResponse {
     match state.keynote_connector.start_slideshow().await {
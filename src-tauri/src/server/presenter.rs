@@ -1,4 +1,5 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::io::Read;
@@ -12,7 +13,7 @@ use crate::server::AppState;
 /// `lines` contains the text of each visual line; `<a:br>` (Shift+Enter) in
 /// the PPTX produces one entry per break.  `align` is a CSS keyword.
 /// `font_size_pt` is the author-specified size in points (0.0 = not found).
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ParagraphContent {
     pub lines: Vec<String>,
@@ -20,11 +21,13 @@ pub struct ParagraphContent {
     pub font_size_pt: f32,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SlideContent {
     pub index: u32,
     pub paragraphs: Vec<ParagraphContent>,
+    /// Speaker notes, one line per paragraph; empty when the slide has none.
+    pub notes: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -184,6 +187,112 @@ fn parse_slide_xml(xml: &[u8]) -> Vec<ParagraphContent> {
     paragraphs
 }
 
+/// Find the notes slide a slide links to via its `_rels` part. Targets are
+/// relative to `ppt/slides/`, e.g. `../notesSlides/notesSlide3.xml`.
+fn notes_slide_name(rels_xml: &[u8]) -> Option<String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(rels_xml);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e))
+                if e.name().into_inner() == b"Relationship" =>
+            {
+                let mut is_notes = false;
+                let mut target = None;
+                for attr in e.attributes().flatten() {
+                    match attr.key.into_inner() {
+                        b"Type" => is_notes = attr.value.ends_with(b"/notesSlide"),
+                        b"Target" => target = Some(String::from_utf8_lossy(&attr.value).into_owned()),
+                        _ => {}
+                    }
+                }
+                if let (true, Some(target)) = (is_notes, target) {
+                    let name = target.rsplit('/').next().unwrap_or(&target);
+                    return Some(format!("ppt/notesSlides/{name}"));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Text of the body placeholder in a notes slide. The slide image and
+/// slide-number placeholders are skipped.
+fn parse_notes_xml(xml: &[u8]) -> String {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(xml);
+    let mut buf: Vec<u8> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_line = String::new();
+    let mut in_body = false;
+    let mut in_text_run = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.name().into_inner() {
+                b"p:sp" => in_body = false,
+                b"a:t" => in_text_run = true,
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.name().into_inner() {
+                b"p:ph" => {
+                    in_body = e
+                        .attributes()
+                        .flatten()
+                        .any(|a| a.key.into_inner() == b"type" && a.value.as_ref() == b"body");
+                }
+                b"a:br" if in_body => current_line.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) => {
+                if in_body && in_text_run {
+                    if let Ok(decoded) = e.decode() {
+                        let unescaped = quick_xml::escape::unescape(&decoded)
+                            .map(|c| c.into_owned())
+                            .unwrap_or_else(|_| decoded.into_owned());
+                        current_line.push_str(&unescaped);
+                    }
+                }
+            }
+            Ok(Event::End(ref e)) => match e.name().into_inner() {
+                b"a:t" => in_text_run = false,
+                b"a:p" if in_body => lines.push(std::mem::take(&mut current_line)),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Speaker notes for slide entry `slide_name`, or empty if it has none.
+fn read_slide_notes(archive: &mut zip::ZipArchive<std::fs::File>, slide_name: &str) -> String {
+    let Some(basename) = slide_name.strip_prefix("ppt/slides/") else {
+        return String::new();
+    };
+    let mut read_entry = |name: &str| -> Option<Vec<u8>> {
+        let mut bytes = Vec::new();
+        archive.by_name(name).ok()?.read_to_end(&mut bytes).ok()?;
+        Some(bytes)
+    };
+    read_entry(&format!("ppt/slides/_rels/{basename}.rels"))
+        .and_then(|rels| notes_slide_name(&rels))
+        .and_then(|notes| read_entry(&notes))
+        .map(|xml| parse_notes_xml(&xml))
+        .unwrap_or_default()
+}
+
 /// Parse `ppt/presentation.xml` for the `<p:sldSz>` element and return its
 /// `cx`/`cy` attributes in EMUs.  Falls back to the standard 16:9 dimensions.
 fn parse_slide_size(xml: &[u8]) -> (u64, u64) {
@@ -276,6 +385,7 @@ pub fn parse_pptx(file_path: &str) -> Result<ParsedPresentation, String> {
         slides.push(SlideContent {
             index: idx as u32 + 1,
             paragraphs: parse_slide_xml(&xml_bytes),
+            notes: read_slide_notes(&mut archive, name),
         });
     }
 
@@ -302,7 +412,91 @@ pub fn parse_pptx(file_path: &str) -> Result<ParsedPresentation, String> {
 
 // ── Live presenter state ──────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize)]
+/// Outcome of checking a presentation file without opening it in an app.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileValidation {
+    pub valid: bool,
+    /// Only known for formats whose slides can be counted offline (.pptx, .odp).
+    pub slide_count: Option<u32>,
+    pub error: Option<String>,
+}
+
+impl FileValidation {
+    fn ok(slide_count: Option<u32>) -> Self {
+        Self { valid: true, slide_count, error: None }
+    }
+
+    fn err(error: impl Into<String>) -> Self {
+        Self { valid: false, slide_count: None, error: Some(error.into()) }
+    }
+}
+
+/// OLE2 compound document signature used by legacy `.ppt` files.
+const OLE2_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Check that a deck is readable and has slides, without launching
+/// PowerPoint/Keynote. `.pptx` and `.odp` are fully parsed; `.ppt` and `.key`
+/// only get a structural check.
+pub fn validate_presentation_file(file_path: &str) -> FileValidation {
+    let path = std::path::Path::new(file_path);
+    if !path.exists() {
+        return FileValidation::err("File not found");
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "pptx" => match parse_pptx(file_path) {
+            Ok(parsed) if parsed.total_slides == 0 => FileValidation::err("No slides found"),
+            Ok(parsed) => FileValidation::ok(Some(parsed.total_slides)),
+            Err(e) => FileValidation::err(e),
+        },
+        "odp" => match count_odp_slides(file_path) {
+            Ok(0) => FileValidation::err("No slides found"),
+            Ok(n) => FileValidation::ok(Some(n)),
+            Err(e) => FileValidation::err(e),
+        },
+        "ppt" => {
+            let mut header = [0u8; 8];
+            match std::fs::File::open(path).and_then(|mut f| f.read_exact(&mut header)) {
+                Ok(()) if header == OLE2_MAGIC => FileValidation::ok(None),
+                Ok(()) => FileValidation::err("Not a valid .ppt file"),
+                Err(e) => FileValidation::err(format!("Cannot read file: {e}")),
+            }
+        }
+        // Keynote documents are either a package directory or a zip archive.
+        "key" => {
+            if path.is_dir() && path.join("Index").exists() {
+                return FileValidation::ok(None);
+            }
+            match std::fs::File::open(path).map(zip::ZipArchive::new) {
+                Ok(Ok(_)) => FileValidation::ok(None),
+                Ok(Err(e)) => FileValidation::err(format!("Not a valid Keynote file: {e}")),
+                Err(e) => FileValidation::err(format!("Cannot open file: {e}")),
+            }
+        }
+        _ => FileValidation::err(format!("Unsupported presentation format: .{ext}")),
+    }
+}
+
+fn count_odp_slides(file_path: &str) -> Result<u32, String> {
+    let file = std::fs::File::open(file_path).map_err(|e| format!("Cannot open file: {e}"))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a valid .odp file: {e}"))?;
+    let mut content = String::new();
+    archive
+        .by_name("content.xml")
+        .map_err(|e| format!("Missing content.xml: {e}"))?
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Cannot read slide content: {e}"))?;
+    Ok(content.matches("<draw:page ").count() as u32)
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PresenterState {
     pub loaded: bool,
@@ -351,6 +545,15 @@ impl PresenterState {
         self.muted = false;
     }
 
+    /// Notes of slide `number` (1-based); empty when out of range.
+    pub fn notes_for(&self, number: u32) -> &str {
+        self.slides
+            .iter()
+            .find(|s| s.index == number)
+            .map(|s| s.notes.as_str())
+            .unwrap_or_default()
+    }
+
     pub fn go_next(&mut self) {
         if self.loaded && self.current_slide < self.total_slides {
             self.current_slide += 1;
@@ -381,6 +584,14 @@ impl PresenterState {
         }
     }
 
+    /// Move `offset` slides relative to the current one, clamped to the deck.
+    pub fn jump(&mut self, offset: i32) {
+        if self.loaded && self.total_slides > 0 {
+            let target = i64::from(self.current_slide) + i64::from(offset);
+            self.current_slide = target.clamp(1, i64::from(self.total_slides)) as u32;
+        }
+    }
+
     /// Replace the paragraphs of a slide from plain editor lines.
     ///
     /// Each text string becomes a single-line paragraph, preserving the
@@ -421,10 +632,13 @@ pub struct ParseBody {
 }
 
 pub async fn parse_presentation(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(body): Json<ParseBody>,
 ) -> impl IntoResponse {
-    let file_path = body.file_path;
+    let file_path = match crate::server::ppt::resolve_allowed_file(&state.pool, &body.file_path).await {
+        Ok(path) => path,
+        Err(e) => return (e.status(), Json(json!({ "success": false, "error": e.to_string() }))),
+    };
     let result = tokio::task::spawn_blocking(move || parse_pptx(&file_path)).await;
 
     match result {
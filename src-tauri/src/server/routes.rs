@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{ConnectInfo, Query, State},
     http::StatusCode,
     response::{Html, IntoResponse},
     Json,
@@ -10,7 +10,7 @@ use serde_json::json;
 use std::sync::atomic::Ordering;
 use uuid::Uuid;
 
-use crate::connectors::{facebook, youtube};
+use crate::connectors::{facebook, obs::ObsError, youtube};
 use crate::models::{
     activity::{self, CreateEventActivity},
     cron_job::{self, CreateCronJob, UpdateCronJob},
@@ -21,6 +21,7 @@ use crate::models::{
 use crate::server::websocket::{
     broadcast_event_changed, broadcast_untracked_removed, spawn_scheduling_tasks,
 };
+use crate::server::extract::{ApiPath, ApiQuery};
 use crate::server::AppState;
 use crate::server::OAUTH_REDIRECT_URI;
 
@@ -86,6 +87,242 @@ pub async fn get_connector_statuses(State(state): State<AppState>) -> impl IntoR
     Json(json!({ "obs": obs, "vmix": vmix, "youtube": yt, "facebook": fb }))
 }
 
+/// Longest any one dashboard section may take before it is reported as timed out.
+const DASHBOARD_SECTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+/// Per-device reachability budget; well under the section timeout, so an
+/// offline device is reported unreachable instead of timing out the section.
+const DASHBOARD_DEVICE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+// Wrap a dashboard section as `{ data, error }` so one failing or slow
+// source doesn't hide the others.
+async fn dashboard_section<T: Serialize>(
+    fut: impl std::future::Future<Output = Result<T, String>>,
+) -> serde_json::Value {
+    match tokio::time::timeout(DASHBOARD_SECTION_TIMEOUT, fut).await {
+        Ok(Ok(data)) => json!({ "data": data, "error": null }),
+        Ok(Err(e)) => json!({ "data": null, "error": e }),
+        Err(_) => json!({ "data": null, "error": "timeout" }),
+    }
+}
+
+/// Everything a client dashboard needs on connect, in one call: connector
+/// statuses, OBS outputs, presentation state and Broadlink reachability.
+/// Sections are fetched concurrently and fail independently.
+pub async fn get_dashboard(State(state): State<AppState>) -> impl IntoResponse {
+    let connectors = dashboard_section(async {
+        Ok::<_, String>(json!({
+            "obs": state.obs_connector.get_status().await,
+            "vmix": state.vmix_connector.get_status(),
+            "youtube": state.youtube_connector.get_status().await,
+            "facebook": state.facebook_connector.get_status().await,
+            "broadlink": state.broadlink_connector.get_status().await,
+        }))
+    });
+    let obs = dashboard_section(async { Ok::<_, String>(state.obs_connector.output_status().await?) });
+    let presentation = dashboard_section(async {
+        Ok::<_, String>(crate::server::websocket::presentation_status(&state).await)
+    });
+    let devices = dashboard_section(async {
+        let rows = sqlx::query_as::<_, (Uuid, String, String, String, String, Option<i32>)>(
+            "SELECT id, name, host, mac, device_type, port FROM broadlink_devices ORDER BY created_at",
+        )
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        let checks = rows.into_iter().map(|(id, name, host, mac, devtype, port)| async move {
+            let reachable = tokio::time::timeout(
+                DASHBOARD_DEVICE_TIMEOUT,
+                crate::broadlink::test_device(&host, &mac, &devtype, port.map(|p| p as u16)),
+            )
+            .await
+            .is_ok_and(|r| r.unwrap_or(false));
+            json!({ "id": id, "name": name, "host": host, "reachable": reachable })
+        });
+        Ok::<_, String>(futures_util::future::join_all(checks).await)
+    });
+
+    let (connectors, obs, presentation, devices) =
+        tokio::join!(connectors, obs, presentation, devices);
+    Json(json!({
+        "connectors": connectors,
+        "obs": obs,
+        "presentation": presentation,
+        "devices": devices,
+    }))
+}
+
+// ── OBS output control ────────────────────────────────────────────────────────
+
+fn obs_error_response(handler: &str, e: ObsError) -> axum::response::Response {
+    use obws::{error::Error as ObwsError, responses::StatusCode as ObsStatus};
+
+    let status = match &e {
+        ObsError::NotConnected => StatusCode::SERVICE_UNAVAILABLE,
+        ObsError::NotFound(_)
+        | ObsError::Obs(ObwsError::Api { code: ObsStatus::ResourceNotFound, .. }) => StatusCode::NOT_FOUND,
+        ObsError::Invalid(_) => StatusCode::BAD_REQUEST,
+        ObsError::Obs(_) => {
+            tracing::error!("{handler}: {e}");
+            StatusCode::BAD_GATEWAY
+        }
+    };
+    (status, Json(json!({ "error": e.to_string() }))).into_response()
+}
+
+pub async fn obs_output_status(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.output_status().await {
+        Ok(status) => Json(status).into_response(),
+        Err(e) => obs_error_response("obs_output_status", e),
+    }
+}
+
+pub async fn obs_stream_start(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.start_streaming().await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_stream_start", e),
+    }
+}
+
+pub async fn obs_stream_stop(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.stop_streaming().await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_stream_stop", e),
+    }
+}
+
+pub async fn obs_record_start(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.start_recording().await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_record_start", e),
+    }
+}
+
+pub async fn obs_record_stop(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.stop_recording().await {
+        Ok(output_path) => Json(json!({ "outputPath": output_path })).into_response(),
+        Err(e) => obs_error_response("obs_record_stop", e),
+    }
+}
+
+pub async fn obs_list_scenes(State(state): State<AppState>) -> impl IntoResponse {
+    match state.obs_connector.list_scenes().await {
+        Ok(list) => Json(list).into_response(),
+        Err(e) => obs_error_response("obs_list_scenes", e),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSceneBody {
+    pub scene_name: String,
+}
+
+pub async fn obs_set_scene(
+    State(state): State<AppState>,
+    Json(body): Json<SetSceneBody>,
+) -> impl IntoResponse {
+    match state.obs_connector.set_scene(&body.scene_name).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_set_scene", e),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleSourceBody {
+    pub scene_name: String,
+    pub source_name: String,
+    pub enabled: bool,
+}
+
+pub async fn obs_toggle_source(
+    State(state): State<AppState>,
+    Json(body): Json<ToggleSourceBody>,
+) -> impl IntoResponse {
+    match state
+        .obs_connector
+        .set_source_enabled(&body.scene_name, &body.source_name, body.enabled)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_toggle_source", e),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTextBody {
+    pub source_name: String,
+    pub text: String,
+}
+
+pub async fn obs_set_text(
+    State(state): State<AppState>,
+    Json(body): Json<SetTextBody>,
+) -> impl IntoResponse {
+    if let Err(e) = crate::connectors::obs::clean_text_input(&body.source_name, &body.text) {
+        return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+    }
+    match state.obs_connector.set_text(&body.source_name, &body.text).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => obs_error_response("obs_set_text", e),
+    }
+}
+
+// ── Pairing ───────────────────────────────────────────────────────────────────
+
+/// Version of the HTTP/WS API advertised to pairing clients.
+pub const API_VERSION: u32 = 1;
+
+/// Machine-readable bootstrap info for a freshly connected client.
+/// Only answered for LAN peers so network topology isn't exposed externally.
+pub async fn pair_info(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<std::net::SocketAddr>,
+) -> impl IntoResponse {
+    if !crate::server::lan::is_lan_address(peer.ip()) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Pairing is only available on the local network" })),
+        )
+            .into_response();
+    }
+
+    // Only enumerate interfaces when listening on all of them; otherwise the
+    // bound address is the only one a client can reach us on.
+    let listen_addr = *state.listen_addr.read().unwrap_or_else(|e| e.into_inner());
+    let addresses: Vec<String> = if listen_addr.ip().is_unspecified() {
+        tokio::task::spawn_blocking(crate::broadlink::get_local_ipv4_addresses)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|ip| ip.to_string())
+            .collect()
+    } else {
+        vec![listen_addr.ip().to_string()]
+    };
+
+    let peer_ip = peer.ip();
+    let url = tokio::task::spawn_blocking(move || {
+        crate::server::lan::best_url_for_client(peer_ip, listen_addr)
+    })
+    .await
+    .ok()
+    .flatten();
+
+    Json(json!({
+        "service": "metocast",
+        "serverId": state.server_id,
+        "url": url,
+        "addresses": addresses,
+        "port": listen_addr.port(),
+        "authRequired": true,
+        "apiVersion": API_VERSION,
+        "wsPath": "/ws",
+    }))
+    .into_response()
+}
+
 // ── YouTube OAuth ─────────────────────────────────────────────────────────────
 
 pub async fn youtube_auth_url(State(state): State<AppState>) -> impl IntoResponse {
@@ -219,7 +456,7 @@ pub async fn facebook_logout(State(state): State<AppState>) -> impl IntoResponse
 
 pub async fn trigger_youtube_schedule(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let event = match fetch_event(event_id, &state.pool).await {
         Ok(Some(e)) => e,
@@ -342,9 +579,42 @@ pub async fn get_youtube_content(State(state): State<AppState>) -> impl IntoResp
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentUploadsQuery {
+    pub max_results: Option<u32>,
+}
+
+pub async fn get_youtube_recent_uploads(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<RecentUploadsQuery>,
+) -> impl IntoResponse {
+    let config = state.youtube_config.read().await.clone();
+    let max_results = query.max_results.unwrap_or(10);
+    match youtube::fetch_recent_uploads(&state.pool, &config, max_results).await {
+        Ok(uploads) => Json(uploads).into_response(),
+        Err(e) => {
+            tracing::error!("fetch_recent_uploads failed: {e}");
+            if e.is::<youtube::AuthRequired>() {
+                state.youtube_connector.stop().await;
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    Json(json!({ "error": "auth_required", "message": "Re-login required" })),
+                )
+                    .into_response();
+            }
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
 pub async fn trigger_facebook_schedule(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let event = match fetch_event(event_id, &state.pool).await {
         Ok(Some(e)) => e,
@@ -488,7 +758,7 @@ pub async fn get_youtube_stream_key(State(state): State<AppState>) -> impl IntoR
         items: Option<Vec<StreamItem>>,
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let resp = client
         .get("https://www.googleapis.com/youtube/v3/liveStreams")
         .query(&[("part", "cdn"), ("mine", "true")])
@@ -569,7 +839,7 @@ pub async fn get_facebook_stream_key(State(state): State<AppState>) -> impl Into
         data: Vec<LiveVideo>,
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::http::http_client();
     let resp = client
         .get(format!(
             "https://graph.facebook.com/v19.0/{}/live_videos",
@@ -645,7 +915,7 @@ pub async fn list_events(State(state): State<AppState>) -> impl IntoResponse {
 
 pub async fn get_event(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     match fetch_event(id, &state.pool).await {
         Ok(Some(event)) => (StatusCode::OK, Json(event)).into_response(),
@@ -783,7 +1053,7 @@ pub async fn create_event(
 
 pub async fn update_event(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
     Json(body): Json<UpdateEvent>,
 ) -> impl IntoResponse {
     let result: anyhow::Result<Option<_>> = async {
@@ -871,7 +1141,7 @@ pub struct RecordingWithEvent {
 
 pub async fn list_all_recordings(
     State(state): State<AppState>,
-    Query(params): Query<AllRecordingsQuery>,
+    ApiQuery(params): ApiQuery<AllRecordingsQuery>,
 ) -> impl IntoResponse {
     let where_clause = match params.filter.as_deref().unwrap_or("") {
         // Never flagged and no upload history at all
@@ -943,7 +1213,7 @@ pub async fn list_all_recordings(
 
 pub async fn list_recordings(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let mut recordings = match sqlx::query_as::<_, Recording>(
         "SELECT * FROM recordings WHERE event_id = $1 ORDER BY detected_at DESC",
@@ -985,7 +1255,7 @@ pub async fn list_recordings(
 
 pub async fn create_recording(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
     Json(body): Json<CreateRecording>,
 ) -> impl IntoResponse {
     let result = sqlx::query_as::<_, Recording>(
@@ -1025,8 +1295,8 @@ pub struct DeleteRecordingParams {
 
 pub async fn delete_recording(
     State(state): State<AppState>,
-    Path((event_id, recording_id)): Path<(Uuid, Uuid)>,
-    Query(params): Query<DeleteRecordingParams>,
+    ApiPath((event_id, recording_id)): ApiPath<(Uuid, Uuid)>,
+    ApiQuery(params): ApiQuery<DeleteRecordingParams>,
 ) -> impl IntoResponse {
     let row = sqlx::query_as::<_, Recording>(
         "SELECT * FROM recordings WHERE id = $1 AND event_id = $2",
@@ -1066,7 +1336,7 @@ pub async fn delete_recording(
 
 pub async fn delete_event(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let event = match fetch_event(id, &state.pool).await {
         Ok(Some(e)) => e,
@@ -1175,7 +1445,7 @@ pub async fn create_cron_job(
 
 pub async fn update_cron_job(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
     Json(body): Json<UpdateCronJob>,
 ) -> impl IntoResponse {
     // Validate the cron expression before updating.
@@ -1250,7 +1520,7 @@ pub async fn update_cron_job(
 
 pub async fn delete_cron_job(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let result =
         sqlx::query("DELETE FROM cron_jobs WHERE id = $1 RETURNING id")
@@ -1282,11 +1552,25 @@ pub async fn delete_cron_job(
 
 pub async fn flag_upload(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
     Json(body): Json<FlagUploadRequest>,
 ) -> impl IntoResponse {
     let result: anyhow::Result<()> = async {
+        let defaults = crate::uploader::load_upload_defaults(&state.pool).await;
         for item in &body.recordings {
+            let description = match &item.custom_description {
+                None if item.use_description_template => Some(
+                    crate::uploader::render_event_description(
+                        &state.pool,
+                        event_id,
+                        item.custom_title.as_deref(),
+                        &defaults.description_template,
+                    )
+                    .await?,
+                ),
+                d => d.clone(),
+            };
+
             // Mark the recording as uploadable, optionally update custom title/description.
             sqlx::query(
                 "UPDATE recordings SET uploadable = true, \
@@ -1296,7 +1580,7 @@ pub async fn flag_upload(
                  WHERE id = $3 AND event_id = $4",
             )
             .bind(item.custom_title.as_deref())
-            .bind(item.custom_description.as_deref())
+            .bind(description.as_deref())
             .bind(item.recording_id)
             .bind(event_id)
             .execute(&state.pool)
@@ -1307,7 +1591,7 @@ pub async fn flag_upload(
                 let visibility = if platform == "youtube" {
                     item.youtube_visibility
                         .as_deref()
-                        .unwrap_or("private")
+                        .unwrap_or(&defaults.youtube_privacy_status)
                         .to_string()
                 } else {
                     item.facebook_visibility
@@ -1345,6 +1629,60 @@ pub async fn flag_upload(
     }
 }
 
+pub async fn cancel_all_uploads(State(state): State<AppState>) -> impl IntoResponse {
+    match state.upload_service.cancel_all().await {
+        Ok(cancelled) => (StatusCode::OK, Json(json!({ "cancelled": cancelled }))).into_response(),
+        Err(e) => {
+            tracing::error!("cancel_all_uploads: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub async fn get_upload_defaults(State(state): State<AppState>) -> impl IntoResponse {
+    Json(crate::uploader::load_upload_defaults(&state.pool).await)
+}
+
+pub async fn update_upload_defaults(
+    State(state): State<AppState>,
+    Json(body): Json<crate::uploader::UploadDefaults>,
+) -> impl IntoResponse {
+    match crate::uploader::save_upload_defaults(&state.pool, &body).await {
+        Ok(()) => (StatusCode::OK, Json(body)).into_response(),
+        Err(e) => {
+            tracing::error!("update_upload_defaults: {e}");
+            (StatusCode::BAD_REQUEST, Json(json!({ "error": e.to_string() }))).into_response()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CheckUploadedBody {
+    pub path: String,
+}
+
+/// Look up whether a file with the same content was uploaded before.
+/// `path` must belong to a known recording (404 otherwise).
+pub async fn check_already_uploaded(
+    State(state): State<AppState>,
+    Json(body): Json<CheckUploadedBody>,
+) -> impl IntoResponse {
+    match crate::uploader::check_already_uploaded(&state.pool, &body.path).await {
+        Ok(previous) => (
+            StatusCode::OK,
+            Json(json!({ "uploaded": previous.is_some(), "previous": previous })),
+        )
+            .into_response(),
+        Err(e) if e.is::<crate::uploader::UnknownRecording>() => {
+            (StatusCode::NOT_FOUND, Json(json!({ "error": e.to_string() }))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("check_already_uploaded: {e}");
+            (StatusCode::BAD_REQUEST, Json(json!({ "error": e.to_string() }))).into_response()
+        }
+    }
+}
+
 pub async fn trigger_upload_cycle(State(state): State<AppState>) -> impl IntoResponse {
     let us = state.upload_service.clone();
     tokio::spawn(async move {
@@ -1372,7 +1710,7 @@ pub struct AssignRecordingBody {
 
 pub async fn assign_untracked_recording(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
     Json(body): Json<AssignRecordingBody>,
 ) -> impl IntoResponse {
     let result: anyhow::Result<Recording> = async {
@@ -1445,8 +1783,8 @@ pub struct DeleteUntrackedParams {
 
 pub async fn delete_untracked_recording(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Query(params): Query<DeleteUntrackedParams>,
+    ApiPath(id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<DeleteUntrackedParams>,
 ) -> impl IntoResponse {
     let row = sqlx::query_as::<_, untracked_recording::UntrackedRecording>(
         "SELECT * FROM untracked_recordings WHERE id = $1",
@@ -1484,11 +1822,138 @@ pub async fn delete_untracked_recording(
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameRecordingBody {
+    pub old_path: String,
+    pub new_name: String,
+}
+
+/// Rename a recording file in place and repoint recording rows at it.
+///
+/// `old_path` must belong to a row in `recordings` or `untracked_recordings`,
+/// so network clients can only rename files the app itself recorded.
+/// `new_name` must be a bare file name; separators, `.` and `..` are rejected
+/// so the file can't leave its directory, and the original extension is
+/// kept. Errors are `not_found`, `target_exists`, `database_error` (the file
+/// is renamed back), or a descriptive message for invalid names and I/O
+/// failures.
+pub(crate) async fn rename_recording_file(
+    pool: &sqlx::PgPool,
+    old_path: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    let new_name = new_name.trim();
+    let is_bare_name = !new_name.is_empty()
+        && !new_name.contains(['/', '\\'])
+        && std::path::Path::new(new_name).file_name() == Some(std::ffi::OsStr::new(new_name));
+    if !is_bare_name {
+        return Err(format!("invalid file name '{new_name}'"));
+    }
+
+    let known: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM recordings WHERE file_path = $1) \
+             OR EXISTS(SELECT 1 FROM untracked_recordings WHERE file_path = $1)",
+    )
+    .bind(old_path)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("rename_recording_file lookup: {e}");
+        "database_error".to_string()
+    })?;
+    if !known {
+        return Err("not_found".to_string());
+    }
+
+    let old = std::path::Path::new(old_path);
+    let new_name = match old.extension().and_then(|e| e.to_str()) {
+        Some(ext)
+            if !std::path::Path::new(new_name)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case(ext)) =>
+        {
+            format!("{new_name}.{ext}")
+        }
+        _ => new_name.to_string(),
+    };
+    let new_name = new_name.as_str();
+    let target = old.with_file_name(new_name);
+
+    // Claim the target name atomically, then rename over the placeholder;
+    // a separate exists check would race with other writers.
+    match tokio::fs::OpenOptions::new().write(true).create_new(true).open(&target).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            return Err("target_exists".to_string())
+        }
+        Err(e) => return Err(e.to_string()),
+    }
+    if let Err(e) = tokio::fs::rename(old, &target).await {
+        let _ = tokio::fs::remove_file(&target).await;
+        return Err(if e.kind() == std::io::ErrorKind::NotFound {
+            "not_found".to_string()
+        } else {
+            e.to_string()
+        });
+    }
+
+    let new_path = target.to_string_lossy().into_owned();
+    if let Err(e) = update_recording_paths(pool, old_path, &new_path, new_name).await {
+        // Keep the file where the rows still point.
+        tracing::error!("rename_recording_file: updating paths failed: {e}");
+        if let Err(e) = tokio::fs::rename(&target, old).await {
+            tracing::error!("rename_recording_file: could not restore {old_path}: {e}");
+        }
+        return Err("database_error".to_string());
+    }
+    Ok(new_path)
+}
+
+/// Point both recording tables at the renamed file, all or nothing.
+async fn update_recording_paths(
+    pool: &sqlx::PgPool,
+    old_path: &str,
+    new_path: &str,
+    new_name: &str,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for table in ["recordings", "untracked_recordings"] {
+        sqlx::query(&format!(
+            "UPDATE {table} SET file_path = $1, file_name = $2 WHERE file_path = $3"
+        ))
+        .bind(new_path)
+        .bind(new_name)
+        .bind(old_path)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+pub async fn rename_recording(
+    State(state): State<AppState>,
+    Json(body): Json<RenameRecordingBody>,
+) -> impl IntoResponse {
+    match rename_recording_file(&state.pool, &body.old_path, &body.new_name).await {
+        Ok(path) => (StatusCode::OK, Json(json!({ "path": path }))).into_response(),
+        Err(e) => {
+            let status = match e.as_str() {
+                "not_found" => StatusCode::NOT_FOUND,
+                "target_exists" => StatusCode::CONFLICT,
+                "database_error" => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (status, Json(json!({ "error": e }))).into_response()
+        }
+    }
+}
+
 // ── Event activities ───────────────────────────────────────────────────────────
 
 pub async fn list_event_activities(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     match activity::list_activities(event_id, &state.pool).await {
         Ok(activities) => (StatusCode::OK, Json(activities)).into_response(),
@@ -1501,7 +1966,7 @@ pub async fn list_event_activities(
 
 pub async fn create_event_activity(
     State(state): State<AppState>,
-    Path(event_id): Path<Uuid>,
+    ApiPath(event_id): ApiPath<Uuid>,
     Json(body): Json<CreateEventActivity>,
 ) -> impl IntoResponse {
     // Verify event exists
@@ -1536,7 +2001,7 @@ pub async fn create_event_activity(
 
 pub async fn delete_event_activity(
     State(state): State<AppState>,
-    Path((event_id, activity_id)): Path<(Uuid, Uuid)>,
+    ApiPath((event_id, activity_id)): ApiPath<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
     let result = sqlx::query(
         "DELETE FROM event_activities WHERE id = $1 AND event_id = $2 RETURNING id",
@@ -1568,6 +2033,8 @@ struct BroadlinkDevice {
     host: String,
     mac: String,
     is_default: bool,
+    /// UDP port found by a probe; `None` means the protocol's port 80.
+    port: Option<i32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1590,6 +2057,8 @@ pub struct AddDeviceBody {
     mac: String,
     device_type: String,
     model: Option<String>,
+    #[serde(default)]
+    port: Option<u16>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1619,20 +2088,79 @@ pub struct LearnBody {
     signal_type: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ProbePortBody {
+    /// Alternate ports to try after 80.
+    #[serde(default)]
+    ports: Vec<u16>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CommandsQuery {
     device_id: Option<Uuid>,
     category: Option<String>,
 }
 
+/// A slug shared by more than one command. `command_ids` is ordered by
+/// creation time (then id), oldest first.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateSlug {
+    slug: String,
+    command_ids: Vec<Uuid>,
+}
+
+/// Find slugs used by more than one command. The schema only enforces
+/// uniqueness per device, so the same slug can exist on two devices (or on
+/// several commands without a device).
+pub(crate) async fn find_duplicate_slugs(
+    pool: &sqlx::PgPool,
+) -> Result<Vec<DuplicateSlug>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, Vec<Uuid>)>(
+        "SELECT slug, array_agg(id ORDER BY created_at, id) \
+         FROM broadlink_commands GROUP BY slug HAVING COUNT(*) > 1 ORDER BY slug",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(slug, command_ids)| DuplicateSlug { slug, command_ids })
+        .collect())
+}
+
+/// Log a warning when `slug` is already used by a command other than `id`.
+pub(crate) async fn warn_on_slug_collision(pool: &sqlx::PgPool, id: Uuid, slug: &str) {
+    let others: Result<i64, _> =
+        sqlx::query_scalar("SELECT COUNT(*) FROM broadlink_commands WHERE slug = $1 AND id <> $2")
+            .bind(slug)
+            .bind(id)
+            .fetch_one(pool)
+            .await;
+    if let Ok(n) = others {
+        if n > 0 {
+            tracing::warn!("Broadlink command slug '{slug}' is used by {n} other command(s)");
+        }
+    }
+}
+
+pub async fn broadlink_validate_commands(State(state): State<AppState>) -> impl IntoResponse {
+    match find_duplicate_slugs(&state.pool).await {
+        Ok(duplicates) => Json(json!({ "duplicates": duplicates })).into_response(),
+        Err(e) => {
+            tracing::error!("broadlink_validate_commands: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 pub async fn broadlink_get_status(State(state): State<AppState>) -> impl IntoResponse {
     let status = state.broadlink_connector.get_status().await;
     Json(json!({ "status": status }))
 }
 
 pub async fn broadlink_list_devices(State(state): State<AppState>) -> impl IntoResponse {
-    let rows = sqlx::query_as::<_, (Uuid, String, String, Option<String>, String, String, bool)>(
-        "SELECT id, name, device_type, model, host, mac, is_default FROM broadlink_devices ORDER BY created_at",
+    let rows = sqlx::query_as::<_, (Uuid, String, String, Option<String>, String, String, bool, Option<i32>)>(
+        "SELECT id, name, device_type, model, host, mac, is_default, port FROM broadlink_devices ORDER BY created_at",
     )
     .fetch_all(&state.pool)
     .await;
@@ -1641,7 +2169,7 @@ pub async fn broadlink_list_devices(State(state): State<AppState>) -> impl IntoR
         Ok(devices) => {
             let list: Vec<BroadlinkDevice> = devices
                 .into_iter()
-                .map(|(id, name, device_type, model, host, mac, is_default)| BroadlinkDevice {
+                .map(|(id, name, device_type, model, host, mac, is_default, port)| BroadlinkDevice {
                     id,
                     name,
                     device_type,
@@ -1649,6 +2177,7 @@ pub async fn broadlink_list_devices(State(state): State<AppState>) -> impl IntoR
                     host,
                     mac,
                     is_default,
+                    port,
                 })
                 .collect();
             Json(list).into_response()
@@ -1665,14 +2194,15 @@ pub async fn broadlink_add_device(
     Json(body): Json<AddDeviceBody>,
 ) -> impl IntoResponse {
     let result = sqlx::query_as::<_, (Uuid,)>(
-        "INSERT INTO broadlink_devices (name, device_type, model, host, mac) \
-         VALUES ($1, $2, $3, $4, $5) RETURNING id",
+        "INSERT INTO broadlink_devices (name, device_type, model, host, mac, port) \
+         VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
     )
     .bind(&body.name)
     .bind(&body.device_type)
     .bind(&body.model)
     .bind(&body.host)
     .bind(&body.mac)
+    .bind(body.port.filter(|&p| p != 0).map(i32::from))
     .fetch_one(&state.pool)
     .await;
 
@@ -1691,6 +2221,7 @@ pub async fn broadlink_add_device(
                 host: body.host,
                 mac: body.mac,
                 is_default: false,
+                port: body.port.filter(|&p| p != 0).map(i32::from),
             };
             (StatusCode::CREATED, Json(device)).into_response()
         }
@@ -1703,7 +2234,7 @@ pub async fn broadlink_add_device(
 
 pub async fn broadlink_remove_device(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let result = sqlx::query("DELETE FROM broadlink_devices WHERE id = $1")
         .bind(id)
@@ -1733,6 +2264,53 @@ pub async fn broadlink_remove_device(
     }
 }
 
+/// Probe which UDP port a saved device answers on and remember it, so later
+/// learns and sends use it. Responds with `{ "port": null }` when nothing
+/// answered; the stored port is left unchanged in that case. The body is
+/// optional: a bare POST (no body or `Content-Type`) probes port 80 only.
+pub async fn broadlink_probe_device_port(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<Uuid>,
+    body: Option<Json<ProbePortBody>>,
+) -> impl IntoResponse {
+    let host: Option<String> = match sqlx::query_scalar("SELECT host FROM broadlink_devices WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+    {
+        Ok(host) => host,
+        Err(e) => {
+            tracing::error!("broadlink_probe_device_port fetch: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Some(host) = host else {
+        return (StatusCode::NOT_FOUND, Json(json!({ "error": "Device not found" }))).into_response();
+    };
+
+    let alternates = body.map(|Json(b)| b.ports).unwrap_or_default();
+    let port = match crate::broadlink::probe_device_port(&host, &alternates).await {
+        Ok(port) => port,
+        Err(e) => {
+            return (StatusCode::BAD_GATEWAY, Json(json!({ "error": e }))).into_response();
+        }
+    };
+    if let Some(port) = port {
+        // Store the default as NULL so the column only records exceptions.
+        let stored = (port != 80).then_some(i32::from(port));
+        if let Err(e) = sqlx::query("UPDATE broadlink_devices SET port = $1 WHERE id = $2")
+            .bind(stored)
+            .bind(id)
+            .execute(&state.pool)
+            .await
+        {
+            tracing::error!("broadlink_probe_device_port update: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+    Json(json!({ "port": port })).into_response()
+}
+
 pub async fn broadlink_discover(State(state): State<AppState>) -> impl IntoResponse {
     let clients = state.ws_clients.clone();
     let pool = state.pool.clone();
@@ -1795,13 +2373,13 @@ pub async fn broadlink_discover(State(state): State<AppState>) -> impl IntoRespo
 
 pub async fn broadlink_list_commands(
     State(state): State<AppState>,
-    Query(q): Query<CommandsQuery>,
+    ApiQuery(q): ApiQuery<CommandsQuery>,
 ) -> impl IntoResponse {
     let rows = if let Some(device_id) = q.device_id {
         if let Some(cat) = q.category {
             sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
                 "SELECT id, device_id, name, slug, code, code_type, category \
-                 FROM broadlink_commands WHERE device_id = $1 AND category = $2 ORDER BY created_at",
+                 FROM broadlink_commands WHERE device_id = $1 AND category = $2 ORDER BY created_at, id",
             )
             .bind(device_id)
             .bind(cat)
@@ -1810,7 +2388,7 @@ pub async fn broadlink_list_commands(
         } else {
             sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
                 "SELECT id, device_id, name, slug, code, code_type, category \
-                 FROM broadlink_commands WHERE device_id = $1 ORDER BY created_at",
+                 FROM broadlink_commands WHERE device_id = $1 ORDER BY created_at, id",
             )
             .bind(device_id)
             .fetch_all(&state.pool)
@@ -1819,7 +2397,7 @@ pub async fn broadlink_list_commands(
     } else {
         sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
             "SELECT id, device_id, name, slug, code, code_type, category \
-             FROM broadlink_commands ORDER BY created_at",
+             FROM broadlink_commands ORDER BY created_at, id",
         )
         .fetch_all(&state.pool)
         .await
@@ -1868,6 +2446,7 @@ pub async fn broadlink_add_command(
 
     match result {
         Ok((id,)) => {
+            warn_on_slug_collision(&state.pool, id, &body.slug).await;
             let cmd = BroadlinkCommand {
                 id,
                 device_id: body.device_id,
@@ -1888,7 +2467,7 @@ pub async fn broadlink_add_command(
 
 pub async fn broadlink_update_command(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
     Json(body): Json<UpdateCommandBody>,
 ) -> impl IntoResponse {
     let result = sqlx::query(
@@ -1912,7 +2491,12 @@ pub async fn broadlink_update_command(
 
     match result {
         Ok(r) if r.rows_affected() == 0 => StatusCode::NOT_FOUND.into_response(),
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Ok(_) => {
+            if let Some(slug) = &body.slug {
+                warn_on_slug_collision(&state.pool, id, slug).await;
+            }
+            StatusCode::NO_CONTENT.into_response()
+        }
         Err(e) => {
             tracing::error!("broadlink_update_command: {e}");
             StatusCode::INTERNAL_SERVER_ERROR.into_response()
@@ -1922,7 +2506,7 @@ pub async fn broadlink_update_command(
 
 pub async fn broadlink_remove_command(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
 ) -> impl IntoResponse {
     let result = sqlx::query("DELETE FROM broadlink_commands WHERE id = $1")
         .bind(id)
@@ -1941,7 +2525,7 @@ pub async fn broadlink_remove_command(
 
 pub async fn broadlink_start_learn(
     State(state): State<AppState>,
-    Path(device_id): Path<Uuid>,
+    ApiPath(device_id): ApiPath<Uuid>,
     Json(body): Json<Option<LearnBody>>,
 ) -> impl IntoResponse {
     // Prevent concurrent learns
@@ -1962,14 +2546,14 @@ pub async fn broadlink_start_learn(
         .unwrap_or_else(|| "ir".to_string());
 
     // Fetch device info
-    let device = sqlx::query_as::<_, (String, String, String)>(
-        "SELECT host, mac, device_type FROM broadlink_devices WHERE id = $1",
+    let device = sqlx::query_as::<_, (String, String, String, Option<i32>)>(
+        "SELECT host, mac, device_type, port FROM broadlink_devices WHERE id = $1",
     )
     .bind(device_id)
     .fetch_optional(&state.pool)
     .await;
 
-    let (host, mac, devtype) = match device {
+    let (host, mac, devtype, port) = match device {
         Ok(Some(row)) => row,
         Ok(None) => {
             state
@@ -1994,16 +2578,19 @@ pub async fn broadlink_start_learn(
     let learn_tx = state.broadlink_connector.learn_tx.clone();
 
     tokio::spawn(async move {
+        let port = port.map(|p| p as u16);
         let result =
-            crate::broadlink::learn_code(&host, &mac, &devtype, &signal_type).await;
+            crate::broadlink::learn_code(&host, &mac, &devtype, &signal_type, port).await;
         let event = match result {
             Ok(lr) => crate::connectors::broadlink::BroadlinkLearnEvent {
                 code: lr.code,
                 error: lr.error,
+                quality: lr.quality,
             },
             Err(e) => crate::connectors::broadlink::BroadlinkLearnEvent {
                 code: None,
                 error: Some(e),
+                quality: None,
             },
         };
         let _ = learn_tx.send(event);
@@ -2021,12 +2608,39 @@ pub async fn broadlink_cancel_learn(State(state): State<AppState>) -> impl IntoR
     StatusCode::NO_CONTENT.into_response()
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendCommandQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Send a stored code. With `?dryRun=true` the command is resolved and its
+/// device pinged instead, and the would-be send is returned.
 pub async fn broadlink_send_command(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ApiPath(id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<SendCommandQuery>,
 ) -> impl IntoResponse {
-    let row = sqlx::query_as::<_, (String, String, String, String)>(
-        "SELECT bc.code, bd.host, bd.mac, bd.device_type \
+    if query.dry_run {
+        return match crate::connectors::broadlink::dry_run_command(&state.pool, &state.ws_clients, id)
+            .await
+        {
+            Ok(Some(result)) => Json(result).into_response(),
+            Ok(None) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": "Command not found" })),
+            )
+                .into_response(),
+            Err(e) => {
+                tracing::error!("broadlink_send_command dry run: {e}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        };
+    }
+
+    let row = sqlx::query_as::<_, (String, String, String, String, Option<i32>)>(
+        "SELECT bc.code, bd.host, bd.mac, bd.device_type, bd.port \
          FROM broadlink_commands bc \
          JOIN broadlink_devices bd ON bc.device_id = bd.id \
          WHERE bc.id = $1",
@@ -2035,7 +2649,7 @@ pub async fn broadlink_send_command(
     .fetch_optional(&state.pool)
     .await;
 
-    let (code, host, mac, devtype) = match row {
+    let (code, host, mac, devtype, port) = match row {
         Ok(Some(r)) => r,
         Ok(None) => {
             return (
@@ -2050,7 +2664,7 @@ pub async fn broadlink_send_command(
         }
     };
 
-    match crate::broadlink::send_code(&host, &mac, &devtype, &code).await {
+    match crate::broadlink::send_code(&host, &mac, &devtype, &code, port.map(|p| p as u16)).await {
         Ok(r) if r.success => StatusCode::NO_CONTENT.into_response(),
         Ok(r) => (
             StatusCode::BAD_GATEWAY,
@@ -2063,3 +2677,325 @@ pub async fn broadlink_send_command(
         }
     }
 }
+
+// ── Broadlink code packs ──────────────────────────────────────────────────────
+
+/// Current version of the shareable RF/IR pack format.
+const RFIR_PACK_VERSION: u32 = 1;
+
+/// Device description inside a pack. Host and MAC are site-specific and are
+/// never exported.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RfirPackDevice {
+    /// Pack-local reference used by commands; not a database id.
+    key: String,
+    name: String,
+    device_type: String,
+    model: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RfirPackCommand {
+    device_key: Option<String>,
+    name: String,
+    slug: String,
+    code: String,
+    code_type: String,
+    category: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RfirPack {
+    version: u32,
+    exported_at: chrono::DateTime<Utc>,
+    devices: Vec<RfirPackDevice>,
+    commands: Vec<RfirPackCommand>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPackBody {
+    pack: RfirPack,
+    target_device_id: Uuid,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    #[serde(default)]
+    format: ReportFormat,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RfirReportRow {
+    name: String,
+    slug: String,
+    category: String,
+    signal_type: String,
+    device: Option<String>,
+    device_model: Option<String>,
+    /// `None` when the stored code doesn't decode.
+    timing: Option<crate::broadlink::TimingSummary>,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `GET /api/connectors/broadlink/report?format=json|csv` — every stored
+/// command with its device and decoded timing summary, for documentation.
+/// Unlike the pack export it omits the codes themselves.
+pub async fn broadlink_rfir_report(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ReportQuery>,
+) -> impl IntoResponse {
+    let rows = sqlx::query_as::<_, (String, String, String, String, String, Option<String>, Option<String>)>(
+        "SELECT bc.name, bc.slug, bc.category, bc.code_type, bc.code, bd.name, bd.model \
+         FROM broadlink_commands bc \
+         LEFT JOIN broadlink_devices bd ON bd.id = bc.device_id \
+         ORDER BY bc.category, bc.name",
+    )
+    .fetch_all(&state.pool)
+    .await;
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("broadlink_rfir_report: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let report: Vec<RfirReportRow> = rows
+        .into_iter()
+        .map(|(name, slug, category, signal_type, code, device, device_model)| RfirReportRow {
+            timing: crate::broadlink::timing_summary(&code)
+                .inspect_err(|e| tracing::debug!("Report: cannot decode '{slug}': {e}"))
+                .ok(),
+            name,
+            slug,
+            category,
+            signal_type,
+            device,
+            device_model,
+        })
+        .collect();
+
+    match query.format {
+        ReportFormat::Json => Json(report).into_response(),
+        ReportFormat::Csv => {
+            let mut csv = String::from("name,slug,category,signal_type,device,device_model,pulses,duration_ms\n");
+            for row in &report {
+                let (pulses, duration) = match &row.timing {
+                    Some(t) => (t.pulses.to_string(), format!("{:.1}", t.duration_ms)),
+                    None => (String::new(), String::new()),
+                };
+                let fields = [
+                    csv_field(&row.name),
+                    csv_field(&row.slug),
+                    csv_field(&row.category),
+                    csv_field(&row.signal_type),
+                    csv_field(row.device.as_deref().unwrap_or("")),
+                    csv_field(row.device_model.as_deref().unwrap_or("")),
+                    pulses,
+                    duration,
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            (
+                [
+                    (axum::http::header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (
+                        axum::http::header::CONTENT_DISPOSITION,
+                        "attachment; filename=\"rfir-report.csv\"",
+                    ),
+                ],
+                csv,
+            )
+                .into_response()
+        }
+    }
+}
+
+pub async fn broadlink_export_pack(State(state): State<AppState>) -> impl IntoResponse {
+    let devices = sqlx::query_as::<_, (Uuid, String, String, Option<String>)>(
+        "SELECT id, name, device_type, model FROM broadlink_devices ORDER BY created_at",
+    )
+    .fetch_all(&state.pool)
+    .await;
+    let commands = sqlx::query_as::<_, (Option<Uuid>, String, String, String, String, String)>(
+        "SELECT device_id, name, slug, code, code_type, category \
+         FROM broadlink_commands ORDER BY created_at",
+    )
+    .fetch_all(&state.pool)
+    .await;
+
+    let (devices, commands) = match (devices, commands) {
+        (Ok(d), Ok(c)) => (d, c),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("broadlink_export_pack: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // Replace database ids with short pack-local keys so nothing site-specific leaks.
+    let keys: std::collections::HashMap<Uuid, String> = devices
+        .iter()
+        .enumerate()
+        .map(|(i, (id, ..))| (*id, format!("device-{}", i + 1)))
+        .collect();
+
+    let pack = RfirPack {
+        version: RFIR_PACK_VERSION,
+        exported_at: Utc::now(),
+        devices: devices
+            .into_iter()
+            .map(|(id, name, device_type, model)| RfirPackDevice {
+                key: keys[&id].clone(),
+                name,
+                device_type,
+                model,
+            })
+            .collect(),
+        commands: commands
+            .into_iter()
+            .map(|(device_id, name, slug, code, code_type, category)| RfirPackCommand {
+                device_key: device_id.and_then(|id| keys.get(&id).cloned()),
+                name,
+                slug,
+                code,
+                code_type,
+                category,
+            })
+            .collect(),
+    };
+    Json(pack).into_response()
+}
+
+pub async fn broadlink_import_pack(
+    State(state): State<AppState>,
+    Json(body): Json<ImportPackBody>,
+) -> impl IntoResponse {
+    if body.pack.version > RFIR_PACK_VERSION {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "error": format!("Unsupported pack version {}", body.pack.version) })),
+        )
+            .into_response();
+    }
+
+    let exists: Result<Option<(Uuid,)>, _> =
+        sqlx::query_as("SELECT id FROM broadlink_devices WHERE id = $1")
+            .bind(body.target_device_id)
+            .fetch_optional(&state.pool)
+            .await;
+    match exists {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": "Device not found" })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!("broadlink_import_pack fetch device: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let result: Result<(u64, u64), sqlx::Error> = async {
+        let mut tx = state.pool.begin().await?;
+        let mut imported = 0;
+        let mut skipped = 0;
+        for cmd in &body.pack.commands {
+            // Existing slugs on the target device are kept untouched.
+            let r = sqlx::query(
+                "INSERT INTO broadlink_commands (device_id, name, slug, code, code_type, category) \
+                 VALUES ($1, $2, $3, $4, $5, $6) \
+                 ON CONFLICT (device_id, slug) DO NOTHING",
+            )
+            .bind(body.target_device_id)
+            .bind(&cmd.name)
+            .bind(&cmd.slug)
+            .bind(&cmd.code)
+            .bind(&cmd.code_type)
+            .bind(&cmd.category)
+            .execute(&mut *tx)
+            .await?;
+            if r.rows_affected() > 0 {
+                imported += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+        tx.commit().await?;
+        Ok((imported, skipped))
+    }
+    .await;
+
+    match result {
+        Ok((imported, skipped)) => {
+            Json(json!({ "imported": imported, "skipped": skipped })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("broadlink_import_pack: {e}");
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({ "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ── Broadlink sequences ───────────────────────────────────────────────────────
+
+pub async fn broadlink_list_sequences(State(state): State<AppState>) -> impl IntoResponse {
+    Json(crate::connectors::broadlink::load_sequences(&state.pool).await)
+}
+
+pub async fn broadlink_save_sequences(
+    State(state): State<AppState>,
+    Json(body): Json<Vec<crate::connectors::broadlink::BroadlinkSequence>>,
+) -> impl IntoResponse {
+    match crate::connectors::broadlink::save_sequences(&state.pool, &body).await {
+        Ok(()) => (StatusCode::OK, Json(body)).into_response(),
+        Err(e) => {
+            tracing::error!("broadlink_save_sequences: {e}");
+            (StatusCode::BAD_REQUEST, Json(json!({ "error": e.to_string() }))).into_response()
+        }
+    }
+}
+
+/// Start a saved sequence. Steps run in the background; progress arrives as
+/// `broadlink.sequence.progress` WebSocket messages.
+pub async fn broadlink_execute_sequence(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<Uuid>,
+) -> impl IntoResponse {
+    match crate::connectors::broadlink::execute_sequence(&state.pool, &state.ws_clients, id).await {
+        Ok(steps) => (StatusCode::ACCEPTED, Json(json!({ "steps": steps }))).into_response(),
+        Err(e) if e == "not_found" => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Sequence not found" })),
+        )
+            .into_response(),
+        Err(e) => (StatusCode::CONFLICT, Json(json!({ "error": e }))).into_response(),
+    }
+}
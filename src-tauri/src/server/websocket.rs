@@ -1,15 +1,16 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
         FromRequestParts, Query, Request, State,
     },
     http::{header, StatusCode},
-    response::{IntoResponse, Response},
+    response::{sse, IntoResponse, Response},
 };
 use futures_util::{SinkExt, StreamExt};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::poll_fn;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -36,7 +37,7 @@ use crate::server::AppState;
 // ── Connected client registry ─────────────────────────────────────────────────
 
 /// Metadata about a single connected WebSocket client.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WsClientInfo {
     pub id: Uuid,
@@ -51,17 +52,24 @@ pub struct WsClientInfo {
     /// Timestamp when the last ping was sent — not serialised.
     #[serde(skip)]
     pub ping_sent_at: Option<chrono::DateTime<Utc>>,
+    /// Connected from this machine (the desktop UI itself); such clients
+    /// don't keep the idle auto-stop at bay. Not serialised.
+    #[serde(skip)]
+    pub loopback: bool,
 }
 
 // ── Incoming WebSocket command types ─────────────────────────────────────────
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
 enum WsCommand {
     // ── Keynote (macOS only) ─────────────────────────────────────────────────
     #[cfg(target_os = "macos")]
     #[serde(rename = "keynote.open")]
-    KeynoteOpen { file_path: String },
+    KeynoteOpen {
+        file_path: String,
+        start_slide: Option<u32>,
+    },
     #[cfg(target_os = "macos")]
     #[serde(rename = "keynote.next")]
     KeynoteNext,
@@ -78,6 +86,9 @@ enum WsCommand {
     #[serde(rename = "keynote.goto")]
     KeynoteGoto { slide: u32 },
     #[cfg(target_os = "macos")]
+    #[serde(rename = "keynote.jump")]
+    KeynoteJump { offset: i32 },
+    #[cfg(target_os = "macos")]
     #[serde(rename = "keynote.start")]
     KeynoteStart,
     #[cfg(target_os = "macos")]
@@ -95,9 +106,16 @@ enum WsCommand {
     #[serde(rename = "ppt.folders.list")]
     PptFoldersList,
     #[serde(rename = "ppt.folders.add")]
-    PptFoldersAdd { path: String, name: String },
+    PptFoldersAdd {
+        path: String,
+        name: String,
+        #[serde(default)]
+        extensions: Option<Vec<String>>,
+    },
     #[serde(rename = "ppt.folders.remove")]
     PptFoldersRemove { id: Uuid },
+    #[serde(rename = "ppt.folders.validate")]
+    PptFoldersValidate,
     // ── Events ───────────────────────────────────────────────────────────────
     #[serde(rename = "events.list")]
     EventsList,
@@ -147,6 +165,9 @@ enum WsCommand {
         recording_id: Uuid,
         delete_file: Option<bool>,
     },
+    /// Rename a recording file within its directory; replies with the new path.
+    #[serde(rename = "recordings.rename")]
+    RecordingsRename { old_path: String, new_name: String },
     #[serde(rename = "recordings.flag_upload")]
     RecordingsFlagUpload {
         event_id: Uuid,
@@ -195,6 +216,20 @@ enum WsCommand {
     // ── Uploads ──────────────────────────────────────────────────────────────
     #[serde(rename = "uploads.trigger")]
     UploadsTrigger,
+    /// Stop every pending or in-progress upload; replies with the count.
+    #[serde(rename = "uploads.cancel_all")]
+    UploadsCancelAll,
+    /// Reply with the previous upload of a file with the same checksum, if any.
+    #[serde(rename = "uploads.check")]
+    UploadsCheck { path: String },
+    #[serde(rename = "uploads.defaults.get")]
+    UploadsDefaultsGet,
+    /// Replace the default YouTube privacy and description template.
+    #[serde(rename = "uploads.defaults.set")]
+    UploadsDefaultsSet {
+        youtube_privacy_status: String,
+        description_template: String,
+    },
     // ── Connectors ───────────────────────────────────────────────────────────
     #[serde(rename = "connectors.status")]
     ConnectorsStatus,
@@ -210,6 +245,9 @@ enum WsCommand {
     ConnectorsFacebookStreamKey,
     #[serde(rename = "connectors.youtube.content")]
     ConnectorsYoutubeContent,
+    /// Latest uploads of the authenticated channel (default 10, max 50).
+    #[serde(rename = "connectors.youtube.uploads")]
+    ConnectorsYoutubeUploads { max_results: Option<u32> },
     // ── Auth ─────────────────────────────────────────────────────────────────
     #[serde(rename = "auth.youtube.url")]
     AuthYoutubeUrl,
@@ -261,6 +299,8 @@ enum WsCommand {
     },
     #[serde(rename = "broadlink.commands.remove")]
     BroadlinkCommandsRemove { id: Uuid },
+    #[serde(rename = "broadlink.commands.validate")]
+    BroadlinkCommandsValidate,
     #[serde(rename = "broadlink.learn.start")]
     BroadlinkLearnStart {
         device_id: Uuid,
@@ -268,8 +308,22 @@ enum WsCommand {
     },
     #[serde(rename = "broadlink.learn.cancel")]
     BroadlinkLearnCancel,
+    /// With `dry_run`, resolve the command and ping its device without sending.
     #[serde(rename = "broadlink.commands.send")]
-    BroadlinkCommandsSend { id: Uuid },
+    BroadlinkCommandsSend {
+        id: Uuid,
+        #[serde(default)]
+        dry_run: bool,
+    },
+    #[serde(rename = "broadlink.sequences.list")]
+    BroadlinkSequencesList,
+    /// Replace all saved sequences.
+    #[serde(rename = "broadlink.sequences.save")]
+    BroadlinkSequencesSave {
+        sequences: Vec<crate::connectors::broadlink::BroadlinkSequence>,
+    },
+    #[serde(rename = "broadlink.sequences.execute")]
+    BroadlinkSequencesExecute { id: Uuid },
     // ── Presenter ────────────────────────────────────────────────────────────
     /// Register a human-readable label and hostname for this connection (shown in the UI).
     #[serde(rename = "presenter.register")]
@@ -283,6 +337,10 @@ enum WsCommand {
     /// Pong reply from a client in response to a `ping` message.
     #[serde(rename = "pong")]
     Pong { ping_id: i64 },
+    /// Limit pushed messages on this connection to `types` (exact, or prefix
+    /// with a trailing `.*`). An empty list restores the default of everything.
+    #[serde(rename = "subscribe")]
+    Subscribe { types: Vec<String> },
     #[serde(rename = "presenter.load")]
     PresenterLoad { file_path: String },
     #[serde(rename = "presenter.unload")]
@@ -297,6 +355,9 @@ enum WsCommand {
     PresenterLast,
     #[serde(rename = "presenter.goto")]
     PresenterGoto { slide: u32 },
+    /// Move relative to the current slide; negative offsets go backwards.
+    #[serde(rename = "presenter.jump")]
+    PresenterJump { offset: i32 },
     #[serde(rename = "presenter.status")]
     PresenterStatus,
     #[serde(rename = "presenter.mute")]
@@ -316,9 +377,17 @@ enum WsCommand {
     /// Toggle the active presentation backend; closes any running presentation first.
     #[serde(rename = "presentation.set_use_web_presenter")]
     PresentationSetUseWebPresenter { enabled: bool },
+    /// Set how often the `presentation.status` change poller checks for
+    /// changes, clamped to 200 ms–10 s; persisted in app_settings.
+    #[serde(rename = "presentation.set_poll_interval")]
+    PresentationSetPollInterval { interval_ms: u64 },
     /// Open a file: routes to web presenter or Keynote based on the stored setting.
+    /// `start_slide` resumes at that slide, clamped to the deck's last slide.
     #[serde(rename = "presentation.open")]
-    PresentationOpen { file_path: String },
+    PresentationOpen {
+        file_path: String,
+        start_slide: Option<u32>,
+    },
     /// Start the slideshow (Keynote only; shows notification in web presenter mode).
     #[serde(rename = "presentation.start")]
     PresentationStart,
@@ -341,12 +410,42 @@ enum WsCommand {
     PresentationLast,
     #[serde(rename = "presentation.goto")]
     PresentationGoto { slide: u32 },
+    /// Skip `offset` slides (negative goes back), clamped to the deck boundaries.
+    #[serde(rename = "presentation.jump")]
+    PresentationJump { offset: i32 },
     /// Mute (blank) the active presentation display.
     #[serde(rename = "presentation.mute")]
     PresentationMute,
     /// Unmute the active presentation display.
     #[serde(rename = "presentation.unmute")]
     PresentationUnmute,
+    /// Flip mute (blank) on the active presentation; in Keynote mode sends blank
+    /// as a keystroke toggle since the current state can't be read.
+    #[serde(rename = "presentation.toggle_mute")]
+    PresentationToggleMute,
+    // ── OBS output control ───────────────────────────────────────────────────
+    /// Request live streaming/recording status with timecodes (reply to sender only).
+    #[serde(rename = "obs.status")]
+    ObsStatus,
+    #[serde(rename = "obs.stream.start")]
+    ObsStreamStart,
+    #[serde(rename = "obs.stream.stop")]
+    ObsStreamStop,
+    #[serde(rename = "obs.record.start")]
+    ObsRecordStart,
+    #[serde(rename = "obs.record.stop")]
+    ObsRecordStop,
+    /// Reply with the scene list and current program scene.
+    #[serde(rename = "obs.scenes.list")]
+    ObsScenesList,
+    #[serde(rename = "obs.scene.set")]
+    ObsSceneSet { scene_name: String },
+    /// Show or hide a source within a scene.
+    #[serde(rename = "obs.source.toggle")]
+    ObsSourceToggle { scene_name: String, source_name: String, enabled: bool },
+    /// Replace the text of an OBS GDI+/FreeType text source.
+    #[serde(rename = "obs.text.set")]
+    ObsTextSet { source_name: String, text: String },
     // ── OBS Devices ──────────────────────────────────────────────────────────
     #[serde(rename = "obs.devices.scan")]
     ObsDevicesScan,
@@ -368,6 +467,205 @@ enum WsCommand {
     ObsListenersDelete { id: Uuid },
 }
 
+// ── Outgoing WebSocket message types ─────────────────────────────────────────
+
+/// Messages the server pushes to every client (also streamed by
+/// `/api/v1/events`), plus the generic command replies and the status
+/// replies that are also pushed. Schema only: the messages are built with
+/// `json!` where they are sent, so update this enum alongside any change to
+/// one. Other replies named after a command (`events.list`,
+/// `broadlink.devices.add`, ...) carry the same payload as the matching REST
+/// endpoint and aren't repeated here.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(tag = "type")]
+pub enum WsServerMessage {
+    /// First message on every connection.
+    #[serde(rename = "connected", rename_all = "camelCase")]
+    Connected { server_id: String },
+    /// Reply to a command that succeeded without a payload.
+    #[serde(rename = "ok")]
+    Ok,
+    /// Reply to a command that failed.
+    #[serde(rename = "error")]
+    Error { message: String },
+    /// Latency probe; answer with `pong` and the same id.
+    #[serde(rename = "ping", rename_all = "camelCase")]
+    Ping { ping_id: i64 },
+    #[serde(rename = "notification")]
+    Notification { level: NotificationLevel, message: String },
+    #[serde(rename = "clients.updated")]
+    ClientsUpdated { clients: Vec<WsClientInfo> },
+
+    // ── Connectors ───────────────────────────────────────────────────────
+    #[serde(rename = "connector.status")]
+    ConnectorStatus { connector: String, status: ConnectorStatus },
+    #[serde(rename = "connector.state", rename_all = "camelCase")]
+    ConnectorState {
+        connector: String,
+        is_streaming: Option<bool>,
+        is_recording: Option<bool>,
+    },
+    /// Reply to `connectors.state`; `obs` is null while OBS is disconnected.
+    #[serde(rename = "connectors.state")]
+    ConnectorsState { obs: Option<OutputFlags> },
+    #[serde(rename = "obs.state", rename_all = "camelCase")]
+    ObsState { is_streaming: bool, is_recording: bool },
+    /// Pushed when OBS outputs change, and the reply to `obs.status`.
+    #[serde(rename = "obs.status")]
+    ObsStatus { status: crate::connectors::obs::ObsOutputStatus },
+    /// Pushed after each OBS device rescan and on connect, and the reply to
+    /// `obs.devices.available`.
+    #[serde(rename = "obs.devices.available", rename_all = "camelCase")]
+    ObsDevicesAvailable {
+        devices: crate::obs_devices::ObsAvailableDevices,
+        listener_statuses: Vec<crate::obs_devices::DeviceListenerStatus>,
+    },
+    #[serde(rename = "obs.scene", rename_all = "camelCase")]
+    ObsScene { scene_name: String },
+    #[serde(rename = "cron.youtube_pull", rename_all = "camelCase")]
+    CronYoutubePull { has_live: bool },
+
+    // ── Events and recordings ────────────────────────────────────────────
+    /// `data.record` is an `Event` as returned by `GET /api/events/{id}`.
+    #[serde(rename = "event.changed")]
+    EventChanged { data: RowChange },
+    /// `data.record` is a `Recording` as returned by the recordings endpoints.
+    #[serde(rename = "recording.changed")]
+    RecordingChanged { data: RowChange },
+    #[serde(rename = "recording.detected", rename_all = "camelCase")]
+    RecordingDetected { file_name: String, event_title: Option<String> },
+    #[serde(rename = "recording.untracked.removed")]
+    RecordingUntrackedRemoved { id: Uuid },
+
+    // ── Uploads ──────────────────────────────────────────────────────────
+    #[serde(rename = "upload.progress", rename_all = "camelCase")]
+    UploadProgress {
+        recording_id: Uuid,
+        platform: String,
+        progress_bytes: i64,
+        total_bytes: i64,
+        chunk_size: i64,
+    },
+    #[serde(rename = "upload.completed", rename_all = "camelCase")]
+    UploadCompleted { recording_id: Uuid, platform: String, video_id: String, video_url: String },
+    #[serde(rename = "upload.failed", rename_all = "camelCase")]
+    UploadFailed { recording_id: Uuid, platform: String, error: String },
+    #[serde(rename = "upload.paused", rename_all = "camelCase")]
+    UploadPaused { recording_id: Uuid, reason: String },
+
+    // ── Presentation ─────────────────────────────────────────────────────
+    #[serde(rename = "caption.changed")]
+    CaptionChanged { caption: Option<crate::server::caption::LiveCaption> },
+    #[serde(rename = "keynote.status")]
+    KeynoteStatus { status: crate::connectors::keynote::KeynoteStatus },
+    #[serde(rename = "presenter.state")]
+    PresenterState { state: crate::server::presenter::PresenterState },
+    #[serde(rename = "presenter.slide_changed", rename_all = "camelCase")]
+    PresenterSlideChanged { current_slide: u32, total_slides: u32, notes: String },
+    #[serde(rename = "presentation.settings", rename_all = "camelCase")]
+    PresentationSettings { use_web_presenter: bool },
+    /// Pushed by the change poller, and the reply to `presentation.status`.
+    #[serde(rename = "presentation.status")]
+    PresentationStatus { status: PresentationStatus },
+    /// Reply to `presentation.set_poll_interval` with the interval in effect.
+    #[serde(rename = "presentation.poll_interval", rename_all = "camelCase")]
+    PresentationPollInterval { interval_ms: u64 },
+    #[serde(rename = "ppt.folders_changed")]
+    PptFoldersChanged,
+
+    // ── Broadlink ────────────────────────────────────────────────────────
+    /// Reply to `broadlink.status`.
+    #[serde(rename = "broadlink.status")]
+    BroadlinkStatus { status: ConnectorStatus },
+    #[serde(rename = "broadlink.device.discovered")]
+    BroadlinkDeviceDiscovered { device: DiscoveredBroadlinkDevice },
+    #[serde(rename = "broadlink.learn.result")]
+    BroadlinkLearnResult {
+        code: Option<String>,
+        error: Option<String>,
+        quality: Option<crate::broadlink::CodeQuality>,
+    },
+    #[serde(rename = "broadlink.dry_run")]
+    BroadlinkDryRun { result: crate::connectors::broadlink::DryRunResult },
+    #[serde(rename = "broadlink.sequence.progress", rename_all = "camelCase")]
+    BroadlinkSequenceProgress {
+        sequence_id: Uuid,
+        step: usize,
+        total: usize,
+        command_id: Uuid,
+        success: bool,
+        error: Option<String>,
+    },
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+/// Payload of `event.changed` / `recording.changed`.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+pub struct RowChange {
+    /// `INSERT`, `UPDATE` or `DELETE`.
+    operation: String,
+    record: serde_json::Value,
+}
+
+/// Payload of `connectors.state`.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputFlags {
+    is_streaming: bool,
+    is_recording: bool,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredBroadlinkDevice {
+    name: String,
+    host: String,
+    mac: String,
+    device_type: String,
+    model: Option<String>,
+}
+
+/// Standalone JSON schema for the WebSocket protocol, derived from
+/// [`WsCommand`] (client → server), which is what the server parses, and
+/// [`WsServerMessage`] (server → client), which is kept in step with the
+/// `json!` sends by hand. Both are in `definitions`; the root accepts either.
+pub fn protocol_schema() -> serde_json::Value {
+    let mut gen = schemars::gen::SchemaSettings::draft07().into_generator();
+    let command = gen.subschema_for::<WsCommand>();
+    let server = gen.subschema_for::<WsServerMessage>();
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "WebSocket protocol",
+        "anyOf": [command, server],
+        "definitions": gen.take_definitions(),
+    })
+}
+
+/// The [`WsCommand`] and [`WsServerMessage`] schemas and the nested types they
+/// reference, keyed by name and referenced from `#/components/schemas/`, for
+/// merging into the OpenAPI document.
+pub fn protocol_schemas_for_openapi() -> schemars::Map<String, schemars::schema::Schema> {
+    let mut settings = schemars::gen::SchemaSettings::draft2019_09();
+    settings.definitions_path = "#/components/schemas/".to_string();
+    settings.meta_schema = None;
+    let mut gen = settings.into_generator();
+    gen.subschema_for::<WsCommand>();
+    gen.subschema_for::<WsServerMessage>();
+    gen.take_definitions()
+}
+
 /// Upsert or delete bible references inside an open transaction (mirrors routes.rs logic).
 async fn ws_upsert_bible_references(
     event_id: Uuid,
@@ -407,6 +705,28 @@ async fn ws_upsert_bible_references(
 
 /// Build a unified `presentation.status` JSON string from current backend state.
 async fn make_presentation_status(state: &AppState) -> String {
+    json!({
+        "type": "presentation.status",
+        "status": presentation_status(state).await,
+    })
+    .to_string()
+}
+
+/// Slideshow state as reported by `presentation.status`, the same shape for
+/// the web presenter and Keynote.
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationStatus {
+    pub app_running: bool,
+    pub slideshow_active: bool,
+    pub current_slide: Option<u32>,
+    pub total_slides: Option<u32>,
+    pub document_name: Option<String>,
+    pub blanked: bool,
+}
+
+/// Current presentation state for whichever backend is active.
+pub(crate) async fn presentation_status(state: &AppState) -> PresentationStatus {
     let (app_running, slideshow_active, current_slide, total_slides, document_name, blanked) =
         if state.use_web_presenter.load(Ordering::Relaxed) {
             let ps = state.presenter_state.read().await;
@@ -423,27 +743,23 @@ async fn make_presentation_status(state: &AppState) -> String {
             #[cfg(target_os = "macos")]
             {
                 let s = state.keynote_connector.get_status().await;
-                (s.app_running, s.slideshow_active, s.current_slide, s.total_slides, s.document_name, false)
+                (s.app_running, s.slideshow_active, s.current_slide, s.total_slides, s.document_name, s.blanked)
             }
             #[cfg(not(target_os = "macos"))]
             { (false, false, None::<u32>, None::<u32>, None::<String>, false) }
         };
-    json!({
-        "type": "presentation.status",
-        "status": {
-            "appRunning": app_running,
-            "slideshowActive": slideshow_active,
-            "currentSlide": current_slide,
-            "totalSlides": total_slides,
-            "documentName": document_name,
-            "blanked": blanked,
-        }
-    })
-    .to_string()
+    PresentationStatus {
+        app_running,
+        slideshow_active,
+        current_slide,
+        total_slides,
+        document_name,
+        blanked,
+    }
 }
 
-async fn broadcast_presentation_status(
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+pub(crate) async fn broadcast_presentation_status(
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     state: &AppState,
 ) {
     let msg = make_presentation_status(state).await;
@@ -453,8 +769,95 @@ async fn broadcast_presentation_status(
     }
 }
 
+/// Default and bounds for the `presentation.status` change poller.
+pub const DEFAULT_PRESENTATION_POLL_MS: u64 = 1000;
+const MIN_PRESENTATION_POLL_MS: u64 = 200;
+const MAX_PRESENTATION_POLL_MS: u64 = 10_000;
+
+/// Demand-started poller that broadcasts `presentation.status` whenever it
+/// changes, including changes made on the presenting machine itself. It runs
+/// only while at least one connection has subscribed to `presentation.status`
+/// (or `presentation.*`). Status reads come from cached backend state, so the
+/// poller never drives AppleScript itself.
+pub struct PresentationWatch {
+    watchers: std::sync::atomic::AtomicUsize,
+    running: std::sync::atomic::AtomicBool,
+    interval_ms: std::sync::atomic::AtomicU64,
+    /// Cuts the poller's sleep short when the interval changes or the last
+    /// watcher leaves.
+    wake: tokio::sync::Notify,
+}
+
+impl PresentationWatch {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            watchers: std::sync::atomic::AtomicUsize::new(0),
+            running: std::sync::atomic::AtomicBool::new(false),
+            interval_ms: std::sync::atomic::AtomicU64::new(clamp_poll_interval(interval_ms)),
+            wake: tokio::sync::Notify::new(),
+        }
+    }
+
+    fn set_interval(&self, interval_ms: u64) {
+        self.interval_ms.store(interval_ms, Ordering::Relaxed);
+        self.wake.notify_one();
+    }
+}
+
+fn clamp_poll_interval(interval_ms: u64) -> u64 {
+    interval_ms.clamp(MIN_PRESENTATION_POLL_MS, MAX_PRESENTATION_POLL_MS)
+}
+
+/// Whether a `subscribe` type list asks for presentation status updates.
+fn wants_presentation_status(types: &[String]) -> bool {
+    types.iter().any(|t| t == "presentation.status" || t == "presentation.*")
+}
+
+fn watch_presentation(state: &AppState) {
+    state.presentation_watch.watchers.fetch_add(1, Ordering::SeqCst);
+    if !state.presentation_watch.running.swap(true, Ordering::SeqCst) {
+        tokio::spawn(presentation_poller(state.clone()));
+    }
+}
+
+fn unwatch_presentation(state: &AppState) {
+    if state.presentation_watch.watchers.fetch_sub(1, Ordering::SeqCst) == 1 {
+        state.presentation_watch.wake.notify_one();
+    }
+}
+
+async fn presentation_poller(state: AppState) {
+    let watch = &state.presentation_watch;
+    let mut last: Option<PresentationStatus> = None;
+    loop {
+        if watch.watchers.load(Ordering::SeqCst) == 0 {
+            watch.running.store(false, Ordering::SeqCst);
+            // A subscriber may have arrived between the check and the store;
+            // keep going if nobody else took over.
+            if watch.watchers.load(Ordering::SeqCst) == 0 || watch.running.swap(true, Ordering::SeqCst) {
+                return;
+            }
+        }
+        let status = presentation_status(&state).await;
+        if last.as_ref() != Some(&status) {
+            let msg = json!({ "type": "presentation.status", "status": status }).to_string();
+            let clients = state.ws_clients.read().await;
+            for tx in clients.values() {
+                let _ = tx.send(Message::Text(msg.clone().into()));
+            }
+            drop(clients);
+            last = Some(status);
+        }
+        let interval = watch.interval_ms.load(Ordering::Relaxed);
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_millis(interval)) => {}
+            _ = watch.wake.notified() => {}
+        }
+    }
+}
+
 async fn broadcast_notification(
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     level: &str,
     message: &str,
 ) {
@@ -465,25 +868,41 @@ async fn broadcast_notification(
     }
 }
 
-fn ws_ok(tx: &mpsc::UnboundedSender<Message>) {
+fn ws_ok(tx: &WsClientSender) {
     let _ = tx.send(Message::Text(json!({"type":"ok"}).to_string().into()));
 }
 
-fn ws_error(tx: &mpsc::UnboundedSender<Message>, msg: &str) {
+fn ws_error(tx: &WsClientSender, msg: &str) {
     let _ = tx.send(Message::Text(json!({"type":"error","message":msg}).to_string().into()));
 }
 
 async fn handle_ws_command(
     cmd: WsCommand,
     state: &AppState,
-    client_tx: &mpsc::UnboundedSender<Message>,
+    client_tx: &WsClientSender,
     client_id: Uuid,
 ) {
     match cmd {
         // ── Keynote (macOS only) ─────────────────────────────────────────────
         #[cfg(target_os = "macos")]
-        WsCommand::KeynoteOpen { file_path } => {
-            let _ = state.keynote_connector.open_file(&file_path).await;
+        WsCommand::KeynoteOpen { file_path, start_slide } => {
+            let file_path = match ppt::resolve_allowed_file(&state.pool, &file_path).await {
+                Ok(path) => path,
+                Err(e) => {
+                    ws_error(client_tx, &e.to_string());
+                    return;
+                }
+            };
+            match start_slide {
+                Some(slide) => {
+                    if let Err(e) = state.keynote_connector.open_file_at(&file_path, slide).await {
+                        ws_error(client_tx, &e);
+                    }
+                }
+                None => {
+                    let _ = state.keynote_connector.open_file(&file_path).await;
+                }
+            }
         }
         #[cfg(target_os = "macos")]
         WsCommand::KeynoteNext => {
@@ -506,6 +925,10 @@ async fn handle_ws_command(
             let _ = state.keynote_connector.goto(slide).await;
         }
         #[cfg(target_os = "macos")]
+        WsCommand::KeynoteJump { offset } => {
+            let _ = state.keynote_connector.jump(offset).await;
+        }
+        #[cfg(target_os = "macos")]
         WsCommand::KeynoteStart => {
             let _ = state.keynote_connector.start_slideshow().await;
         }
@@ -533,12 +956,7 @@ async fn handle_ws_command(
             }
         }
         WsCommand::PptFoldersList => {
-            match sqlx::query_as::<_, ppt::PptFolder>(
-                "SELECT id, path, name, sort_order FROM ppt_folders ORDER BY sort_order, name",
-            )
-            .fetch_all(&state.pool)
-            .await
-            {
+            match ppt::list_folders_internal(&state.pool).await {
                 Ok(folders) => {
                     let msg = json!({ "type": "ppt.folders.list", "folders": folders }).to_string();
                     let _ = client_tx.send(Message::Text(msg.into()));
@@ -546,20 +964,32 @@ async fn handle_ws_command(
                 Err(e) => ws_error(client_tx, &e.to_string()),
             }
         }
-        WsCommand::PptFoldersAdd { path, name } => {
-            let result = sqlx::query_as::<_, ppt::PptFolder>(
-                "INSERT INTO ppt_folders (path, name) VALUES ($1, $2) \
-                 ON CONFLICT (path) DO UPDATE SET name = EXCLUDED.name \
-                 RETURNING id, path, name, sort_order",
-            )
-            .bind(&path)
-            .bind(&name)
-            .fetch_optional(&state.pool)
-            .await;
-            match result {
-                Ok(folder) => {
+        WsCommand::PptFoldersAdd { path, name, extensions } => {
+            let path = match ppt::normalize_folder_path(&path).await {
+                Ok(path) => path,
+                Err(e) => {
+                    ws_error(client_tx, &e);
+                    return;
+                }
+            };
+            match ppt::add_folder_checked(&state.pool, &path, &name, extensions).await {
+                Ok((folder, warnings)) => {
                     broadcast_ppt_folders_changed(&state.ws_clients).await;
-                    let msg = json!({ "type": "ppt.folders.add", "folder": folder }).to_string();
+                    let msg = json!({ "type": "ppt.folders.add", "folder": folder, "warnings": warnings })
+                        .to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
+        WsCommand::PptFoldersValidate => {
+            match ppt::list_folders_internal(&state.pool).await {
+                Ok(folders) => {
+                    let msg = json!({
+                        "type": "ppt.folders.validate",
+                        "overlaps": ppt::find_folder_overlaps(&folders),
+                    })
+                    .to_string();
                     let _ = client_tx.send(Message::Text(msg.into()));
                 }
                 Err(e) => ws_error(client_tx, &e.to_string()),
@@ -614,6 +1044,8 @@ async fn handle_ws_command(
                 let _ = target_tx.send(Message::Text(ping_msg.into()));
             }
         }
+        // Handled per connection in `handle_socket`, which owns the filter.
+        WsCommand::Subscribe { .. } => {}
         WsCommand::Pong { ping_id } => {
             let now = Utc::now();
             {
@@ -628,6 +1060,13 @@ async fn handle_ws_command(
         }
         // ── Presenter ────────────────────────────────────────────────────────
         WsCommand::PresenterLoad { file_path } => {
+            let file_path = match ppt::resolve_allowed_file(&state.pool, &file_path).await {
+                Ok(path) => path,
+                Err(e) => {
+                    ws_error(client_tx, &e.to_string());
+                    return;
+                }
+            };
             let result = tokio::task::spawn_blocking(move || presenter::parse_pptx(&file_path)).await;
             match result {
                 Ok(Ok(parsed)) => {
@@ -670,6 +1109,11 @@ async fn handle_ws_command(
             broadcast_presenter_slide_changed(&state.ws_clients, &*state.presenter_state.read().await).await;
             broadcast_presentation_status(&state.ws_clients, state).await;
         }
+        WsCommand::PresenterJump { offset } => {
+            state.presenter_state.write().await.jump(offset);
+            broadcast_presenter_slide_changed(&state.ws_clients, &*state.presenter_state.read().await).await;
+            broadcast_presentation_status(&state.ws_clients, state).await;
+        }
         WsCommand::PresenterStatus => {
             let ps = state.presenter_state.read().await;
             let msg = serde_json::json!({ "type": "presenter.state", "state": &*ps }).to_string();
@@ -725,12 +1169,55 @@ async fn handle_ws_command(
                 let _ = tx.send(Message::Text(msg.clone().into()));
             }
         }
-        WsCommand::PresentationOpen { file_path } => {
+        WsCommand::PresentationSetPollInterval { interval_ms } => {
+            let interval_ms = clamp_poll_interval(interval_ms);
+            if let Err(e) = sqlx::query(
+                "INSERT INTO app_settings (key, value) VALUES ('presentation_poll_interval_ms', $1) \
+                 ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()",
+            )
+            .bind(interval_ms.to_string())
+            .execute(&state.pool)
+            .await
+            {
+                tracing::error!("presentation.set_poll_interval persist: {e}");
+                ws_error(client_tx, &e.to_string());
+                return;
+            }
+            state.presentation_watch.set_interval(interval_ms);
+            let msg = json!({ "type": "presentation.poll_interval", "intervalMs": interval_ms }).to_string();
+            let _ = client_tx.send(Message::Text(msg.into()));
+        }
+        WsCommand::PresentationOpen { file_path, start_slide } => {
+            if start_slide == Some(0) {
+                ws_error(client_tx, "start_slide must be at least 1");
+                return;
+            }
+            let file_path = match ppt::resolve_allowed_file(&state.pool, &file_path).await {
+                Ok(path) => path,
+                Err(e) => {
+                    ws_error(client_tx, &e.to_string());
+                    return;
+                }
+            };
+            if !ppt::is_presentation_file(&file_path) {
+                // Non-presentation documents (e.g. PDFs) just open in their default app.
+                if !ppt::is_allowed_document(&file_path) {
+                    ws_error(client_tx, "Only documents can be opened in their default app");
+                    return;
+                }
+                if let Err(e) = tauri_plugin_opener::open_path(&file_path, None::<&str>) {
+                    ws_error(client_tx, &e.to_string());
+                }
+                return;
+            }
             if state.use_web_presenter.load(Ordering::Relaxed) {
                 let result = tokio::task::spawn_blocking(move || presenter::parse_pptx(&file_path)).await;
                 match result {
                     Ok(Ok(parsed)) => {
-                        let new_state = presenter::PresenterState::from_parsed(parsed);
+                        let mut new_state = presenter::PresenterState::from_parsed(parsed);
+                        if let Some(slide) = start_slide {
+                            new_state.go_to(slide);
+                        }
                         *state.presenter_state.write().await = new_state;
                         broadcast_presenter_state(&state.ws_clients, &*state.presenter_state.read().await).await;
                     }
@@ -739,7 +1226,16 @@ async fn handle_ws_command(
                 }
             } else {
                 #[cfg(target_os = "macos")]
-                { let _ = state.keynote_connector.open_file(&file_path).await; }
+                match start_slide {
+                    Some(slide) => {
+                        if let Err(e) = state.keynote_connector.open_file_at(&file_path, slide).await {
+                            ws_error(client_tx, &e);
+                        }
+                    }
+                    None => {
+                        let _ = state.keynote_connector.open_file(&file_path).await;
+                    }
+                }
             }
             broadcast_presentation_status(&state.ws_clients, state).await;
         }
@@ -833,11 +1329,26 @@ async fn handle_ws_command(
             }
             broadcast_presentation_status(&state.ws_clients, state).await;
         }
+        WsCommand::PresentationJump { offset } => {
+            if state.use_web_presenter.load(Ordering::Relaxed) {
+                state.presenter_state.write().await.jump(offset);
+                broadcast_presenter_slide_changed(&state.ws_clients, &*state.presenter_state.read().await).await;
+            } else {
+                #[cfg(target_os = "macos")]
+                { let _ = state.keynote_connector.jump(offset).await; }
+            }
+            broadcast_presentation_status(&state.ws_clients, state).await;
+        }
         WsCommand::PresentationMute => {
             if state.use_web_presenter.load(Ordering::Relaxed) {
                 state.presenter_state.write().await.mute();
                 broadcast_presenter_state(&state.ws_clients, &*state.presenter_state.read().await).await;
                 broadcast_presentation_status(&state.ws_clients, state).await;
+            } else {
+                #[cfg(target_os = "macos")]
+                if let Err(e) = state.keynote_connector.set_blank(true).await {
+                    ws_error(client_tx, &e);
+                }
             }
         }
         WsCommand::PresentationUnmute => {
@@ -845,6 +1356,16 @@ async fn handle_ws_command(
                 state.presenter_state.write().await.unmute();
                 broadcast_presenter_state(&state.ws_clients, &*state.presenter_state.read().await).await;
                 broadcast_presentation_status(&state.ws_clients, state).await;
+            } else {
+                #[cfg(target_os = "macos")]
+                if let Err(e) = state.keynote_connector.set_blank(false).await {
+                    ws_error(client_tx, &e);
+                }
+            }
+        }
+        WsCommand::PresentationToggleMute => {
+            if let Err(e) = ppt::toggle_mute_internal(state).await {
+                ws_error(client_tx, &e);
             }
         }
         // ── Events ───────────────────────────────────────────────────────────
@@ -1224,7 +1745,20 @@ async fn handle_ws_command(
         }
         WsCommand::RecordingsFlagUpload { event_id, recordings } => {
             let result: anyhow::Result<()> = async {
+                let defaults = crate::uploader::load_upload_defaults(&state.pool).await;
                 for item in &recordings {
+                    let description = match &item.custom_description {
+                        None if item.use_description_template => Some(
+                            crate::uploader::render_event_description(
+                                &state.pool,
+                                event_id,
+                                item.custom_title.as_deref(),
+                                &defaults.description_template,
+                            )
+                            .await?,
+                        ),
+                        d => d.clone(),
+                    };
                     sqlx::query(
                         "UPDATE recordings SET uploadable=true, \
                          custom_title=COALESCE($1, custom_title), \
@@ -1232,14 +1766,17 @@ async fn handle_ws_command(
                          updated_at=NOW() WHERE id=$3 AND event_id=$4",
                     )
                     .bind(item.custom_title.as_deref())
-                    .bind(item.custom_description.as_deref())
+                    .bind(description.as_deref())
                     .bind(item.recording_id)
                     .bind(event_id)
                     .execute(&state.pool)
                     .await?;
                     for platform in &item.platforms {
                         let visibility = if platform == "youtube" {
-                            item.youtube_visibility.as_deref().unwrap_or("private").to_string()
+                            item.youtube_visibility
+                                .as_deref()
+                                .unwrap_or(&defaults.youtube_privacy_status)
+                                .to_string()
                         } else {
                             item.facebook_visibility.as_deref().unwrap_or("ONLY_ME").to_string()
                         };
@@ -1266,6 +1803,15 @@ async fn handle_ws_command(
                 Err(e) => ws_error(client_tx, &e.to_string()),
             }
         }
+        WsCommand::RecordingsRename { old_path, new_name } => {
+            match crate::server::routes::rename_recording_file(&state.pool, &old_path, &new_name).await {
+                Ok(path) => {
+                    let msg = json!({ "type": "recordings.rename", "path": path }).to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Err(e) => ws_error(client_tx, &e),
+            }
+        }
         // ── Untracked recordings ─────────────────────────────────────────────
         WsCommand::RecordingsUntrackedList => {
             match untracked_recording::list_untracked(&state.pool).await {
@@ -1560,6 +2106,37 @@ async fn handle_ws_command(
             });
             ws_ok(client_tx);
         }
+        WsCommand::UploadsCancelAll => match state.upload_service.cancel_all().await {
+            Ok(cancelled) => {
+                let msg = json!({ "type": "uploads.cancel_all", "cancelled": cancelled }).to_string();
+                let _ = client_tx.send(Message::Text(msg.into()));
+            }
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::UploadsCheck { path } => {
+            match crate::uploader::check_already_uploaded(&state.pool, &path).await {
+                Ok(previous) => {
+                    let msg = json!({ "type": "uploads.check", "path": path, "previous": previous }).to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
+        WsCommand::UploadsDefaultsGet => {
+            let defaults = crate::uploader::load_upload_defaults(&state.pool).await;
+            let msg = json!({ "type": "uploads.defaults.get", "defaults": defaults }).to_string();
+            let _ = client_tx.send(Message::Text(msg.into()));
+        }
+        WsCommand::UploadsDefaultsSet { youtube_privacy_status, description_template } => {
+            let defaults = crate::uploader::UploadDefaults {
+                youtube_privacy_status,
+                description_template,
+            };
+            match crate::uploader::save_upload_defaults(&state.pool, &defaults).await {
+                Ok(()) => ws_ok(client_tx),
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
         // ── Connectors ───────────────────────────────────────────────────────
         WsCommand::ConnectorsStatus => {
             let obs = state.obs_connector.get_status().await;
@@ -1677,7 +2254,7 @@ async fn handle_ws_command(
             struct StreamItem { cdn: Cdn }
             #[derive(serde::Deserialize)]
             struct StreamList { items: Option<Vec<StreamItem>> }
-            let client = reqwest::Client::new();
+            let client = crate::http::http_client();
             let resp = client
                 .get("https://www.googleapis.com/youtube/v3/liveStreams")
                 .query(&[("part", "cdn"), ("mine", "true")])
@@ -1727,7 +2304,7 @@ async fn handle_ws_command(
             }
             #[derive(serde::Deserialize)]
             struct FbList { data: Vec<FbLiveVideo> }
-            let client = reqwest::Client::new();
+            let client = crate::http::http_client();
             let resp = client
                 .get(format!("https://graph.facebook.com/v19.0/{}/live_videos", config.page_id))
                 .query(&[
@@ -1769,6 +2346,20 @@ async fn handle_ws_command(
                 Err(e) => ws_error(client_tx, &e.to_string()),
             }
         }
+        WsCommand::ConnectorsYoutubeUploads { max_results } => {
+            let config = state.youtube_config.read().await.clone();
+            match youtube::fetch_recent_uploads(&state.pool, &config, max_results.unwrap_or(10)).await {
+                Ok(uploads) => {
+                    let msg = json!({ "type": "connectors.youtube.uploads", "uploads": uploads }).to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Err(e) if e.is::<youtube::AuthRequired>() => {
+                    state.youtube_connector.stop().await;
+                    ws_error(client_tx, "auth_required");
+                }
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
         // ── Auth ─────────────────────────────────────────────────────────────
         WsCommand::AuthYoutubeUrl => {
             let config = state.youtube_config.read().await.clone();
@@ -1835,8 +2426,8 @@ async fn handle_ws_command(
             let _ = client_tx.send(Message::Text(msg.into()));
         }
         WsCommand::BroadlinkDevicesList => {
-            let rows = sqlx::query_as::<_, (Uuid, String, String, Option<String>, String, String, bool)>(
-                "SELECT id, name, device_type, model, host, mac, is_default FROM broadlink_devices ORDER BY created_at",
+            let rows = sqlx::query_as::<_, (Uuid, String, String, Option<String>, String, String, bool, Option<i32>)>(
+                "SELECT id, name, device_type, model, host, mac, is_default, port FROM broadlink_devices ORDER BY created_at",
             )
             .fetch_all(&state.pool)
             .await;
@@ -1844,8 +2435,8 @@ async fn handle_ws_command(
                 Ok(devices) => {
                     let list: Vec<serde_json::Value> = devices
                         .into_iter()
-                        .map(|(id, name, device_type, model, host, mac, is_default)| {
-                            json!({ "id": id, "name": name, "deviceType": device_type, "model": model, "host": host, "mac": mac, "isDefault": is_default })
+                        .map(|(id, name, device_type, model, host, mac, is_default, port)| {
+                            json!({ "id": id, "name": name, "deviceType": device_type, "model": model, "host": host, "mac": mac, "isDefault": is_default, "port": port })
                         })
                         .collect();
                     let msg = json!({ "type": "broadlink.devices.list", "devices": list }).to_string();
@@ -1928,7 +2519,7 @@ async fn handle_ws_command(
                 if let Some(cat) = category {
                     sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
                         "SELECT id, device_id, name, slug, code, code_type, category \
-                         FROM broadlink_commands WHERE device_id=$1 AND category=$2 ORDER BY created_at",
+                         FROM broadlink_commands WHERE device_id=$1 AND category=$2 ORDER BY created_at, id",
                     )
                     .bind(did)
                     .bind(cat)
@@ -1937,7 +2528,7 @@ async fn handle_ws_command(
                 } else {
                     sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
                         "SELECT id, device_id, name, slug, code, code_type, category \
-                         FROM broadlink_commands WHERE device_id=$1 ORDER BY created_at",
+                         FROM broadlink_commands WHERE device_id=$1 ORDER BY created_at, id",
                     )
                     .bind(did)
                     .fetch_all(&state.pool)
@@ -1946,7 +2537,7 @@ async fn handle_ws_command(
             } else {
                 sqlx::query_as::<_, (Uuid, Option<Uuid>, String, String, String, String, String)>(
                     "SELECT id, device_id, name, slug, code, code_type, category \
-                     FROM broadlink_commands ORDER BY created_at",
+                     FROM broadlink_commands ORDER BY created_at, id",
                 )
                 .fetch_all(&state.pool)
                 .await
@@ -1981,6 +2572,7 @@ async fn handle_ws_command(
             .await;
             match result {
                 Ok((id,)) => {
+                    crate::server::routes::warn_on_slug_collision(&state.pool, id, &slug).await;
                     let cmd = json!({ "id": id, "deviceId": device_id, "name": name, "slug": slug, "code": code, "codeType": code_type, "category": cat });
                     let msg = json!({ "type": "broadlink.commands.add", "command": cmd }).to_string();
                     let _ = client_tx.send(Message::Text(msg.into()));
@@ -2005,6 +2597,9 @@ async fn handle_ws_command(
             .await;
             match result {
                 Ok(Some(row)) => {
+                    if let Some(slug) = &slug {
+                        crate::server::routes::warn_on_slug_collision(&state.pool, id, slug).await;
+                    }
                     let cmd = json!({
                         "id": row.get::<Uuid, _>("id"),
                         "deviceId": row.get::<Option<Uuid>, _>("device_id"),
@@ -2032,6 +2627,15 @@ async fn handle_ws_command(
                 Err(e) => ws_error(client_tx, &e.to_string()),
             }
         }
+        WsCommand::BroadlinkCommandsValidate => {
+            match crate::server::routes::find_duplicate_slugs(&state.pool).await {
+                Ok(duplicates) => {
+                    let msg = json!({ "type": "broadlink.commands.validate", "duplicates": duplicates }).to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
         WsCommand::BroadlinkLearnStart { device_id, signal_type } => {
             if state
                 .broadlink_learn_active
@@ -2042,13 +2646,13 @@ async fn handle_ws_command(
                 return;
             }
             let sig_type = signal_type.unwrap_or_else(|| "ir".to_string());
-            let device = sqlx::query_as::<_, (String, String, String)>(
-                "SELECT host, mac, device_type FROM broadlink_devices WHERE id = $1",
+            let device = sqlx::query_as::<_, (String, String, String, Option<i32>)>(
+                "SELECT host, mac, device_type, port FROM broadlink_devices WHERE id = $1",
             )
             .bind(device_id)
             .fetch_optional(&state.pool)
             .await;
-            let (host, mac, devtype) = match device {
+            let (host, mac, devtype, port) = match device {
                 Ok(Some(row)) => row,
                 Ok(None) => {
                     state.broadlink_learn_active.store(false, Ordering::SeqCst);
@@ -2064,10 +2668,11 @@ async fn handle_ws_command(
             let learn_active = state.broadlink_learn_active.clone();
             let learn_tx = state.broadlink_connector.learn_tx.clone();
             tokio::spawn(async move {
-                let result = crate::broadlink::learn_code(&host, &mac, &devtype, &sig_type).await;
+                let port = port.map(|p| p as u16);
+                let result = crate::broadlink::learn_code(&host, &mac, &devtype, &sig_type, port).await;
                 let event = match result {
-                    Ok(lr) => crate::connectors::broadlink::BroadlinkLearnEvent { code: lr.code, error: lr.error },
-                    Err(e) => crate::connectors::broadlink::BroadlinkLearnEvent { code: None, error: Some(e) },
+                    Ok(lr) => crate::connectors::broadlink::BroadlinkLearnEvent { code: lr.code, error: lr.error, quality: lr.quality },
+                    Err(e) => crate::connectors::broadlink::BroadlinkLearnEvent { code: None, error: Some(e), quality: None },
                 };
                 let _ = learn_tx.send(event);
                 learn_active.store(false, Ordering::SeqCst);
@@ -2079,9 +2684,19 @@ async fn handle_ws_command(
             state.broadlink_learn_active.store(false, Ordering::SeqCst);
             ws_ok(client_tx);
         }
-        WsCommand::BroadlinkCommandsSend { id } => {
-            let row = sqlx::query_as::<_, (String, String, String, String)>(
-                "SELECT bc.code, bd.host, bd.mac, bd.device_type \
+        WsCommand::BroadlinkCommandsSend { id, dry_run: true } => {
+            match crate::connectors::broadlink::dry_run_command(&state.pool, &state.ws_clients, id).await {
+                Ok(Some(result)) => {
+                    let msg = json!({ "type": "broadlink.commands.send", "dryRun": result }).to_string();
+                    let _ = client_tx.send(Message::Text(msg.into()));
+                }
+                Ok(None) => ws_error(client_tx, "not_found"),
+                Err(e) => ws_error(client_tx, &e),
+            }
+        }
+        WsCommand::BroadlinkCommandsSend { id, dry_run: false } => {
+            let row = sqlx::query_as::<_, (String, String, String, String, Option<i32>)>(
+                "SELECT bc.code, bd.host, bd.mac, bd.device_type, bd.port \
                  FROM broadlink_commands bc \
                  JOIN broadlink_devices bd ON bc.device_id = bd.id \
                  WHERE bc.id = $1",
@@ -2089,17 +2704,81 @@ async fn handle_ws_command(
             .bind(id)
             .fetch_optional(&state.pool)
             .await;
-            let (code, host, mac, devtype) = match row {
+            let (code, host, mac, devtype, port) = match row {
                 Ok(Some(r)) => r,
                 Ok(None) => { ws_error(client_tx, "not_found"); return; }
                 Err(e) => { ws_error(client_tx, &e.to_string()); return; }
             };
-            match crate::broadlink::send_code(&host, &mac, &devtype, &code).await {
+            match crate::broadlink::send_code(&host, &mac, &devtype, &code, port.map(|p| p as u16)).await {
                 Ok(r) if r.success => ws_ok(client_tx),
                 Ok(r) => ws_error(client_tx, &r.error.unwrap_or_default()),
                 Err(e) => ws_error(client_tx, &e.to_string()),
             }
         }
+        WsCommand::BroadlinkSequencesList => {
+            let sequences = crate::connectors::broadlink::load_sequences(&state.pool).await;
+            let msg = json!({ "type": "broadlink.sequences.list", "sequences": sequences }).to_string();
+            let _ = client_tx.send(Message::Text(msg.into()));
+        }
+        WsCommand::BroadlinkSequencesSave { sequences } => {
+            match crate::connectors::broadlink::save_sequences(&state.pool, &sequences).await {
+                Ok(()) => ws_ok(client_tx),
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
+        WsCommand::BroadlinkSequencesExecute { id } => {
+            match crate::connectors::broadlink::execute_sequence(&state.pool, &state.ws_clients, id).await {
+                Ok(_) => ws_ok(client_tx),
+                Err(e) => ws_error(client_tx, &e),
+            }
+        }
+        // ── OBS output control ────────────────────────────────────────────────
+        WsCommand::ObsStatus => match state.obs_connector.output_status().await {
+            Ok(status) => {
+                let msg = json!({ "type": "obs.status", "status": status }).to_string();
+                let _ = client_tx.send(Message::Text(msg.into()));
+            }
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsStreamStart => match state.obs_connector.start_streaming().await {
+            Ok(()) => ws_ok(client_tx),
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsStreamStop => match state.obs_connector.stop_streaming().await {
+            Ok(()) => ws_ok(client_tx),
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsRecordStart => match state.obs_connector.start_recording().await {
+            Ok(()) => ws_ok(client_tx),
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsRecordStop => match state.obs_connector.stop_recording().await {
+            Ok(_) => ws_ok(client_tx),
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsScenesList => match state.obs_connector.list_scenes().await {
+            Ok(list) => {
+                let msg = json!({ "type": "obs.scenes.list", "currentScene": list.current_scene, "scenes": list.scenes }).to_string();
+                let _ = client_tx.send(Message::Text(msg.into()));
+            }
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsSceneSet { scene_name } => match state.obs_connector.set_scene(&scene_name).await {
+            Ok(()) => ws_ok(client_tx),
+            Err(e) => ws_error(client_tx, &e.to_string()),
+        },
+        WsCommand::ObsSourceToggle { scene_name, source_name, enabled } => {
+            match state.obs_connector.set_source_enabled(&scene_name, &source_name, enabled).await {
+                Ok(()) => ws_ok(client_tx),
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
+        WsCommand::ObsTextSet { source_name, text } => {
+            match state.obs_connector.set_text(&source_name, &text).await {
+                Ok(()) => ws_ok(client_tx),
+                Err(e) => ws_error(client_tx, &e.to_string()),
+            }
+        }
         // ── OBS Devices ───────────────────────────────────────────────────────
         WsCommand::ObsDevicesScan => {
             let _ = state.obs_connector.devices_tx.send(());
@@ -2277,8 +2956,13 @@ pub async fn ws_handler(State(state): State<AppState>, req: Request) -> Response
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
 
-    let current_token = state.auth_token.read().await.clone();
-    let is_authenticated = query.token.as_deref() == Some(current_token.as_str());
+    let is_authenticated = match query.token.as_deref() {
+        // Scope-limited API keys connect read-only: WS commands aren't mapped to scopes.
+        Some(token) => crate::server::auth::authenticate(&state, token)
+            .await
+            .is_some_and(|credential| credential.is_unrestricted()),
+        None => false,
+    };
 
     let ws = match WebSocketUpgrade::from_request_parts(&mut parts, &state).await {
         Ok(ws) => ws,
@@ -2287,16 +2971,166 @@ pub async fn ws_handler(State(state): State<AppState>, req: Request) -> Response
 
     drop(body); // WebSocket requests have no body; release it explicitly.
     let server_id = state.server_id.clone();
+    let loopback = parts
+        .extensions
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .is_some_and(|info| info.0.ip().is_loopback());
     let user_agent = parts
         .headers
         .get(header::USER_AGENT)
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
-    ws.on_upgrade(move |socket| handle_socket(socket, state, server_id, user_agent, is_authenticated))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, server_id, user_agent, is_authenticated, loopback))
+}
+
+/// `GET /api/v1/events` — Server-Sent Events alternative to `/ws` for clients
+/// that can't easily speak WebSocket (`curl -N`, shell scripts, simple
+/// dashboards). Registers as a receive-only client, so it gets exactly the
+/// broadcasts a WS client would; each is sent as a `data:` line with the
+/// message `type` as the SSE event name. Auth is the bearer token, in the
+/// `Authorization` header or, as for `/ws`, the `token` query parameter.
+pub async fn sse_handler(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(peer): axum::extract::ConnectInfo<std::net::SocketAddr>,
+    headers: axum::http::HeaderMap,
+) -> sse::Sse<impl futures_util::Stream<Item = Result<sse::Event, std::convert::Infallible>>> {
+    let client_id = Uuid::new_v4();
+    let overflow = Arc::new(tokio::sync::Notify::new());
+    let (tx, rx) = client_channel(&overflow);
+    state.ws_clients.write().await.insert(client_id, tx);
+    state.ws_client_info.write().await.insert(
+        client_id,
+        WsClientInfo {
+            id: client_id,
+            label: "Event stream".to_string(),
+            user_agent: headers
+                .get(header::USER_AGENT)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            hostname: None,
+            connected_at: Utc::now(),
+            last_pong_at: None,
+            latency_ms: None,
+            ping_sent_at: None,
+            loopback: peer.ip().is_loopback(),
+        },
+    );
+    broadcast_clients_updated(&state).await;
+    let guard = SseClientGuard { state, client_id };
+
+    let stream = futures_util::stream::unfold((rx, guard, overflow), |(mut rx, guard, overflow)| async move {
+        loop {
+            let msg = tokio::select! {
+                msg = rx.recv() => msg?,
+                _ = overflow.notified() => {
+                    tracing::warn!("Dropping SSE client {}: queue full", guard.client_id);
+                    return None;
+                }
+            };
+            match msg {
+                Message::Text(text) => {
+                    let name = serde_json::from_str::<serde_json::Value>(&text)
+                        .ok()
+                        .and_then(|v| v["type"].as_str().map(str::to_string))
+                        .unwrap_or_else(|| "message".to_string());
+                    let event = sse::Event::default().event(name).data(text.as_str());
+                    return Some((Ok(event), (rx, guard, overflow)));
+                }
+                // Shutdown/restart: end the stream so the client reconnects.
+                Message::Close(_) => return None,
+                _ => continue,
+            }
+        }
+    });
+    sse::Sse::new(stream).keep_alive(sse::KeepAlive::default())
+}
+
+/// Unregisters an SSE client once its response stream is dropped.
+struct SseClientGuard {
+    state: AppState,
+    client_id: Uuid,
+}
+
+impl Drop for SseClientGuard {
+    fn drop(&mut self) {
+        let state = self.state.clone();
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            state.ws_clients.write().await.remove(&client_id);
+            state.ws_client_info.write().await.remove(&client_id);
+            broadcast_clients_updated(&state).await;
+        });
+    }
 }
 
 /// WS commands that read-only (unauthenticated) clients are permitted to send.
-const READONLY_ALLOWED: &[&str] = &["presenter.register", "presenter.status", "pong"];
+const READONLY_ALLOWED: &[&str] = &["presenter.register", "presenter.status", "pong", "subscribe"];
+
+/// Message types delivered regardless of a connection's subscription.
+const ALWAYS_DELIVERED: &[&str] = &["connected", "ping", "ok", "error"];
+
+/// How long a single socket write may take before the client is considered
+/// too slow and dropped. Broadcasters never wait on a client; this bounds how
+/// long one stuck connection can hold its own queue open.
+const CLIENT_SEND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Messages a client may have queued. A client that falls this far behind,
+/// even one that keeps acking writes within [`CLIENT_SEND_TIMEOUT`], is
+/// disconnected rather than buffered without bound.
+const CLIENT_QUEUE_CAPACITY: usize = 256;
+
+/// Sending half of a client's bounded outgoing queue. `send` never waits: a
+/// full queue flags the client, and its send task drops the connection.
+#[derive(Clone)]
+pub struct WsClientSender {
+    tx: mpsc::Sender<Message>,
+    overflow: Arc<tokio::sync::Notify>,
+}
+
+impl WsClientSender {
+    pub fn send(&self, msg: Message) -> Result<(), mpsc::error::TrySendError<Message>> {
+        let result = self.tx.try_send(msg);
+        if let Err(mpsc::error::TrySendError::Full(_)) = &result {
+            self.overflow.notify_one();
+        }
+        result
+    }
+}
+
+/// A bounded client queue that signals `overflow` when it fills up.
+fn client_channel(overflow: &Arc<tokio::sync::Notify>) -> (WsClientSender, mpsc::Receiver<Message>) {
+    let (tx, rx) = mpsc::channel(CLIENT_QUEUE_CAPACITY);
+    (WsClientSender { tx, overflow: Arc::clone(overflow) }, rx)
+}
+
+/// Per-connection allowlist set via `subscribe`; `None` delivers everything.
+#[derive(Default)]
+struct WsSubscription {
+    types: std::sync::RwLock<Option<HashSet<String>>>,
+}
+
+impl WsSubscription {
+    fn set(&self, types: Vec<String>) {
+        let filter = if types.is_empty() { None } else { Some(types.into_iter().collect()) };
+        *self.types.write().unwrap_or_else(|e| e.into_inner()) = filter;
+    }
+
+    fn allows(&self, msg: &Message) -> bool {
+        let guard = self.types.read().unwrap_or_else(|e| e.into_inner());
+        let Some(types) = guard.as_ref() else { return true };
+        let Message::Text(text) = msg else { return true };
+        let msg_type = serde_json::from_str::<serde_json::Value>(text)
+            .ok()
+            .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_owned))
+            .unwrap_or_default();
+        ALWAYS_DELIVERED.contains(&msg_type.as_str())
+            || types.contains(&msg_type)
+            || types.iter().any(|t| {
+                t.strip_suffix(".*")
+                    .is_some_and(|prefix| msg_type.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('.')))
+            })
+    }
+}
 
 async fn handle_socket(
     socket: WebSocket,
@@ -2304,9 +3138,11 @@ async fn handle_socket(
     server_id: String,
     user_agent: Option<String>,
     is_authenticated: bool,
+    loopback: bool,
 ) {
     let client_id = Uuid::new_v4();
-    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+    let overflow = Arc::new(tokio::sync::Notify::new());
+    let (tx, mut rx) = client_channel(&overflow);
 
     {
         let mut clients = state.ws_clients.write().await;
@@ -2325,6 +3161,7 @@ async fn handle_socket(
                 last_pong_at: None,
                 latency_ms: None,
                 ping_sent_at: None,
+                loopback,
             },
         );
     }
@@ -2407,17 +3244,43 @@ async fn handle_socket(
 
     let (mut ws_sink, mut ws_stream) = socket.split();
 
-    let send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if ws_sink.send(msg).await.is_err() {
-                break;
+    let subscription = Arc::new(WsSubscription::default());
+    let subscription_send = Arc::clone(&subscription);
+    let presentation_watching = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let presentation_watching_recv = Arc::clone(&presentation_watching);
+    // Replies to this client's own commands bypass the subscription filter;
+    // only broadcasts arriving through `ws_clients` are filtered.
+    let (reply_tx, mut reply_rx) = client_channel(&overflow);
+    let mut send_task = tokio::spawn(async move {
+        loop {
+            let msg = tokio::select! {
+                biased;
+                _ = overflow.notified() => {
+                    tracing::warn!("Dropping WebSocket client {client_id}: send queue full");
+                    break;
+                }
+                Some(msg) = reply_rx.recv() => msg,
+                msg = rx.recv() => match msg {
+                    Some(msg) if subscription_send.allows(&msg) => msg,
+                    Some(_) => continue,
+                    None => break,
+                },
+            };
+            let closing = matches!(msg, Message::Close(_));
+            match tokio::time::timeout(CLIENT_SEND_TIMEOUT, ws_sink.send(msg)).await {
+                Ok(Ok(())) if !closing => {}
+                Ok(_) => break,
+                Err(_) => {
+                    tracing::warn!("Dropping WebSocket client {client_id}: send timed out");
+                    break;
+                }
             }
         }
     });
 
     let state_recv = state.clone();
-    let tx_recv = tx.clone();
-    let recv_task = tokio::spawn(async move {
+    let tx_recv = reply_tx;
+    let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = ws_stream.next().await {
             if let Message::Text(text) = msg {
                 if !is_authenticated {
@@ -2433,16 +3296,33 @@ async fn handle_socket(
                         continue;
                     }
                 }
-                if let Ok(cmd) = serde_json::from_str::<WsCommand>(&text) {
-                    handle_ws_command(cmd, &state_recv, &tx_recv, client_id).await;
+                match serde_json::from_str::<WsCommand>(&text) {
+                    Ok(WsCommand::Subscribe { types }) => {
+                        let wants = wants_presentation_status(&types);
+                        if wants != presentation_watching_recv.swap(wants, Ordering::SeqCst) {
+                            if wants {
+                                watch_presentation(&state_recv);
+                            } else {
+                                unwatch_presentation(&state_recv);
+                            }
+                        }
+                        subscription.set(types);
+                        ws_ok(&tx_recv);
+                    }
+                    Ok(cmd) => handle_ws_command(cmd, &state_recv, &tx_recv, client_id).await,
+                    Err(_) => {}
                 }
             }
         }
     });
 
     tokio::select! {
-        _ = send_task => {}
-        _ = recv_task => {}
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
+    }
+
+    if presentation_watching.load(Ordering::SeqCst) {
+        unwatch_presentation(&state);
     }
 
     {
@@ -2456,9 +3336,53 @@ async fn handle_socket(
     broadcast_clients_updated(&state).await;
 }
 
+/// Tell every client the server is going away before dropping the sockets.
+///
+/// Clients first receive `{"type":"error","message":"server_shutting_down"}`
+/// followed by a Close frame with code 1001 (going away). On 1001 clients
+/// should back off (tens of seconds, growing) instead of reconnecting at their
+/// normal rate; any other close or a dropped connection is treated as a
+/// network blip and retried promptly.
+pub(crate) async fn close_all_for_shutdown(state: &AppState) {
+    let error_msg = json!({ "type": "error", "message": "server_shutting_down" }).to_string();
+    let clients = state.ws_clients.read().await;
+    for tx in clients.values() {
+        let _ = tx.send(Message::Text(error_msg.clone().into()));
+        let _ = tx.send(Message::Close(Some(CloseFrame {
+            code: close_code::AWAY,
+            reason: "server shutting down".into(),
+        })));
+    }
+    tracing::info!("Closed {} WebSocket client(s) for shutdown", clients.len());
+}
+
+/// Close every WebSocket connection because the server moved to `addr`.
+///
+/// Clients receive `{"type":"error","message":"server_restarting","address":…,"port":…}`
+/// followed by a Close frame with code 1012 (service restart), and should
+/// reconnect promptly on the new address.
+pub(crate) async fn close_all_for_restart(state: &AppState, addr: std::net::SocketAddr) {
+    let error_msg = json!({
+        "type": "error",
+        "message": "server_restarting",
+        "address": addr.ip().to_string(),
+        "port": addr.port(),
+    })
+    .to_string();
+    let clients = state.ws_clients.read().await;
+    for tx in clients.values() {
+        let _ = tx.send(Message::Text(error_msg.clone().into()));
+        let _ = tx.send(Message::Close(Some(CloseFrame {
+            code: close_code::RESTART,
+            reason: "server restarting".into(),
+        })));
+    }
+    tracing::info!("Closed {} WebSocket client(s) for restart", clients.len());
+}
+
 pub async fn start_notify_listener(
     connection_url: String,
-    ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     app_state: AppState,
 ) {
     let (client, mut connection) =
@@ -2545,7 +3469,7 @@ pub async fn broadcast_clients_updated(state: &AppState) {
 
 /// Broadcast a `presenter.state` message to all WebSocket clients.
 pub async fn broadcast_presenter_state(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     state: &presenter::PresenterState,
 ) {
     let msg = json!({ "type": "presenter.state", "state": state }).to_string();
@@ -2557,13 +3481,14 @@ pub async fn broadcast_presenter_state(
 
 /// Broadcast a `presenter.slide_changed` message to all WebSocket clients.
 pub async fn broadcast_presenter_slide_changed(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     state: &presenter::PresenterState,
 ) {
     let msg = json!({
         "type": "presenter.slide_changed",
         "currentSlide": state.current_slide,
         "totalSlides": state.total_slides,
+        "notes": state.notes_for(state.current_slide),
     })
     .to_string();
     let guard = clients.read().await;
@@ -2574,7 +3499,7 @@ pub async fn broadcast_presenter_slide_changed(
 
 /// Broadcast a `ppt.folders_changed` message when PPT folders are added/removed.
 pub async fn broadcast_ppt_folders_changed(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
 ) {
     let msg = json!({ "type": "ppt.folders_changed" }).to_string();
     let guard = clients.read().await;
@@ -2585,7 +3510,7 @@ pub async fn broadcast_ppt_folders_changed(
 
 /// Broadcast a `recording.detected` message when OBS stops recording.
 pub async fn broadcast_recording_detected(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     file_name: &str,
     event_title: Option<&str>,
 ) {
@@ -2603,7 +3528,7 @@ pub async fn broadcast_recording_detected(
 
 /// Broadcast a `recording.untracked.removed` message when an untracked recording is assigned.
 pub async fn broadcast_untracked_removed(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     untracked_id: Uuid,
 ) {
     let msg = json!({
@@ -2718,11 +3643,12 @@ pub fn spawn_scheduling_tasks(event: Event, state: AppState) {
 
 /// Broadcast `upload.progress` to all connected WebSocket clients.
 pub async fn broadcast_upload_progress(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     platform: &str,
     progress_bytes: i64,
     total_bytes: i64,
+    chunk_size: i64,
 ) {
     let msg = json!({
         "type": "upload.progress",
@@ -2730,6 +3656,7 @@ pub async fn broadcast_upload_progress(
         "platform": platform,
         "progressBytes": progress_bytes,
         "totalBytes": total_bytes,
+        "chunkSize": chunk_size,
     })
     .to_string();
     let guard = clients.read().await;
@@ -2740,7 +3667,7 @@ pub async fn broadcast_upload_progress(
 
 /// Broadcast `upload.completed` to all connected WebSocket clients.
 pub async fn broadcast_upload_completed(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     platform: &str,
     video_id: &str,
@@ -2762,7 +3689,7 @@ pub async fn broadcast_upload_completed(
 
 /// Broadcast `upload.failed` to all connected WebSocket clients.
 pub async fn broadcast_upload_failed(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     platform: &str,
     error: &str,
@@ -2782,7 +3709,7 @@ pub async fn broadcast_upload_failed(
 
 /// Broadcast `upload.paused` to all connected WebSocket clients.
 pub async fn broadcast_upload_paused(
-    clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     reason: &str,
 ) {
@@ -2798,6 +3725,44 @@ pub async fn broadcast_upload_paused(
     }
 }
 
+/// Broadcast `broadlink.sequence.progress` after each step of a running sequence.
+/// `error` is set on the failed step, which is also the last one reported.
+pub async fn broadcast_broadlink_sequence_progress(
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+    sequence_id: Uuid,
+    step: usize,
+    total: usize,
+    command_id: Uuid,
+    error: Option<&str>,
+) {
+    let msg = json!({
+        "type": "broadlink.sequence.progress",
+        "sequenceId": sequence_id,
+        "step": step,
+        "total": total,
+        "commandId": command_id,
+        "success": error.is_none(),
+        "error": error,
+    })
+    .to_string();
+    let guard = clients.read().await;
+    for tx in guard.values() {
+        let _ = tx.send(Message::Text(msg.clone().into()));
+    }
+}
+
+/// Broadcast `broadlink.dry_run` after a command was resolved without sending it.
+pub async fn broadcast_broadlink_dry_run(
+    clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+    result: &crate::connectors::broadlink::DryRunResult,
+) {
+    let msg = json!({ "type": "broadlink.dry_run", "result": result }).to_string();
+    let guard = clients.read().await;
+    for tx in guard.values() {
+        let _ = tx.send(Message::Text(msg.clone().into()));
+    }
+}
+
 pub async fn write_youtube_result(
     state: &AppState,
     event_id: Uuid,
@@ -1,13 +1,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::ws::Message;
 use tokio::io::AsyncReadExt;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::server::websocket::{
-    broadcast_upload_completed, broadcast_upload_failed, broadcast_upload_progress,
+    broadcast_upload_completed, broadcast_upload_failed, broadcast_upload_progress, WsClientSender,
 };
 
 /// Start a Facebook chunked upload session.
@@ -169,9 +168,10 @@ pub async fn finish_upload(
 }
 
 /// Run the full Facebook chunked upload for a recording.
+/// Returns the new video ID (empty if Facebook didn't report one).
 pub async fn run_upload(
     pool: &sqlx::PgPool,
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     file_path: &str,
     file_size: i64,
@@ -182,8 +182,9 @@ pub async fn run_upload(
     progress_bytes: i64,
     token: &str,
     page_id: &str,
-) -> anyhow::Result<()> {
-    let client = reqwest::Client::new();
+    cancel: super::CancelToken<'_>,
+) -> anyhow::Result<String> {
+    let client = crate::http::http_client();
     let total = file_size as u64;
 
     // Step 1: get or create upload session
@@ -199,15 +200,21 @@ pub async fn run_upload(
             _ => {
                 let (sid, s, e) = start_upload(&client, token, page_id, total).await?;
 
-                sqlx::query(
+                // The state guard keeps a `cancel_all` that ran during
+                // session start from being undone.
+                let persisted = sqlx::query(
                     "UPDATE recording_uploads \
                      SET upload_session_id = $1, state = 'uploading', started_at = NOW(), updated_at = NOW() \
-                     WHERE recording_id = $2 AND platform = 'facebook'",
+                     WHERE recording_id = $2 AND platform = 'facebook' \
+                       AND state IN ('pending', 'paused', 'uploading')",
                 )
                 .bind(&sid)
                 .bind(recording_id)
                 .execute(pool)
                 .await?;
+                if persisted.rows_affected() == 0 {
+                    return Err(super::UploadCancelled.into());
+                }
 
                 (sid, s, e)
             }
@@ -218,6 +225,9 @@ pub async fn run_upload(
         if start_offset >= total {
             break;
         }
+        if cancel.is_cancelled() {
+            return Err(super::UploadCancelled.into());
+        }
 
         match transfer_chunk(
             &client,
@@ -233,18 +243,30 @@ pub async fn run_upload(
         {
             Ok((next_start, next_end)) => {
                 // Persist progress
-                sqlx::query(
+                let persisted = sqlx::query(
                     "UPDATE recording_uploads \
                      SET progress_bytes = $1, total_bytes = $2, updated_at = NOW() \
-                     WHERE recording_id = $3 AND platform = 'facebook'",
+                     WHERE recording_id = $3 AND platform = 'facebook' \
+                       AND state IN ('pending', 'paused', 'uploading')",
                 )
                 .bind(end_offset as i64)
                 .bind(total as i64)
                 .bind(recording_id)
                 .execute(pool)
                 .await?;
+                if persisted.rows_affected() == 0 {
+                    return Err(super::UploadCancelled.into());
+                }
 
-                broadcast_upload_progress(ws_clients, recording_id, "facebook", end_offset as i64, total as i64).await;
+                broadcast_upload_progress(
+                    ws_clients,
+                    recording_id,
+                    "facebook",
+                    end_offset as i64,
+                    total as i64,
+                    (end_offset - start_offset) as i64,
+                )
+                .await;
 
                 start_offset = next_start;
                 end_offset = next_end;
@@ -253,12 +275,14 @@ pub async fn run_upload(
                     break;
                 }
             }
+            Err(_) if cancel.is_cancelled() => return Err(super::UploadCancelled.into()),
             Err(e) => {
                 tracing::error!("Facebook upload chunk error for {recording_id}: {e}");
                 sqlx::query(
                     "UPDATE recording_uploads \
                      SET state = 'failed', error = $1, updated_at = NOW() \
-                     WHERE recording_id = $2 AND platform = 'facebook'",
+                     WHERE recording_id = $2 AND platform = 'facebook' \
+                       AND state IN ('pending', 'paused', 'uploading')",
                 )
                 .bind(e.to_string())
                 .bind(recording_id)
@@ -295,7 +319,7 @@ pub async fn run_upload(
 
             broadcast_upload_completed(ws_clients, recording_id, "facebook", &video_id, &video_url).await;
             tracing::info!("Facebook upload completed for recording {recording_id}: {video_id}");
-            Ok(())
+            Ok(video_id)
         }
         Err(e) => {
             tracing::error!("Facebook finish_upload error for {recording_id}: {e}");
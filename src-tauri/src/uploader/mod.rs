@@ -2,18 +2,207 @@ pub mod facebook;
 pub mod youtube;
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use axum::extract::ws::Message;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::connectors::{
     facebook::FacebookConnector, obs::ObsConnector, youtube::YouTubeConnector, ConnectorStatus,
     FacebookConfig,
 };
-use crate::server::websocket::broadcast_upload_paused;
+use crate::server::websocket::{broadcast_upload_failed, broadcast_upload_paused, WsClientSender};
+
+/// Tauri event emitted once per finished upload; payload `{ recordingId, platform, videoId, title }`.
+pub const UPLOAD_COMPLETE_EVENT: &str = "upload-complete";
+/// Tauri event emitted when an upload ends in error; payload `{ recordingId, platform, title, error }`.
+pub const UPLOAD_FAILED_EVENT: &str = "upload-failed";
+
+const DEFAULT_PRIVACY_KEY: &str = "upload_default_privacy_status";
+const DESCRIPTION_TEMPLATE_KEY: &str = "upload_description_template";
+const PRIVACY_STATUSES: &[&str] = &["public", "private", "unlisted"];
+
+/// Settings-backed defaults applied when recordings are flagged for upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadDefaults {
+    /// YouTube privacy used when a flag request doesn't specify one.
+    pub youtube_privacy_status: String,
+    /// Description template with `{title}`, `{event}`, `{date}` and `{speaker}` placeholders.
+    pub description_template: String,
+}
+
+impl Default for UploadDefaults {
+    fn default() -> Self {
+        Self {
+            youtube_privacy_status: "private".to_string(),
+            description_template: String::new(),
+        }
+    }
+}
+
+pub async fn load_upload_defaults(pool: &PgPool) -> UploadDefaults {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT key, value FROM app_settings WHERE key = ANY($1)",
+    )
+    .bind([DEFAULT_PRIVACY_KEY, DESCRIPTION_TEMPLATE_KEY])
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let mut defaults = UploadDefaults::default();
+    for (key, value) in rows {
+        match key.as_str() {
+            DEFAULT_PRIVACY_KEY => defaults.youtube_privacy_status = value,
+            DESCRIPTION_TEMPLATE_KEY => defaults.description_template = value,
+            _ => {}
+        }
+    }
+    defaults
+}
+
+pub async fn save_upload_defaults(pool: &PgPool, defaults: &UploadDefaults) -> anyhow::Result<()> {
+    if !PRIVACY_STATUSES.contains(&defaults.youtube_privacy_status.as_str()) {
+        anyhow::bail!(
+            "invalid privacy status '{}' (expected one of {})",
+            defaults.youtube_privacy_status,
+            PRIVACY_STATUSES.join(", ")
+        );
+    }
+    for (key, value) in [
+        (DEFAULT_PRIVACY_KEY, &defaults.youtube_privacy_status),
+        (DESCRIPTION_TEMPLATE_KEY, &defaults.description_template),
+    ] {
+        sqlx::query(
+            "INSERT INTO app_settings (key, value) VALUES ($1, $2) \
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = now()",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Expand `{name}` placeholders in `template` from `vars`. Unknown
+/// placeholders are left as-is so typos stay visible in the result.
+pub fn render_upload_description(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) if vars.contains_key(&after[..end]) => {
+                out.push_str(&vars[&after[..end]]);
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Render the stored description template for a recording of `event_id`.
+/// `{title}` is the recording's custom title when given, else the event title.
+pub async fn render_event_description(
+    pool: &PgPool,
+    event_id: Uuid,
+    custom_title: Option<&str>,
+    template: &str,
+) -> anyhow::Result<String> {
+    let (event_title, date_time, speaker): (String, chrono::DateTime<chrono::Utc>, String) =
+        sqlx::query_as("SELECT title, date_time, speaker FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_one(pool)
+            .await?;
+
+    let vars = HashMap::from([
+        ("title", custom_title.unwrap_or(&event_title).to_string()),
+        (
+            "date",
+            date_time
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        ("speaker", speaker),
+        ("event", event_title),
+    ]);
+    Ok(render_upload_description(template, &vars))
+}
+
+/// A file that was uploaded before, identified by its content checksum.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadHistoryEntry {
+    pub file_path: String,
+    pub checksum: String,
+    pub platform: String,
+    pub video_id: String,
+    pub uploaded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Hex-encoded SHA-256 of the file at `path`, read in chunks so multi-GB
+/// recordings don't have to fit in memory.
+pub async fn file_checksum(path: &str) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Returned by `check_already_uploaded` for a path that isn't a recording.
+#[derive(Debug, thiserror::Error)]
+#[error("not a known recording")]
+pub struct UnknownRecording;
+
+/// Most recent upload of a file with the same content as `path`, if any.
+/// The match is by checksum, so renamed or moved copies are still found.
+/// Only paths of known recordings are hashed, so network clients can't use
+/// this to probe or read arbitrary files.
+pub async fn check_already_uploaded(
+    pool: &PgPool,
+    path: &str,
+) -> anyhow::Result<Option<UploadHistoryEntry>> {
+    let known: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM recordings WHERE file_path = $1) \
+             OR EXISTS(SELECT 1 FROM untracked_recordings WHERE file_path = $1)",
+    )
+    .bind(path)
+    .fetch_one(pool)
+    .await?;
+    if !known {
+        return Err(UnknownRecording.into());
+    }
+    let checksum = file_checksum(path).await?;
+    let entry = sqlx::query_as::<_, UploadHistoryEntry>(
+        "SELECT file_path, checksum, platform, video_id, uploaded_at FROM upload_history \
+         WHERE checksum = $1 AND video_id <> '' ORDER BY uploaded_at DESC LIMIT 1",
+    )
+    .bind(&checksum)
+    .fetch_optional(pool)
+    .await?;
+    Ok(entry)
+}
 
 /// Pending upload row joined with recording metadata.
 #[derive(sqlx::FromRow)]
@@ -31,13 +220,34 @@ struct PendingUpload {
     custom_description: Option<String>,
 }
 
+/// Returned by `run_upload` when `UploadService::cancel_all` stopped it.
+#[derive(Debug, thiserror::Error)]
+#[error("upload cancelled")]
+pub struct UploadCancelled;
+
+/// Cancel generation captured when an upload cycle starts. `cancel_all` bumps
+/// the generation, so every cycle started before it winds down.
+#[derive(Clone, Copy)]
+pub struct CancelToken<'a> {
+    generation: &'a AtomicU64,
+    started_at: u64,
+}
+
+impl CancelToken<'_> {
+    pub fn is_cancelled(&self) -> bool {
+        self.generation.load(Ordering::SeqCst) != self.started_at
+    }
+}
+
 pub struct UploadService {
     pool: PgPool,
     youtube_connector: Arc<YouTubeConnector>,
     facebook_connector: Arc<FacebookConnector>,
     obs_connector: Arc<ObsConnector>,
     facebook_config: Arc<RwLock<FacebookConfig>>,
-    ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+    app_handle: Option<tauri::AppHandle>,
+    cancel_generation: AtomicU64,
 }
 
 impl UploadService {
@@ -47,7 +257,8 @@ impl UploadService {
         facebook_connector: Arc<FacebookConnector>,
         obs_connector: Arc<ObsConnector>,
         facebook_config: Arc<RwLock<FacebookConfig>>,
-        ws_clients: Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+        ws_clients: Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
+        app_handle: Option<tauri::AppHandle>,
     ) -> Self {
         Self {
             pool,
@@ -56,6 +267,73 @@ impl UploadService {
             obs_connector,
             facebook_config,
             ws_clients,
+            app_handle,
+            cancel_generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Emit `upload-complete` / `upload-failed` so the desktop UI can notify
+    /// the user when an unattended upload ends.
+    fn emit_outcome(&self, row: &PendingUpload, title: &str, result: &anyhow::Result<String>) {
+        let Some(app) = &self.app_handle else { return };
+        if matches!(result, Err(e) if e.is::<UploadCancelled>()) {
+            return;
+        }
+        use tauri::Emitter;
+        let _ = match result {
+            Ok(video_id) => app.emit(
+                UPLOAD_COMPLETE_EVENT,
+                serde_json::json!({
+                    "recordingId": row.recording_id,
+                    "platform": row.platform,
+                    "videoId": video_id,
+                    "title": title,
+                }),
+            ),
+            Err(e) => app.emit(
+                UPLOAD_FAILED_EVENT,
+                serde_json::json!({
+                    "recordingId": row.recording_id,
+                    "platform": row.platform,
+                    "title": title,
+                    "error": e.to_string(),
+                }),
+            ),
+        };
+    }
+
+    /// Add a finished upload to `upload_history`. Failures are only logged;
+    /// the upload itself already succeeded. Skipped without a video id (a
+    /// recovered upload, or Facebook not returning one): there is nothing
+    /// to point a later duplicate check at.
+    async fn record_history(&self, row: &PendingUpload, video_id: &str) {
+        if video_id.is_empty() {
+            tracing::debug!(
+                "UploadService: no video id for {}, not recording upload history",
+                row.recording_id
+            );
+            return;
+        }
+        let result = async {
+            let checksum = file_checksum(&row.file_path).await?;
+            sqlx::query(
+                "INSERT INTO upload_history (file_path, checksum, platform, video_id) \
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(&row.file_path)
+            .bind(&checksum)
+            .bind(&row.platform)
+            .bind(video_id)
+            .execute(&self.pool)
+            .await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!(
+                "UploadService: recording upload history for {} failed: {e}",
+                row.recording_id
+            );
         }
     }
 
@@ -67,10 +345,53 @@ impl UploadService {
         false
     }
 
+    /// Stop every pending, paused, or in-progress upload. Upload rows are
+    /// marked failed with error `cancelled` and lose their stored session, and
+    /// YouTube sessions are cancelled server-side concurrently (Facebook has
+    /// no cancel endpoint; its sessions expire on their own). Individual cancel
+    /// failures are logged. Returns the number of uploads cancelled.
+    pub async fn cancel_all(&self) -> anyhow::Result<usize> {
+        self.cancel_generation.fetch_add(1, Ordering::SeqCst);
+
+        let rows: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
+            "UPDATE recording_uploads ru \
+             SET state = 'failed', error = 'cancelled', upload_uri = NULL, \
+                 upload_session_id = NULL, updated_at = NOW() \
+             FROM (SELECT recording_id, platform, upload_uri FROM recording_uploads \
+                   WHERE state IN ('pending', 'paused', 'uploading')) old \
+             WHERE ru.recording_id = old.recording_id AND ru.platform = old.platform \
+             RETURNING ru.recording_id, ru.platform, old.upload_uri",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let client = crate::http::http_client();
+        let cancels = rows.iter().filter_map(|(recording_id, platform, uri)| {
+            let uri = uri.as_deref().filter(|_| platform == "youtube")?;
+            let client = &client;
+            Some(async move { (recording_id, youtube::cancel_upload(client, uri).await) })
+        });
+        for (recording_id, result) in futures_util::future::join_all(cancels).await {
+            if let Err(e) = result {
+                tracing::warn!("UploadService: cancelling YouTube session for {recording_id} failed: {e}");
+            }
+        }
+
+        for (recording_id, platform, _) in &rows {
+            broadcast_upload_failed(&self.ws_clients, *recording_id, platform, "cancelled").await;
+        }
+        tracing::info!("UploadService: cancelled {} upload(s)", rows.len());
+        Ok(rows.len())
+    }
+
     /// Process all pending/paused/uploading upload rows.
     /// Pauses any uploading rows if OBS is currently streaming.
     pub async fn run_cycle(&self) {
         tracing::info!("UploadService: starting cycle");
+        let cancel = CancelToken {
+            generation: &self.cancel_generation,
+            started_at: self.cancel_generation.load(Ordering::SeqCst),
+        };
 
         let rows = sqlx::query_as::<_, PendingUpload>(
             r#"SELECT
@@ -133,7 +454,11 @@ impl UploadService {
 
         // Process each pending/paused upload
         for row in rows {
-            if let Err(e) = self.process_upload(&row).await {
+            if cancel.is_cancelled() {
+                tracing::info!("UploadService: cycle cancelled");
+                return;
+            }
+            if let Err(e) = self.process_upload(&row, cancel).await {
                 tracing::error!(
                     "UploadService: upload failed for {} on {}: {e}",
                     row.recording_id,
@@ -145,7 +470,7 @@ impl UploadService {
         tracing::info!("UploadService: cycle complete");
     }
 
-    async fn process_upload(&self, row: &PendingUpload) -> anyhow::Result<()> {
+    async fn process_upload(&self, row: &PendingUpload, cancel: CancelToken<'_>) -> anyhow::Result<()> {
         let title = row
             .custom_title
             .as_deref()
@@ -175,7 +500,7 @@ impl UploadService {
                     }
                 };
 
-                youtube::run_upload(
+                let result = youtube::run_upload(
                     &self.pool,
                     &self.ws_clients,
                     row.recording_id,
@@ -186,8 +511,11 @@ impl UploadService {
                     &row.visibility,
                     row.upload_uri.clone(),
                     &token.access_token,
+                    cancel,
                 )
-                .await?;
+                .await;
+                self.emit_outcome(row, &title, &result);
+                self.record_history(row, &result?).await;
             }
             "facebook" => {
                 let fb_status = self.facebook_connector.get_status().await;
@@ -223,7 +551,7 @@ impl UploadService {
                     return Ok(());
                 }
 
-                facebook::run_upload(
+                let result = facebook::run_upload(
                     &self.pool,
                     &self.ws_clients,
                     row.recording_id,
@@ -236,8 +564,11 @@ impl UploadService {
                     row.progress_bytes,
                     &token.access_token,
                     &page_id,
+                    cancel,
                 )
-                .await?;
+                .await;
+                self.emit_outcome(row, &title, &result);
+                self.record_history(row, &result?).await;
             }
             other => {
                 tracing::warn!("UploadService: unknown platform '{other}' — skipping");
@@ -1,16 +1,50 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::ws::Message;
 use tokio::io::AsyncReadExt;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::server::websocket::{
-    broadcast_upload_completed, broadcast_upload_failed, broadcast_upload_progress,
+    broadcast_upload_completed, broadcast_upload_failed, broadcast_upload_progress, WsClientSender,
 };
 
-const CHUNK_SIZE: u64 = 8 * 1024 * 1024; // 8 MB
+/// YouTube requires every chunk except the last to be a multiple of 256 KiB.
+const CHUNK_QUANTUM: u64 = 256 * 1024;
+const INITIAL_CHUNK_SIZE: u64 = 8 * 1024 * 1024; // 8 MB
+const MIN_CHUNK_SIZE: u64 = 1024 * 1024; // 1 MB
+const MAX_CHUNK_SIZE: u64 = 64 * 1024 * 1024; // 64 MB
+/// How long each chunk should take on the observed link.
+const TARGET_CHUNK_SECS: f64 = 10.0;
+
+/// Picks the next chunk size from the throughput of the previous one, so
+/// fast links make fewer round-trips and slow links don't stall on one PUT.
+struct ChunkSizer {
+    size: u64,
+}
+
+impl ChunkSizer {
+    fn new() -> Self {
+        Self { size: INITIAL_CHUNK_SIZE }
+    }
+
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Feed back how long `bytes` took. Growth and shrinkage are capped at 2×
+    /// per step so a single outlier doesn't swing the size wildly.
+    fn observe(&mut self, bytes: u64, elapsed: std::time::Duration) {
+        let secs = elapsed.as_secs_f64();
+        if bytes == 0 || secs <= 0.0 {
+            return;
+        }
+        let ideal = (bytes as f64 / secs * TARGET_CHUNK_SECS) as u64;
+        let bounded = ideal.clamp(self.size / 2, self.size.saturating_mul(2));
+        let rounded = (bounded / CHUNK_QUANTUM) * CHUNK_QUANTUM;
+        self.size = rounded.clamp(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE);
+    }
+}
 
 pub struct UploadChunkResult {
     pub bytes_uploaded: u64,
@@ -104,7 +138,8 @@ pub async fn query_upload_offset(
     ))
 }
 
-/// Upload a chunk of the file to YouTube.
+/// Upload a chunk of the file to YouTube. `chunk_size` must be a multiple
+/// of 256 KiB unless the chunk reaches the end of the file.
 /// Returns bytes_uploaded (cumulative), done flag, and video_id on completion.
 pub async fn upload_chunk(
     client: &reqwest::Client,
@@ -112,8 +147,9 @@ pub async fn upload_chunk(
     file_path: &str,
     offset: u64,
     file_size: u64,
+    chunk_size: u64,
 ) -> anyhow::Result<UploadChunkResult> {
-    let end = (offset + CHUNK_SIZE).min(file_size);
+    let end = (offset + chunk_size).min(file_size);
     let chunk_len = end - offset;
 
     let mut file = tokio::fs::File::open(file_path).await?;
@@ -164,11 +200,27 @@ pub async fn upload_chunk(
     ))
 }
 
+/// Cancel a resumable upload session so YouTube discards the partial file.
+/// YouTube answers a successful cancel with 499; 404 means it is already gone.
+pub async fn cancel_upload(client: &reqwest::Client, upload_uri: &str) -> anyhow::Result<()> {
+    let resp = client
+        .delete(upload_uri)
+        .header("Content-Length", "0")
+        .send()
+        .await?;
+    let status = resp.status().as_u16();
+    if status == 499 || status == 404 || resp.status().is_success() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!("cancel_upload: unexpected status {status}"))
+}
+
 /// Run the full YouTube resumable upload for a recording.
 /// Handles initiation, chunking, progress broadcasting, and completion.
+/// Returns the new video ID (empty if YouTube didn't report one).
 pub async fn run_upload(
     pool: &sqlx::PgPool,
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     file_path: &str,
     file_size: i64,
@@ -177,8 +229,9 @@ pub async fn run_upload(
     visibility: &str,
     existing_uri: Option<String>,
     token: &str,
-) -> anyhow::Result<()> {
-    let client = reqwest::Client::new();
+    cancel: super::CancelToken<'_>,
+) -> anyhow::Result<String> {
+    let client = crate::http::http_client();
     let total = file_size as u64;
 
     // Step 1: get or create the upload URI
@@ -195,16 +248,24 @@ pub async fn run_upload(
             )
             .await?;
 
-            // Persist URI so a crash can resume
-            sqlx::query(
+            // Persist URI so a crash can resume. The state guard keeps a
+            // `cancel_all` that ran during initiation from being undone.
+            let persisted = sqlx::query(
                 "UPDATE recording_uploads \
                  SET upload_uri = $1, state = 'uploading', started_at = NOW(), updated_at = NOW() \
-                 WHERE recording_id = $2 AND platform = 'youtube'",
+                 WHERE recording_id = $2 AND platform = 'youtube' \
+                   AND state IN ('pending', 'paused', 'uploading')",
             )
             .bind(&uri)
             .bind(recording_id)
             .execute(pool)
             .await?;
+            if persisted.rows_affected() == 0 || cancel.is_cancelled() {
+                if let Err(e) = cancel_upload(&client, &uri).await {
+                    tracing::warn!("Cancelling fresh YouTube session for {recording_id} failed: {e}");
+                }
+                return Err(super::UploadCancelled.into());
+            }
 
             uri
         }
@@ -217,28 +278,48 @@ pub async fn run_upload(
 
     if offset >= total {
         // Already complete (e.g. recovered after crash with full upload)
-        return finalize_completed(pool, ws_clients, recording_id, "youtube", total, None).await;
+        finalize_completed(pool, ws_clients, recording_id, "youtube", total, None).await?;
+        return Ok(String::new());
     }
 
-    // Step 3: upload in chunks
+    // Step 3: upload in chunks, adapting the size to the observed throughput
+    let mut sizer = ChunkSizer::new();
     loop {
-        match upload_chunk(&client, &upload_uri, file_path, offset, total).await {
+        if cancel.is_cancelled() {
+            return Err(super::UploadCancelled.into());
+        }
+        let chunk_size = sizer.size();
+        let started = std::time::Instant::now();
+        match upload_chunk(&client, &upload_uri, file_path, offset, total, chunk_size).await {
             Ok(result) => {
+                sizer.observe(result.bytes_uploaded - offset, started.elapsed());
                 offset = result.bytes_uploaded;
 
                 // Persist progress
-                sqlx::query(
+                let persisted = sqlx::query(
                     "UPDATE recording_uploads \
                      SET progress_bytes = $1, total_bytes = $2, updated_at = NOW() \
-                     WHERE recording_id = $3 AND platform = 'youtube'",
+                     WHERE recording_id = $3 AND platform = 'youtube' \
+                       AND state IN ('pending', 'paused', 'uploading')",
                 )
                 .bind(offset as i64)
                 .bind(total as i64)
                 .bind(recording_id)
                 .execute(pool)
                 .await?;
+                if persisted.rows_affected() == 0 && !result.done {
+                    return Err(super::UploadCancelled.into());
+                }
 
-                broadcast_upload_progress(ws_clients, recording_id, "youtube", offset as i64, total as i64).await;
+                broadcast_upload_progress(
+                    ws_clients,
+                    recording_id,
+                    "youtube",
+                    offset as i64,
+                    total as i64,
+                    chunk_size as i64,
+                )
+                .await;
 
                 if result.done {
                     let video_id = result.video_id.as_deref();
@@ -261,15 +342,18 @@ pub async fn run_upload(
                         "YouTube upload completed for recording {recording_id}: {:?}",
                         video_id
                     );
-                    return Ok(());
+                    return Ok(result.video_id.unwrap_or_default());
                 }
             }
+            // The session was deleted by `cancel_all` mid-chunk.
+            Err(_) if cancel.is_cancelled() => return Err(super::UploadCancelled.into()),
             Err(e) => {
                 tracing::error!("YouTube upload chunk error for {recording_id}: {e}");
                 sqlx::query(
                     "UPDATE recording_uploads \
                      SET state = 'failed', error = $1, updated_at = NOW() \
-                     WHERE recording_id = $2 AND platform = 'youtube'",
+                     WHERE recording_id = $2 AND platform = 'youtube' \
+                       AND state IN ('pending', 'paused', 'uploading')",
                 )
                 .bind(e.to_string())
                 .bind(recording_id)
@@ -284,7 +368,7 @@ pub async fn run_upload(
 
 async fn finalize_completed(
     pool: &sqlx::PgPool,
-    ws_clients: &Arc<RwLock<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>,
+    ws_clients: &Arc<RwLock<HashMap<Uuid, WsClientSender>>>,
     recording_id: Uuid,
     platform: &str,
     total_bytes: u64,
@@ -315,3 +399,50 @@ async fn finalize_completed(
     .await;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn chunk_size_grows_at_most_2x_on_fast_links() {
+        let mut sizer = ChunkSizer::new();
+        assert_eq!(sizer.size(), INITIAL_CHUNK_SIZE);
+        sizer.observe(8 * MIB, Duration::from_secs(1));
+        assert_eq!(sizer.size(), 16 * MIB);
+        for _ in 0..5 {
+            sizer.observe(sizer.size(), Duration::from_millis(100));
+        }
+        assert_eq!(sizer.size(), MAX_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn chunk_size_shrinks_at_most_2x_on_slow_links() {
+        let mut sizer = ChunkSizer::new();
+        sizer.observe(8 * MIB, Duration::from_secs(100));
+        assert_eq!(sizer.size(), 4 * MIB);
+        for _ in 0..5 {
+            sizer.observe(sizer.size(), Duration::from_secs(100));
+        }
+        assert_eq!(sizer.size(), MIN_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn chunk_size_stays_a_multiple_of_the_quantum() {
+        let mut sizer = ChunkSizer::new();
+        sizer.observe(9 * MIB + 100, Duration::from_secs(10));
+        assert_eq!(sizer.size(), 9 * MIB);
+        assert_eq!(sizer.size() % CHUNK_QUANTUM, 0);
+    }
+
+    #[test]
+    fn empty_observations_are_ignored() {
+        let mut sizer = ChunkSizer::new();
+        sizer.observe(0, Duration::from_secs(1));
+        sizer.observe(8 * MIB, Duration::ZERO);
+        assert_eq!(sizer.size(), INITIAL_CHUNK_SIZE);
+    }
+}
@@ -22,6 +22,8 @@ export interface BroadlinkDevice {
 	host: string;
 	mac: string;
 	isDefault: boolean;
+	/** UDP port found by a probe; null means the default port 80. */
+	port: number | null;
 }
 
 export interface BroadlinkCommand {
@@ -46,6 +48,7 @@ export async function addDevice(body: {
 	mac: string;
 	deviceType: string;
 	model?: string;
+	port?: number;
 }): Promise<BroadlinkDevice> {
 	const res = await apiFetch('/api/connectors/broadlink/devices', {
 		method: 'POST',
@@ -60,6 +63,17 @@ export async function removeDevice(id: string): Promise<void> {
 	if (!res.ok) throw new Error(`Failed to remove device: ${res.status}`);
 }
 
+/** Probe which UDP port a device answers on (80, then `ports`) and remember it. */
+export async function probeDevicePort(id: string, ports: number[] = []): Promise<number | null> {
+	const res = await apiFetch(`/api/connectors/broadlink/devices/${id}/probe-port`, {
+		method: 'POST',
+		body: JSON.stringify({ ports })
+	});
+	if (!res.ok) throw new Error(`Failed to probe device port: ${res.status}`);
+	const { port } = (await res.json()) as { port: number | null };
+	return port;
+}
+
 export async function triggerDiscover(): Promise<void> {
 	const res = await apiFetch('/api/connectors/broadlink/discover', { method: 'POST' });
 	if (!res.ok) throw new Error(`Failed to start discovery: ${res.status}`);
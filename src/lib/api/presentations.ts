@@ -41,10 +41,14 @@ export async function listFolders(): Promise<PptFolder[]> {
 	return result.success && result.data ? result.data : [];
 }
 
-export async function addFolder(path: string, name: string): Promise<PptFolder | null> {
+export async function addFolder(
+	path: string,
+	name: string,
+	extensions?: string[]
+): Promise<PptFolder | null> {
 	const result = await apiFetch<PptFolder>('/api/ppt/folders', {
 		method: 'POST',
-		body: JSON.stringify({ path, name }),
+		body: JSON.stringify({ path, name, extensions }),
 	});
 	return result.success && result.data ? result.data : null;
 }
@@ -2,6 +2,8 @@ import type { ConnectorDefinition } from './types.js';
 
 export interface BroadlinkConfig {
 	enabled: boolean;
+	interfaceInclude?: string[];
+	interfaceExclude?: string[];
 }
 
 export const broadlinkDefinition: ConnectorDefinition<BroadlinkConfig> = {
@@ -58,6 +58,7 @@ export const FlagUploadItemSchema = z.object({
   youtube_visibility: z.enum(['private', 'unlisted', 'public']).optional(),
   facebook_visibility: z.enum(['ONLY_ME', 'FRIENDS', 'EVERYONE']).optional(),
   platforms: z.array(z.string()),
+  use_description_template: z.boolean().optional(),
 });
 
 export type FlagUploadItem = z.infer<typeof FlagUploadItemSchema>;
@@ -52,6 +52,7 @@ export const KeynoteStatusSchema = z.object({
   currentSlide: z.number().nullable(),
   totalSlides: z.number().nullable(),
   documentName: z.string().nullable(),
+  blanked: z.boolean().optional(),
 });
 
 export const PptFileSchema = z.object({
@@ -66,6 +67,13 @@ export const PptFolderSchema = z.object({
   path: z.string(),
   name: z.string(),
   sortOrder: z.number().int(),
+  extensions: z.array(z.string()),
+});
+
+export const PptFolderOverlapSchema = z.object({
+  kind: z.enum(['duplicate', 'nested']),
+  path: z.string(),
+  otherPath: z.string(),
 });
 
 export const CronJobSchema = z.object({
@@ -117,6 +125,7 @@ export const ParagraphContentSchema = z
 export const SlideContentSchema = z.object({
   index: z.number().int().positive(),
   paragraphs: z.array(ParagraphContentSchema),
+  notes: z.string().default(''),
 });
 
 export const PresenterStateSchema = z.object({
@@ -196,6 +205,20 @@ export const WsMessageSchema = z.discriminatedUnion('type', [
     isStreaming: z.boolean(),
     isRecording: z.boolean(),
   }),
+  z.object({
+    type: z.literal('obs.scene'),
+    sceneName: z.string(),
+  }),
+  z.object({
+    type: z.literal('caption.changed'),
+    caption: z
+      .object({
+        text: z.string(),
+        type: z.string().nullable(),
+        expiresAt: z.string().nullable(),
+      })
+      .nullable(),
+  }),
   z.object({
     type: z.literal('broadlink.device.discovered'),
     device: z.object({
@@ -210,6 +233,36 @@ export const WsMessageSchema = z.discriminatedUnion('type', [
     type: z.literal('broadlink.learn.result'),
     code: z.string().nullable(),
     error: z.string().nullable(),
+    quality: z
+      .object({
+        score: z.number(),
+        transitions: z.number(),
+        warnings: z.array(z.string()),
+      })
+      .nullish(),
+  }),
+  z.object({
+    type: z.literal('broadlink.dry_run'),
+    result: z.object({
+      commandId: z.string().uuid(),
+      name: z.string(),
+      slug: z.string(),
+      codeType: z.string(),
+      codeBytes: z.number().int(),
+      deviceId: z.string().uuid(),
+      deviceName: z.string(),
+      host: z.string(),
+      reachable: z.boolean(),
+    }),
+  }),
+  z.object({
+    type: z.literal('broadlink.sequence.progress'),
+    sequenceId: z.string().uuid(),
+    step: z.number().int(),
+    total: z.number().int(),
+    commandId: z.string().uuid(),
+    success: z.boolean(),
+    error: z.string().nullable(),
   }),
   z.object({
     type: z.literal('keynote.status'),
@@ -229,6 +282,7 @@ export const WsMessageSchema = z.discriminatedUnion('type', [
     platform: z.string(),
     progressBytes: z.number().int(),
     totalBytes: z.number().int(),
+    chunkSize: z.number().int().optional(),
   }),
   z.object({
     type: z.literal('upload.completed'),
@@ -268,15 +322,22 @@ export const WsMessageSchema = z.discriminatedUnion('type', [
   z.object({ type: z.literal('cron_jobs.update'), job: CronJobSchema }),
   // ── PPT (WS command responses) ─────────────────────────────────────────────
   z.object({ type: z.literal('ppt.folders.list'), folders: z.array(PptFolderSchema) }),
-  z.object({ type: z.literal('ppt.folders.add'), folder: PptFolderSchema.nullable() }),
+  z.object({
+    type: z.literal('ppt.folders.add'),
+    folder: PptFolderSchema.nullable(),
+    warnings: z.array(PptFolderOverlapSchema).default([]),
+  }),
+  z.object({ type: z.literal('ppt.folders.validate'), overlaps: z.array(PptFolderOverlapSchema) }),
   // ── Presentation settings (push + command response) ────────────────────────
   z.object({ type: z.literal('presentation.settings'), useWebPresenter: z.boolean() }),
+  z.object({ type: z.literal('presentation.poll_interval'), intervalMs: z.number().int().positive() }),
   // ── Presenter (push + command responses) ───────────────────────────────────
   z.object({ type: z.literal('presenter.state'), state: PresenterStateSchema }),
   z.object({
     type: z.literal('presenter.slide_changed'),
     currentSlide: z.number().int().nonnegative(),
     totalSlides: z.number().int().nonnegative(),
+    notes: z.string().default(''),
   }),
   // ── Connected clients ───────────────────────────────────────────────────────
   z.object({ type: z.literal('clients.updated'), clients: z.array(WsClientInfoSchema) }),
@@ -8,9 +8,16 @@ export interface BroadlinkDiscoveredDevice {
 	model: string | null;
 }
 
+export interface BroadlinkCodeQuality {
+	score: number;
+	transitions: number;
+	warnings: string[];
+}
+
 export interface BroadlinkLearnResult {
 	code: string | null;
 	error: string | null;
+	quality?: BroadlinkCodeQuality | null;
 }
 
 /** Devices pushed via WS during a discover scan. Reset before each scan. */
@@ -37,6 +37,10 @@ export interface FacebookConfig {
 
 export interface BroadlinkConfig {
   enabled: boolean;
+  /** Interface name substrings to use even if normally skipped as virtual. */
+  interfaceInclude?: string[];
+  /** Interface name substrings never used for discovery or sending. */
+  interfaceExclude?: string[];
 }
 
 export interface ObsBadgeConfig {
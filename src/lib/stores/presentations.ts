@@ -8,6 +8,7 @@ export interface PptFolder {
 	path: string;
 	name: string;
 	sortOrder: number;
+	extensions: string[];
 }
 
 export const keynoteStatus = writable<KeynoteStatus>({
@@ -81,6 +81,13 @@ export interface BibleReference {
   translation: string;
 }
 
+// Which parts of the verse HTML the backend keeps (default: strip everything)
+export interface BibleCleanOptions {
+  keepFootnotes?: boolean;
+  keepLineBreaks?: boolean;
+  keepHeadings?: boolean;
+}
+
 // Translation types
 export type V2Translation = 'UF_v2' | 'RUF_v2';
 export type LegacyTranslationType = 'RUF' | 'KG' | 'KNB' | 'SZIT' | 'BD' | 'STL';
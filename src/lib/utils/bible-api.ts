@@ -1,11 +1,14 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { getApiConfig, buildProxiedUrl } from '$lib/config/bible-api';
 import type {
   V2SuggestApiResponse,
+  V2Verse,
   LegacySearchResponse,
   LegacySuggestion,
   BibleVerse,
   BibleTranslation,
+  BibleCleanOptions,
 } from '$lib/types/bible';
 import { isV2Translation, getV2TranslationCode } from '$lib/types/bible';
 
@@ -28,11 +31,27 @@ const buildBrowserUrl = (url: string, useCorsProxy: boolean, proxyUrl?: string):
 
 class BibleApiService {
   private config = getApiConfig();
+  // Monotonic id so the backend can drop superseded in-flight fetches
+  private requestSeq = 0;
+
+  constructor() {
+    this.syncBackendApiUrl();
+  }
+
+  /**
+   * Tell the backend which V2 API to use for its own lookups (scripture captions)
+   */
+  private syncBackendApiUrl() {
+    if (!isTauriApp()) return;
+    invoke('set_bible_api_url', { url: this.config.v2ApiUrl }).catch((e) =>
+      console.warn('Failed to sync Bible API URL:', e)
+    );
+  }
 
   /**
    * Fetch verses using V2 API (immediate results, no suggestion needed)
    */
-  async fetchV2(reference: string, translation: string): Promise<{ verses: BibleVerse[]; label: string }> {
+  async fetchV2(reference: string, translation: string, caller?: string): Promise<{ verses: BibleVerse[]; label: string }> {
     // V2 API expects translation without _v2 suffix
     const apiTranslation = getV2TranslationCode(translation as any);
 
@@ -41,6 +60,8 @@ class BibleApiService {
         reference,
         translation: apiTranslation,
         apiUrl: this.config.v2ApiUrl,
+        requestId: ++this.requestSeq,
+        caller,
       });
 
       return {
@@ -75,10 +96,80 @@ class BibleApiService {
     }
   }
 
+  /**
+   * Fetch a verse (or short range) by V2 numeric IDs — used for prev/next navigation.
+   * The book must have appeared in an earlier V2 response; unseen book ids are rejected.
+   */
+  async fetchV2ByIds(
+    bookId: number,
+    chapter: number,
+    verse: number,
+    translation: string,
+    verseTo?: number,
+    prefetchAdjacent = false
+  ): Promise<BibleVerse[]> {
+    const apiTranslation = getV2TranslationCode(translation as any);
+    const verses = await invoke<V2Verse[]>('fetch_bible_by_ids', {
+      bookId,
+      chapter,
+      verse,
+      verseTo,
+      translation: apiTranslation,
+      apiUrl: this.config.v2ApiUrl,
+      prefetchAdjacent,
+    });
+    return verses.map(v => ({
+      chapter: v.chapter,
+      verse: v.verse,
+      text: v.text,
+      editing: false,
+    }));
+  }
+
+  /**
+   * Fetch a long passage via V2, receiving verses as they are processed (Tauri only).
+   * Resolves with the label once the backend reports completion.
+   */
+  async streamV2(
+    reference: string,
+    translation: string,
+    onVerse: (verse: BibleVerse, index: number) => void
+  ): Promise<{ label: string; count: number }> {
+    const streamId = ++this.requestSeq;
+    let result = { label: reference, count: 0 };
+    const unlistenVerse = await listen<{ streamId: number; index: number; verse: V2Verse }>(
+      'bible-verse',
+      ({ payload }) => {
+        if (payload.streamId !== streamId) return;
+        const { chapter, verse, text } = payload.verse;
+        onVerse({ chapter, verse, text, editing: false }, payload.index);
+      }
+    );
+    const unlistenComplete = await listen<{ streamId: number; label: string; count: number }>(
+      'bible-complete',
+      ({ payload }) => {
+        if (payload.streamId !== streamId) return;
+        result = { label: payload.label, count: payload.count };
+      }
+    );
+    try {
+      await invoke('fetch_bible_v2_stream', {
+        streamId,
+        reference,
+        translation: getV2TranslationCode(translation as any),
+        apiUrl: this.config.v2ApiUrl,
+      });
+      return result;
+    } finally {
+      unlistenVerse();
+      unlistenComplete();
+    }
+  }
+
   /**
    * Fetch suggestions for legacy API (autocomplete)
    */
-  async fetchSuggestions(term: string): Promise<LegacySuggestion[]> {
+  async fetchSuggestions(term: string, caller?: string): Promise<LegacySuggestion[]> {
     if (!term || term.length < 2) {
       return [];
     }
@@ -87,6 +178,8 @@ class BibleApiService {
       return invoke<LegacySuggestion[]>('fetch_bible_suggestions', {
         term,
         apiUrl: this.config.legacyApiUrl,
+        requestId: ++this.requestSeq,
+        caller,
       });
     } else {
       // Browser fallback with CORS proxy
@@ -107,12 +200,14 @@ class BibleApiService {
   /**
    * Fetch verses using Legacy API
    */
-  async fetchLegacy(reference: string, translation: string): Promise<{ verses: BibleVerse[]; label: string }> {
+  async fetchLegacy(reference: string, translation: string, caller?: string): Promise<{ verses: BibleVerse[]; label: string }> {
     if (isTauriApp()) {
       const response = await invoke<LegacySearchResponse>('fetch_bible_legacy', {
         reference,
         translation,
         apiUrl: this.config.legacyApiUrl,
+        requestId: ++this.requestSeq,
+        caller,
       });
 
       return {
@@ -180,11 +275,93 @@ class BibleApiService {
   /**
    * Unified fetch method that auto-detects translation type
    */
-  async fetchVerses(reference: string, translation: BibleTranslation): Promise<{ verses: BibleVerse[]; label: string }> {
+  async fetchVerses(reference: string, translation: BibleTranslation, caller?: string): Promise<{ verses: BibleVerse[]; label: string }> {
     if (isV2Translation(translation)) {
-      return this.fetchV2(reference, translation);
+      return this.fetchV2(reference, translation, caller);
     } else {
-      return this.fetchLegacy(reference, translation);
+      return this.fetchLegacy(reference, translation, caller);
+    }
+  }
+
+  /**
+   * Fetch a passage as a single plain-text string (Tauri only)
+   */
+  async fetchPlaintext(
+    reference: string,
+    translation: BibleTranslation,
+    format: 'verses' | 'paragraph' | 'with_numbers' = 'paragraph',
+    clean?: BibleCleanOptions
+  ): Promise<string> {
+    return invoke<string>('fetch_bible_plaintext', {
+      reference,
+      translation,
+      apiUrl: isV2Translation(translation) ? this.config.v2ApiUrl : this.config.legacyApiUrl,
+      format,
+      clean,
+    });
+  }
+
+  /**
+   * Normalize a typed reference ("jn3.16" → "Jn 3,16") before fetching (Tauri only)
+   */
+  async normalizeReference(input: string): Promise<{ normalized: string; bookId: number | null }> {
+    return invoke('normalize_reference', { input });
+  }
+
+  /**
+   * Full-text search for a phrase via the legacy API (Tauri only)
+   */
+  async searchText(
+    query: string,
+    translation: string
+  ): Promise<{ reference: string; gepi: string; text: string }[]> {
+    return invoke('search_bible_text', {
+      query,
+      translation,
+      apiUrl: this.config.legacyApiUrl,
+    });
+  }
+
+  /**
+   * Fetch a passage from a curated (or given) list via the V2 API (Tauri only).
+   * The same seed always yields the same passage, e.g. the day number.
+   */
+  async fetchRandomVerse(
+    translation: string,
+    seed?: number,
+    references?: string[]
+  ): Promise<{ reference: string; label: string; verses: V2Verse[] }> {
+    return invoke('fetch_random_verse', {
+      translation: getV2TranslationCode(translation as any),
+      apiUrl: this.config.v2ApiUrl,
+      seed,
+      references,
+    });
+  }
+
+  /**
+   * Download a whole V2 translation for offline use (Tauri only).
+   * Calling again after an interruption resumes where it stopped.
+   */
+  async downloadTranslation(
+    translation: string,
+    onProgress?: (done: number, total: number, failed: number) => void
+  ): Promise<{ translation: string; totalChapters: number; skipped: number; downloaded: number; failed: string[] }> {
+    const apiTranslation = getV2TranslationCode(translation as any);
+    const unlisten = await listen<{ translation: string; done: number; total: number; failed: number }>(
+      'bible-download-progress',
+      ({ payload }) => {
+        if (payload.translation !== apiTranslation) return;
+        onProgress?.(payload.done, payload.total, payload.failed);
+      }
+    );
+    try {
+      return await invoke('download_bible_translation', {
+        translation: apiTranslation,
+        apiUrl: this.config.v2ApiUrl,
+      });
+    } finally {
+      unlisten();
     }
   }
 
@@ -199,6 +376,7 @@ class BibleApiService {
   }>) {
     if (config.v2ApiUrl) {
       this.config.v2ApiUrl = config.v2ApiUrl;
+      this.syncBackendApiUrl();
     }
     if (config.legacyApiUrl) {
       this.config.legacyApiUrl = config.legacyApiUrl;
@@ -200,7 +200,7 @@ function handleMessage(msg: ReturnType<typeof WsMessageSchema.parse>): void {
 			return exists ? list : [...list, msg.device];
 		});
 	} else if (msg.type === 'broadlink.learn.result') {
-		broadlinkLearnResult.set({ code: msg.code, error: msg.error });
+		broadlinkLearnResult.set({ code: msg.code, error: msg.error, quality: msg.quality });
 	} else if (msg.type === 'cron.youtube_pull') {
 		youtubeLiveActive.set(msg.hasLive);
 		youtubeState.update((s) => ({ ...s, isLive: msg.hasLive }));